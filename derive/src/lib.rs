@@ -0,0 +1,133 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Item, LitStr, parse_macro_input};
+
+mod model;
+
+/// Derives `atopio_extra::Table` for a struct or enum.
+///
+/// The table name is taken from `#[surreal(table = "...")]`, or defaults to the snake_case
+/// form of the type name (`UserProfile` becomes `user_profile`). The derive also adds an
+/// inherent `const fn table_name() -> &'static str` returning the same name.
+///
+/// Table names must not be empty or contain whitespace.
+#[proc_macro_derive(SurrealTable, attributes(surreal))]
+pub fn derive_surreal_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_surreal_table(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Adds the `atopio_extra` serde helpers to every record id field of a struct.
+///
+/// `#[surreal_model(id = "full")]` gives each field of type `RecordId`, `Option<RecordId>` or
+/// `Vec<RecordId>` the matching `#[serde(with = "...")]` attribute from `record_id_full`;
+/// `id = "object"` uses `record_id_object` instead. `id = "naked"` uses `record_id_naked` and
+/// also needs the table to read keys back into, as in
+/// `#[surreal_model(id = "naked", table = User)]` where `User` implements `Table`.
+///
+/// Other fields, and fields that already have `with`, `serialize_with` or `deserialize_with`,
+/// are left untouched. Any other field type that contains `RecordId`, such as
+/// `HashMap<RecordId, u32>`, is a compile error, so it cannot silently fall back to the default
+/// encoding. Put the attribute above `#[derive(Serialize, Deserialize)]`, so the derives see
+/// the added attributes.
+#[proc_macro_attribute]
+pub fn surreal_model(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut parsed = model::Args::default();
+    let parser = syn::meta::parser(|meta| parsed.parse(meta));
+    parse_macro_input!(args with parser);
+    let item = parse_macro_input!(input as Item);
+    let Item::Struct(item) = item else {
+        let err = syn::Error::new_spanned(&item, "`#[surreal_model]` only supports structs");
+        let err = err.to_compile_error();
+        return quote!(#item #err).into();
+    };
+    match model::expand(parsed, item.clone()) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => {
+            // Keep the struct so its uses do not report errors of their own.
+            let err = err.to_compile_error();
+            quote!(#item #err).into()
+        }
+    }
+}
+
+fn expand_surreal_table(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let table = match table_attribute(input)? {
+        Some(lit) => {
+            let value = lit.value();
+            if value.trim().is_empty() {
+                return Err(syn::Error::new(lit.span(), "table name must not be empty"));
+            }
+            if value.chars().any(char::is_whitespace) {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "table name must not contain whitespace",
+                ));
+            }
+            value
+        }
+        None => to_snake_case(&ident.to_string()),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::atopio_extra::Table for #ident #ty_generics #where_clause {
+            const NAME: &'static str = #table;
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Returns the SurrealDB table this type is stored in.
+            pub const fn table_name() -> &'static str {
+                #table
+            }
+        }
+    })
+}
+
+fn table_attribute(input: &DeriveInput) -> syn::Result<Option<LitStr>> {
+    let mut table = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("surreal")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("table") {
+                if table.is_some() {
+                    return Err(meta.error("duplicate `table` attribute"));
+                }
+                table = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `surreal` attribute, expected `table`"))
+            }
+        })?;
+    }
+    Ok(table)
+}
+
+fn to_snake_case(name: &str) -> String {
+    let name = name.strip_prefix("r#").unwrap_or(name);
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1).copied();
+            let boundary = match prev {
+                Some('_') => false,
+                Some(p) if p.is_lowercase() || p.is_ascii_digit() => true,
+                Some(p) if p.is_uppercase() => next.is_some_and(char::is_lowercase),
+                _ => false,
+            };
+            if boundary {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
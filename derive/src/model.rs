@@ -0,0 +1,231 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{ToTokens, quote};
+use syn::Meta;
+use syn::meta::ParseNestedMeta;
+use syn::{Field, Fields, GenericArgument, ItemStruct, LitStr, Path, PathArguments, Type};
+
+/// How `#[surreal_model]` writes record ids.
+#[derive(Clone, Copy)]
+enum Mode {
+    Full,
+    Naked,
+    Object,
+}
+
+impl Mode {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Naked => "naked",
+            Self::Object => "object",
+        }
+    }
+}
+
+/// The record id shapes a field can have.
+#[derive(Clone, Copy)]
+enum Shape {
+    Plain,
+    Option,
+    Vec,
+}
+
+#[derive(Default)]
+pub(crate) struct Args {
+    mode: Option<(Mode, LitStr)>,
+    table: Option<Path>,
+}
+
+impl Args {
+    pub(crate) fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("id") {
+            if self.mode.is_some() {
+                return Err(meta.error("duplicate `id` argument"));
+            }
+            let lit = meta.value()?.parse::<LitStr>()?;
+            let mode = match lit.value().as_str() {
+                "full" => Mode::Full,
+                "naked" => Mode::Naked,
+                "object" => Mode::Object,
+                _ => {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "unsupported id mode, expected \"full\", \"naked\" or \"object\"",
+                    ));
+                }
+            };
+            self.mode = Some((mode, lit));
+            Ok(())
+        } else if meta.path.is_ident("table") {
+            if self.table.is_some() {
+                return Err(meta.error("duplicate `table` argument"));
+            }
+            self.table = Some(meta.value()?.parse::<Path>()?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported `surreal_model` argument, expected `id` or `table`"))
+        }
+    }
+}
+
+pub(crate) fn expand(args: Args, mut item: ItemStruct) -> syn::Result<TokenStream> {
+    let Some((mode, lit)) = args.mode else {
+        return Err(syn::Error::new(
+            item.ident.span(),
+            "missing `id` argument, as in `#[surreal_model(id = \"full\")]`",
+        ));
+    };
+    if let (Some(table), Mode::Full | Mode::Object) = (&args.table, mode) {
+        return Err(syn::Error::new_spanned(
+            table,
+            format!(
+                "`table` is only used with `id = \"naked\"`, not `{}`",
+                lit.value()
+            ),
+        ));
+    }
+
+    let fields = match &mut item.fields {
+        Fields::Named(fields) => &mut fields.named,
+        Fields::Unnamed(fields) => &mut fields.unnamed,
+        Fields::Unit => return Ok(item.into_token_stream()),
+    };
+    let mut errors: Option<syn::Error> = None;
+    for field in fields.iter_mut() {
+        if let Err(err) = rewrite_field(field, mode, args.table.as_ref()) {
+            match &mut errors {
+                Some(errors) => errors.combine(err),
+                None => errors = Some(err),
+            }
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(item.into_token_stream()),
+    }
+}
+
+fn rewrite_field(field: &mut Field, mode: Mode, table: Option<&Path>) -> syn::Result<()> {
+    if has_serde_with(field) {
+        return Ok(());
+    }
+    let Some(shape) = shape_of(&field.ty) else {
+        if mentions_record_id(&field.ty) {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                format!(
+                    "`#[surreal_model(id = \"{}\")]` supports `RecordId`, `Option<RecordId>` and \
+                     `Vec<RecordId>` fields; add `#[serde(with = \"...\")]` to this field",
+                    mode.name()
+                ),
+            ));
+        }
+        return Ok(());
+    };
+
+    let module = match (mode, shape) {
+        (Mode::Full, Shape::Plain) => "record_id_full",
+        (Mode::Full, Shape::Option) => "record_id_full::option",
+        (Mode::Full, Shape::Vec) => "record_id_full::vec",
+        (Mode::Object, Shape::Plain) => "record_id_object",
+        (Mode::Object, Shape::Option) => "record_id_object::option",
+        (Mode::Object, Shape::Vec) => "record_id_object::vec",
+        (Mode::Naked, Shape::Plain) => "record_id_naked",
+        (Mode::Naked, Shape::Option) => "record_id_naked::option",
+        (Mode::Naked, Shape::Vec) => "record_id_naked::vec",
+    };
+    let attr = match mode {
+        Mode::Full | Mode::Object => {
+            let with = format!("::atopio_extra::{module}");
+            quote!(#[serde(with = #with)])
+        }
+        Mode::Naked => {
+            let Some(table) = table else {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    "`id = \"naked\"` needs `table = ...` to read keys back into record ids, \
+                     as in `#[surreal_model(id = \"naked\", table = User)]`",
+                ));
+            };
+            let table = table.to_token_stream().to_string();
+            let serialize = format!("::atopio_extra::{module}::serialize");
+            let deserialize = format!("::atopio_extra::{module}::deserialize_for::<{table}, _>");
+            quote!(#[serde(serialize_with = #serialize, deserialize_with = #deserialize)])
+        }
+    };
+    field.attrs.push(syn::parse_quote!(#attr));
+    Ok(())
+}
+
+/// Returns `true` if the field already picks its own serde functions.
+fn has_serde_with(field: &Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .any(|attr| match &attr.meta {
+            Meta::List(list) => list.tokens.clone().into_iter().any(|token| {
+                matches!(
+                    &token,
+                    TokenTree::Ident(ident)
+                        if ident == "with" || ident == "serialize_with" || ident == "deserialize_with"
+                )
+            }),
+            _ => false,
+        })
+}
+
+fn shape_of(ty: &Type) -> Option<Shape> {
+    if is_record_id(ty) {
+        return Some(Shape::Plain);
+    }
+    let (name, inner) = single_generic(ty)?;
+    if !is_record_id(inner) {
+        return None;
+    }
+    match name.as_str() {
+        "Option" => Some(Shape::Option),
+        "Vec" => Some(Shape::Vec),
+        _ => None,
+    }
+}
+
+fn is_record_id(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "RecordId" && s.arguments.is_none()),
+        _ => false,
+    }
+}
+
+/// Splits `Name<Inner>` into the last path segment's name and its only type argument.
+fn single_generic(ty: &Type) -> Option<(String, &Type)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.iter().collect::<Vec<_>>().as_slice() {
+        [GenericArgument::Type(inner)] => Some((segment.ident.to_string(), inner)),
+        _ => None,
+    }
+}
+
+fn mentions_record_id(ty: &Type) -> bool {
+    ty.to_token_stream()
+        .into_iter()
+        .any(|token| mentions(&token))
+}
+
+fn mentions(token: &TokenTree) -> bool {
+    match token {
+        TokenTree::Ident(ident) => ident == "RecordId",
+        TokenTree::Group(group) => group.stream().into_iter().any(|t| mentions(&t)),
+        _ => false,
+    }
+}
@@ -0,0 +1,145 @@
+//! An axum extractor for [`TypedId`] path parameters.
+//!
+//! Enabled by the `axum` feature. Use this module's [`Path`] in place of axum's to take a typed
+//! id straight from the URL:
+//!
+//! ```
+//! use atopio_extra::axum::Path;
+//! use atopio_extra::{Table, TypedId};
+//! use axum::{Router, routing::get};
+//!
+//! struct User;
+//!
+//! impl Table for User {
+//!     const NAME: &'static str = "user";
+//! }
+//!
+//! async fn show(Path(id): Path<TypedId<User>>) -> String {
+//!     id.to_string()
+//! }
+//!
+//! let app: Router = Router::new().route("/users/{id}", get(show));
+//! ```
+//!
+//! The parameter may be a naked key (`/users/abc123`, `/users/42`) or a full id
+//! (`/users/user:abc123`). Naked keys are read like [`crate::record_id_naked`] reads them, so
+//! `42` is the integer key. A key containing `:` has to be given as a full id, as in
+//! `/users/user:⟨a:b⟩`. Anything else is rejected with a `400 Bad Request` and a JSON body:
+//!
+//! ```json
+//! {"error": "table_mismatch", "message": "expected table \"user\", got \"post\""}
+//! ```
+
+use crate::record_id_naked::parse_key;
+use crate::typed_id::TableMismatch;
+use crate::{Table, TypedId};
+use ::axum::extract::FromRequestParts;
+use ::axum::extract::rejection::PathRejection;
+use ::axum::http::request::Parts;
+use ::axum::http::{HeaderValue, StatusCode, header};
+use ::axum::response::{IntoResponse, Response};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+use surrealdb::RecordId;
+
+/// Extracts a [`TypedId`] from the route's single path parameter.
+///
+/// See the [module documentation](self) for the accepted forms.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<T>(pub T);
+
+impl<T> Deref for Path<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Path<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T, S> FromRequestParts<S> for Path<TypedId<T>>
+where
+    T: Table,
+    S: Send + Sync,
+{
+    type Rejection = TypedIdRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let ::axum::extract::Path(text) =
+            ::axum::extract::Path::<String>::from_request_parts(parts, state)
+                .await
+                .map_err(TypedIdRejection::Path)?;
+        parse::<T>(&text).map(Path)
+    }
+}
+
+fn parse<T: Table>(text: &str) -> Result<TypedId<T>, TypedIdRejection> {
+    if let Ok(id) = RecordId::from_str(text) {
+        return TypedId::try_from(id).map_err(TypedIdRejection::TableMismatch);
+    }
+    if text.trim().is_empty() || text.contains(':') {
+        return Err(TypedIdRejection::Malformed(text.to_owned()));
+    }
+    Ok(TypedId::new(parse_key(text)))
+}
+
+/// The rejection returned by [`Path`] when the parameter is not an id in the expected table.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TypedIdRejection {
+    /// The route does not have exactly one path parameter, or it is not valid UTF-8. Responds
+    /// as axum's own `Path` extractor would.
+    Path(PathRejection),
+    /// The parameter is neither a naked key nor a full id. Holds the parameter.
+    Malformed(String),
+    /// The parameter is a full id in another table.
+    TableMismatch(TableMismatch),
+}
+
+impl TypedIdRejection {
+    /// Returns the `error` code written in the JSON body.
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Path(_) => "invalid_path",
+            Self::Malformed(_) => "malformed_id",
+            Self::TableMismatch(_) => "table_mismatch",
+        }
+    }
+}
+
+impl fmt::Display for TypedIdRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path(rejection) => fmt::Display::fmt(rejection, f),
+            Self::Malformed(text) => write!(f, "{text:?} is not a valid record id or key"),
+            Self::TableMismatch(mismatch) => fmt::Display::fmt(mismatch, f),
+        }
+    }
+}
+
+impl std::error::Error for TypedIdRejection {}
+
+impl IntoResponse for TypedIdRejection {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            Self::Path(rejection) => rejection.status(),
+            Self::Malformed(_) | Self::TableMismatch(_) => StatusCode::BAD_REQUEST,
+        };
+        let body = serde_json::json!({
+            "error": self.code(),
+            "message": self.to_string(),
+        });
+        let mut response = (status, body.to_string()).into_response();
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        response
+    }
+}
@@ -0,0 +1,169 @@
+use crate::parse::validate_record_id_str;
+use std::collections::BTreeMap;
+use std::fmt;
+use surrealdb::sql::{Array, Id, Object, Value};
+use surrealdb::{RecordId, RecordIdKey, Uuid};
+
+/// A builder for record ids, in particular those with array or object keys.
+///
+/// Array keys are built up one element at a time with [`push_key`](Self::push_key) and
+/// finished with [`build`](Self::build); object keys are built field by field with
+/// [`field`](Self::field) and finished with [`build_object`](Self::build_object):
+///
+/// ```
+/// use atopio_extra::RecordIdBuilder;
+///
+/// let reading = RecordIdBuilder::table("reading")
+///     .push_key("sensor1")
+///     .push_key(1717000000_i64)
+///     .build()
+///     .unwrap();
+/// assert_eq!(reading.to_string(), "reading:['sensor1', 1717000000]");
+///
+/// let event = RecordIdBuilder::table("event")
+///     .field("city", "london")
+///     .field("year", 2024)
+///     .build_object()
+///     .unwrap();
+/// assert_eq!(event.to_string(), "event:{ city: 'london', year: 2024 }");
+/// ```
+///
+/// Nothing is checked until the id is built, so the builder can be passed around and filled
+/// in from several places.
+#[derive(Debug, Clone)]
+pub struct RecordIdBuilder {
+    table: String,
+    key: Option<RecordIdKey>,
+    items: Vec<Value>,
+    fields: BTreeMap<String, Value>,
+}
+
+impl RecordIdBuilder {
+    /// Starts building an id in `table`.
+    pub fn table(table: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            key: None,
+            items: Vec::new(),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Sets a plain key, replacing any key set before.
+    pub fn key(mut self, key: impl Into<RecordIdKey>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets a uuid key.
+    pub fn uuid(self, uuid: Uuid) -> Self {
+        self.key(uuid)
+    }
+
+    /// Sets a freshly generated ULID as a string key.
+    pub fn ulid_now(self) -> Self {
+        self.key(RecordIdKey::from_inner(Id::ulid()))
+    }
+
+    /// Appends an element to an array key.
+    pub fn push_key(mut self, value: impl Into<Value>) -> Self {
+        self.items.push(value.into());
+        self
+    }
+
+    /// Sets a field of an object key, replacing any earlier value for the same name.
+    pub fn field(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.fields.insert(name.into(), value.into());
+        self
+    }
+
+    /// Builds an id with a plain key, or with an array key made of the pushed elements.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RecordIdBuildError`] if the table name is empty or not a valid table name, if
+    /// no key was given or the key is an empty string, or if object fields were added (use
+    /// [`build_object`](Self::build_object) for those) or both a plain key and array elements
+    /// were given.
+    pub fn build(self) -> Result<RecordId, RecordIdBuildError> {
+        check_table(&self.table)?;
+        if !self.fields.is_empty() || (self.key.is_some() && !self.items.is_empty()) {
+            return Err(RecordIdBuildError::ConflictingKey);
+        }
+        let key = match self.key {
+            Some(key) => key,
+            None if !self.items.is_empty() => {
+                RecordIdKey::from_inner(Id::Array(Array::from(self.items)))
+            }
+            None => return Err(RecordIdBuildError::EmptyKey),
+        };
+        if matches!(key.into_inner_ref(), Id::String(s) if s.is_empty()) {
+            return Err(RecordIdBuildError::EmptyKey);
+        }
+        Ok(RecordId::from_table_key(self.table, key))
+    }
+
+    /// Builds an id with an object key made of the added fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RecordIdBuildError`] if the table name is empty or not a valid table name, if
+    /// no fields were added, or if a plain key or array elements were given as well.
+    pub fn build_object(self) -> Result<RecordId, RecordIdBuildError> {
+        check_table(&self.table)?;
+        if self.key.is_some() || !self.items.is_empty() {
+            return Err(RecordIdBuildError::ConflictingKey);
+        }
+        if self.fields.is_empty() {
+            return Err(RecordIdBuildError::EmptyKey);
+        }
+        let key = RecordIdKey::from_inner(Id::Object(Object::from(self.fields)));
+        Ok(RecordId::from_table_key(self.table, key))
+    }
+}
+
+/// Checks the table with the rules of [`validate_record_id_str`], by writing out an id in it
+/// and reading that back, and also rejects whitespace.
+fn check_table(table: &str) -> Result<(), RecordIdBuildError> {
+    if table.is_empty() {
+        return Err(RecordIdBuildError::EmptyTable);
+    }
+    let written = RecordId::from_table_key(table, 0).to_string();
+    if table.chars().any(char::is_whitespace) || validate_record_id_str(&written).is_err() {
+        return Err(RecordIdBuildError::InvalidTable(table.to_owned()));
+    }
+    Ok(())
+}
+
+/// The reason a [`RecordIdBuilder`] could not build an id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RecordIdBuildError {
+    /// The table name is empty.
+    EmptyTable,
+    /// The table name contains whitespace, or an id in it would not parse back with
+    /// [`validate_record_id_str`], as with `123` or `1e5`, which read as numbers. Holds the
+    /// table name.
+    InvalidTable(String),
+    /// No key was given, or the key is an empty string.
+    EmptyKey,
+    /// More than one kind of key was given, or the wrong `build` method was called.
+    ConflictingKey,
+}
+
+impl fmt::Display for RecordIdBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyTable => f.write_str("table name must not be empty"),
+            Self::InvalidTable(table) => {
+                write!(f, "{table:?} is not a valid table name")
+            }
+            Self::EmptyKey => f.write_str("record id key must not be empty"),
+            Self::ConflictingKey => f.write_str(
+                "record id key must be exactly one of a plain key, array elements or object fields",
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RecordIdBuildError {}
@@ -0,0 +1,197 @@
+use crate::clock::{Clock, SystemClock};
+use crate::types::{SurrealJWTClaims, since_epoch};
+use std::fmt;
+use std::time::{Duration, SystemTime};
+use surrealdb::RecordId;
+
+/// A builder for [`SurrealJWTClaims`], returned by [`SurrealJWTClaims::builder`].
+///
+/// The times are worked out from the time the claims are built: `iat` is that time, `nbf` is
+/// [`not_before_in`](Self::not_before_in) after it and `exp` is [`ttl`](Self::ttl) after it.
+/// The token's level follows from the claims given: a subject record makes a record token,
+/// which needs a namespace and a database, a database alone makes a database token, which
+/// needs a namespace, and so on down to a root token with none of them.
+///
+/// ```
+/// use atopio_extra::TokenKind;
+/// use atopio_extra::types::SurrealJWTClaims;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let user: surrealdb::RecordId = ("user", "ada").into();
+/// let claims = SurrealJWTClaims::builder()
+///     .namespace("app")
+///     .database("main")
+///     .subject_record(&user)
+///     .access("user".to_owned())
+///     .jti("j1")
+///     .ttl(Duration::from_secs(3600))
+///     .build_at(UNIX_EPOCH + Duration::from_secs(1_000))
+///     .unwrap();
+/// assert_eq!((claims.iat, claims.nbf, claims.exp), (1_000, 1_000, 4_600));
+/// assert_eq!(claims.id, "user:ada");
+/// assert_eq!(claims.iss, "SurrealDB");
+/// assert_eq!(claims.kind(), TokenKind::Record);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SurrealJWTClaimsBuilder<T> {
+    iss: String,
+    jti: Option<String>,
+    ns: Option<String>,
+    db: Option<String>,
+    ac: Option<T>,
+    id: Option<String>,
+    ttl: Duration,
+    not_before: Duration,
+}
+
+impl<T> SurrealJWTClaimsBuilder<T> {
+    /// Starts with the issuer `SurrealDB`, as SurrealDB writes it, a one hour `ttl` and no
+    /// other claims.
+    pub fn new() -> Self {
+        Self {
+            iss: "SurrealDB".to_owned(),
+            jti: None,
+            ns: None,
+            db: None,
+            ac: None,
+            id: None,
+            ttl: Duration::from_secs(3600),
+            not_before: Duration::ZERO,
+        }
+    }
+
+    /// Sets the `iss` claim.
+    pub fn issuer(mut self, iss: impl Into<String>) -> Self {
+        self.iss = iss.into();
+        self
+    }
+
+    /// Sets the `NS` claim.
+    pub fn namespace(mut self, ns: impl Into<String>) -> Self {
+        self.ns = Some(ns.into());
+        self
+    }
+
+    /// Sets the `DB` claim.
+    pub fn database(mut self, db: impl Into<String>) -> Self {
+        self.db = Some(db.into());
+        self
+    }
+
+    /// Sets the `ID` claim to `id` in SurrealQL, such as `user:ada`.
+    pub fn subject_record(mut self, id: &RecordId) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    /// Sets the `AC` claim.
+    pub fn access(mut self, ac: T) -> Self {
+        self.ac = Some(ac);
+        self
+    }
+
+    /// Sets the `jti` claim.
+    pub fn jti(mut self, jti: impl Into<String>) -> Self {
+        self.jti = Some(jti.into());
+        self
+    }
+
+    /// Sets the `jti` claim to a random version 4 uuid.
+    ///
+    /// Enabled by the `uuid` feature.
+    #[cfg(feature = "uuid")]
+    pub fn jti_random(self) -> Self {
+        self.jti(uuid::Uuid::new_v4().to_string())
+    }
+
+    /// Sets how long after it is issued the token expires. Whole seconds are kept, as the
+    /// claims hold seconds.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets how long after it is issued the token becomes valid. Whole seconds are kept.
+    pub fn not_before_in(mut self, not_before: Duration) -> Self {
+        self.not_before = not_before;
+        self
+    }
+
+    /// Builds the claims, issued now.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`build_at`](Self::build_at).
+    pub fn build(self) -> Result<SurrealJWTClaims<T>, ClaimsBuildError> {
+        self.build_at(SystemClock.now())
+    }
+
+    /// Builds the claims, issued at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClaimsBuildError::MissingClaim`] if `AC` or `jti` was not given, or a claim
+    /// the token's level needs was not, such as `NS` for a database token, and
+    /// [`ClaimsBuildError::Overflow`] if `exp` or `nbf` would not fit in a `u64`.
+    pub fn build_at(self, now: SystemTime) -> Result<SurrealJWTClaims<T>, ClaimsBuildError> {
+        let needs_db = self.id.is_some();
+        let needs_ns = needs_db || self.db.is_some();
+        let ns = match self.ns {
+            Some(ns) => ns,
+            None if needs_ns => return Err(ClaimsBuildError::MissingClaim("NS")),
+            None => String::new(),
+        };
+        let db = match self.db {
+            Some(db) => db,
+            None if needs_db => return Err(ClaimsBuildError::MissingClaim("DB")),
+            None => String::new(),
+        };
+        let ac = self.ac.ok_or(ClaimsBuildError::MissingClaim("AC"))?;
+        let jti = self.jti.ok_or(ClaimsBuildError::MissingClaim("jti"))?;
+        let iat = since_epoch(now).as_secs();
+        let after = |claim, by: Duration| {
+            iat.checked_add(by.as_secs())
+                .ok_or(ClaimsBuildError::Overflow(claim))
+        };
+        Ok(SurrealJWTClaims {
+            iat,
+            nbf: after("nbf", self.not_before)?,
+            exp: after("exp", self.ttl)?,
+            iss: self.iss,
+            jti,
+            ns,
+            db,
+            ac,
+            id: self.id.unwrap_or_default(),
+            rl: None,
+            aud: None,
+        })
+    }
+}
+
+impl<T> Default for SurrealJWTClaimsBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The reason a [`SurrealJWTClaimsBuilder`] could not build the claims.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ClaimsBuildError {
+    /// A claim the token needs was not given. Holds its JSON name, such as `NS`.
+    MissingClaim(&'static str),
+    /// A time claim would be past `u64::MAX` seconds. Holds its JSON name: `exp` or `nbf`.
+    Overflow(&'static str),
+}
+
+impl fmt::Display for ClaimsBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingClaim(claim) => write!(f, "the `{claim}` claim was not given"),
+            Self::Overflow(claim) => write!(f, "the `{claim}` claim is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ClaimsBuildError {}
@@ -0,0 +1,396 @@
+//! Shortcuts for fetching and changing a single record by table and key.
+//!
+//! Enabled by the `client` feature. Each function builds the `RecordId` from a table name and a
+//! key, for example a naked key taken from a URL, and calls the matching `Surreal` method:
+//!
+//! ```no_run
+//! # async fn example<C: surrealdb::Connection>(db: surrealdb::Surreal<C>) -> surrealdb::Result<()> {
+//! #[derive(serde::Deserialize)]
+//! struct User {
+//!     name: String,
+//! }
+//!
+//! let user: Option<User> = atopio_extra::client::select_by_key(&db, "user", "abc123").await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! They work with any connection type, so the same code runs against a remote server and an
+//! embedded database in tests.
+//!
+//! For models with a [`Table`] impl, [`SurrealRepo`] offers the same operations keyed by
+//! [`TypedId`], so the table name is never taken from a string, and [`Pager`] walks a table
+//! page by page.
+
+use crate::surrealql::{key_to_surrealql, table_to_surrealql};
+use crate::{RecordIdRange, Table, TypedId};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::future::Future;
+use std::ops::Bound;
+use surrealdb::{Connection, RecordId, RecordIdKey, Surreal};
+
+/// Selects the record `table:key`, returning `None` if it does not exist.
+///
+/// # Errors
+///
+/// Returns the client's error if the query fails or the record does not deserialize into `T`.
+pub async fn select_by_key<T, C>(
+    db: &Surreal<C>,
+    table: &str,
+    key: impl Into<RecordIdKey>,
+) -> surrealdb::Result<Option<T>>
+where
+    T: DeserializeOwned,
+    C: Connection,
+{
+    db.select(RecordId::from_table_key(table, key)).await
+}
+
+/// Deletes the record `table:key`, returning it as it was, or `None` if it did not exist.
+///
+/// # Errors
+///
+/// Returns the client's error if the query fails or the record does not deserialize into `T`.
+pub async fn delete_by_key<T, C>(
+    db: &Surreal<C>,
+    table: &str,
+    key: impl Into<RecordIdKey>,
+) -> surrealdb::Result<Option<T>>
+where
+    T: DeserializeOwned,
+    C: Connection,
+{
+    db.delete(RecordId::from_table_key(table, key)).await
+}
+
+/// Merges `data` into the record `table:key`, returning the updated record.
+///
+/// Like `UPDATE table:key MERGE $data`, fields not in `data` are kept. Returns `None` if the
+/// record does not exist; it is not created.
+///
+/// # Errors
+///
+/// Returns the client's error if the query fails or the record does not deserialize into `T`.
+pub async fn update_by_key_merge<T, C, D>(
+    db: &Surreal<C>,
+    table: &str,
+    key: impl Into<RecordIdKey>,
+    data: D,
+) -> surrealdb::Result<Option<T>>
+where
+    T: DeserializeOwned,
+    C: Connection,
+    D: Serialize + 'static,
+{
+    db.update(RecordId::from_table_key(table, key))
+        .merge(data)
+        .await
+}
+
+/// Create, read, update and delete for the model `T`, keyed by [`TypedId<T>`].
+///
+/// Implement [`db`](SurrealRepo::db) and every other method is provided. The table is always
+/// `T::NAME`, never user input:
+///
+/// ```no_run
+/// use atopio_extra::client::SurrealRepo;
+/// use atopio_extra::{Table, TypedId};
+/// use surrealdb::Surreal;
+/// use surrealdb::engine::any::Any;
+///
+/// #[derive(Clone, serde::Serialize, serde::Deserialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// impl Table for User {
+///     const NAME: &'static str = "user";
+/// }
+///
+/// struct Users(Surreal<Any>);
+///
+/// impl SurrealRepo<User> for Users {
+///     type Connection = Any;
+///
+///     fn db(&self) -> &Surreal<Any> {
+///         &self.0
+///     }
+/// }
+///
+/// # async fn example(users: Users) -> surrealdb::Result<()> {
+/// let user = users.get(&TypedId::new("abc123")).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The client only accepts owned, `'static` content, so [`create`](SurrealRepo::create) and
+/// [`update`](SurrealRepo::update) clone the model they are given; hence the `Clone` bound.
+pub trait SurrealRepo<T>: Sync
+where
+    T: Table + Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// The connection type of the client.
+    type Connection: Connection;
+
+    /// Returns the client the provided methods run against.
+    fn db(&self) -> &Surreal<Self::Connection>;
+
+    /// Selects the record `id`, returning `None` if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns the client's error if the query fails or the record does not deserialize into `T`.
+    fn get(&self, id: &TypedId<T>) -> impl Future<Output = surrealdb::Result<Option<T>>> + Send {
+        self.db().select(id.id().clone()).into_future()
+    }
+
+    /// Creates a record in table `T` with a generated key, returning it as stored.
+    ///
+    /// The result is only `None` if the database returned no record, which it does not do for
+    /// a successful create.
+    ///
+    /// # Errors
+    ///
+    /// Returns the client's error if the query fails, for example because `content` holds an
+    /// `id` that already exists, or the record does not deserialize into `T`.
+    fn create(&self, content: &T) -> impl Future<Output = surrealdb::Result<Option<T>>> + Send {
+        self.db()
+            .create(T::NAME)
+            .content(content.clone())
+            .into_future()
+    }
+
+    /// Replaces the content of the record `id`, returning the updated record.
+    ///
+    /// Returns `None` if the record does not exist; it is not created.
+    ///
+    /// # Errors
+    ///
+    /// Returns the client's error if the query fails or the record does not deserialize into `T`.
+    fn update(
+        &self,
+        id: &TypedId<T>,
+        content: &T,
+    ) -> impl Future<Output = surrealdb::Result<Option<T>>> + Send {
+        self.db()
+            .update(id.id().clone())
+            .content(content.clone())
+            .into_future()
+    }
+
+    /// Deletes the record `id`, returning it as it was, or `None` if it did not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns the client's error if the query fails or the record does not deserialize into `T`.
+    fn delete(&self, id: &TypedId<T>) -> impl Future<Output = surrealdb::Result<Option<T>>> + Send {
+        self.db().delete(id.id().clone()).into_future()
+    }
+
+    /// Returns up to `limit` records of table `T` ordered by id, skipping the first `start`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the client's error if the query fails or a record does not deserialize into `T`.
+    fn list(
+        &self,
+        limit: u64,
+        start: u64,
+    ) -> impl Future<Output = surrealdb::Result<Vec<T>>> + Send {
+        let query = self
+            .db()
+            .query("SELECT * FROM type::table($table) ORDER BY id LIMIT $limit START $start")
+            .bind(("table", T::NAME))
+            .bind(("limit", limit))
+            .bind(("start", start));
+        async move { query.await?.take(0) }
+    }
+}
+
+/// Keyset pagination over table `T` using record ranges.
+///
+/// Each page is read with a range such as `SELECT * FROM user:⟨abc⟩>.. LIMIT 101`, which starts
+/// scanning right after the cursor instead of counting past skipped rows the way `START` does,
+/// so every page costs the same however deep it is. Pass the [`Page::next`] cursor of one page
+/// to the pager for the next:
+///
+/// ```no_run
+/// use atopio_extra::client::Pager;
+/// # use atopio_extra::Table;
+/// # #[derive(serde::Deserialize)]
+/// # struct User;
+/// # impl Table for User {
+/// #     const NAME: &'static str = "user";
+/// # }
+///
+/// # async fn example<C: surrealdb::Connection>(
+/// #     db: surrealdb::Surreal<C>,
+/// # ) -> Result<(), Box<dyn std::error::Error>> {
+/// let mut cursor = None;
+/// loop {
+///     let page = Pager::<User>::new(cursor, 100)?.fetch::<User, _>(&db).await?;
+///     // ... handle page.rows ...
+///     match page.next {
+///         Some(next) => cursor = Some(next),
+///         None => break,
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Records come back in key order. Integer keys sort before string keys, so a table mixing key
+/// kinds is still walked exactly once.
+pub struct Pager<T> {
+    after: Option<TypedId<T>>,
+    size: u64,
+}
+
+impl<T: Table> Pager<T> {
+    /// Creates a pager for the `size` records following `after`, or the first `size` records of
+    /// the table if `after` is `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PageSizeError`] if `size` is `0`.
+    pub fn new(after: Option<TypedId<T>>, size: u64) -> Result<Self, PageSizeError> {
+        if size == 0 {
+            return Err(PageSizeError);
+        }
+        Ok(Self { after, size })
+    }
+
+    /// Returns the cursor the page starts after.
+    pub fn after(&self) -> Option<&TypedId<T>> {
+        self.after.as_ref()
+    }
+
+    /// Returns the maximum number of records on a page.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the range of records from the cursor to the end of the table.
+    pub fn range(&self) -> RecordIdRange {
+        let start = match &self.after {
+            Some(after) => Bound::Excluded(after.key().clone()),
+            None => Bound::Unbounded,
+        };
+        RecordIdRange::new(T::NAME, start, Bound::Unbounded)
+    }
+
+    /// Returns the statement that reads the page and its single binding.
+    ///
+    /// The range is written into the statement, with the table and key escaped as
+    /// [`to_surrealql`](crate::to_surrealql) escapes them, and the limit is bound as `$limit`. The limit is one more than the page size, so the extra record tells whether
+    /// another page follows:
+    ///
+    /// ```
+    /// use atopio_extra::{Table, TypedId};
+    /// use atopio_extra::client::Pager;
+    ///
+    /// struct User;
+    ///
+    /// impl Table for User {
+    ///     const NAME: &'static str = "user";
+    /// }
+    ///
+    /// let pager = Pager::new(Some(TypedId::<User>::new("abc")), 100).unwrap();
+    /// let (sql, binding) = pager.query();
+    /// assert_eq!(sql, "SELECT * FROM user:abc>.. LIMIT $limit");
+    /// assert_eq!(binding, ("limit", 101));
+    /// ```
+    pub fn query(&self) -> (String, (&'static str, u64)) {
+        // Not `self.range()`, whose `Display` leaves keyword tables such as `select` bare.
+        let table = table_to_surrealql(T::NAME);
+        let range = match &self.after {
+            Some(after) => format!("{table}:{}>..", key_to_surrealql(after.key())),
+            None => format!("{table}:.."),
+        };
+        (
+            format!("SELECT * FROM {range} LIMIT $limit"),
+            ("limit", self.size.saturating_add(1)),
+        )
+    }
+
+    /// Reads the page from `db`.
+    ///
+    /// [`Page::next`] is the id of the last record on the page when more records follow it,
+    /// and `None` on the last page, including a last page that is exactly full.
+    ///
+    /// # Errors
+    ///
+    /// Returns the client's error if the query fails or a record does not deserialize into `R`.
+    pub async fn fetch<R, C>(&self, db: &Surreal<C>) -> surrealdb::Result<Page<R, T>>
+    where
+        R: DeserializeOwned,
+        C: Connection,
+    {
+        let (select, binding) = self.query();
+        let mut response = db
+            .query(format!(
+                "LET $page = ({select}); RETURN $page; RETURN $page.id"
+            ))
+            .bind(binding)
+            .await?;
+        let mut rows: Vec<R> = response.take(1)?;
+        let ids: Vec<RecordId> = response.take(2)?;
+        let size = usize::try_from(self.size).unwrap_or(usize::MAX);
+        let next = if rows.len() > size {
+            rows.truncate(size);
+            ids.get(size - 1).map(|id| TypedId::new(id.key().clone()))
+        } else {
+            None
+        };
+        Ok(Page { rows, next })
+    }
+}
+
+impl<T> Clone for Pager<T> {
+    fn clone(&self) -> Self {
+        Self {
+            after: self.after.clone(),
+            size: self.size,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Pager<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pager")
+            .field("after", &self.after)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+/// The reason a [`Pager`] could not be created: its page size was `0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageSizeError;
+
+impl fmt::Display for PageSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("page size must be at least 1")
+    }
+}
+
+impl std::error::Error for PageSizeError {}
+
+/// One page of records read by [`Pager::fetch`].
+pub struct Page<R, T> {
+    /// The records on the page, in key order.
+    pub rows: Vec<R>,
+    /// The cursor for the next page, or `None` if this is the last page.
+    pub next: Option<TypedId<T>>,
+}
+
+impl<R: fmt::Debug, T> fmt::Debug for Page<R, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Page")
+            .field("rows", &self.rows)
+            .field("next", &self.next)
+            .finish()
+    }
+}
@@ -0,0 +1,102 @@
+//! A source of the current time, so checks against it can be tested.
+//!
+//! Every time-based check of this crate has a variant taking the time explicitly, such as
+//! [`SurrealJWTClaims::is_expired_at`](crate::types::SurrealJWTClaims::is_expired_at) or
+//! [`Validation::validate`](crate::Validation::validate). Code that makes those checks can take
+//! a [`Clock`] and pass it `clock.now()`, using [`SystemClock`] in production and, with the
+//! `test-utils` feature, `FixedClock` or `SteppingClock` in tests:
+//!
+//! ```
+//! use atopio_extra::clock::{Clock, SystemClock};
+//! use atopio_extra::types::SurrealJWTClaims;
+//!
+//! fn still_valid<T>(claims: &SurrealJWTClaims<T>, clock: &impl Clock) -> bool {
+//!     !claims.is_expired_at(clock.now())
+//! }
+//!
+//! let claims: SurrealJWTClaims<String> = serde_json::from_str(
+//!     r#"{"iat":1,"nbf":1,"exp":2,"iss":"SurrealDB","jti":"j","NS":"app","DB":"main","AC":"user","ID":"user:1"}"#,
+//! ).unwrap();
+//! assert!(!still_valid(&claims, &SystemClock));
+//! ```
+
+use std::time::SystemTime;
+#[cfg(feature = "test-utils")]
+use std::{sync::Mutex, time::Duration};
+
+/// A source of the current time.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> SystemTime {
+        (**self).now()
+    }
+}
+
+/// The system's clock, read with `SystemTime::now`. The methods of this crate that take no
+/// time use it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock stuck at one time.
+///
+/// Enabled by the `test-utils` feature.
+#[cfg(feature = "test-utils")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub SystemTime);
+
+#[cfg(feature = "test-utils")]
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+/// A clock that moves forward by a fixed step each time it is read.
+///
+/// The first read returns the start time. Use it to test code that reads the clock more than
+/// once, such as a refresh loop. A step or [`advance`](Self::advance) that would pass the latest
+/// time `SystemTime` can hold leaves the clock where it is. Enabled by the `test-utils`
+/// feature.
+#[cfg(feature = "test-utils")]
+#[derive(Debug)]
+pub struct SteppingClock {
+    next: Mutex<SystemTime>,
+    step: Duration,
+}
+
+#[cfg(feature = "test-utils")]
+impl SteppingClock {
+    /// Creates a clock that reads `start`, then `start + step`, and so on.
+    pub fn new(start: SystemTime, step: Duration) -> Self {
+        Self {
+            next: Mutex::new(start),
+            step,
+        }
+    }
+
+    /// Moves the clock forward by `by`, on top of its steps.
+    pub fn advance(&self, by: Duration) {
+        let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+        *next = next.checked_add(by).unwrap_or(*next);
+    }
+}
+
+#[cfg(feature = "test-utils")]
+impl Clock for SteppingClock {
+    fn now(&self) -> SystemTime {
+        let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+        let now = *next;
+        *next = now.checked_add(self.step).unwrap_or(now);
+        now
+    }
+}
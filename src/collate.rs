@@ -0,0 +1,47 @@
+//! Ordering record ids the way SurrealDB orders them.
+//!
+//! [`collate`] compares ids by table name first and then by key, the same order the server
+//! uses for `ORDER BY id` and for record ranges. Keys of different kinds sort by kind:
+//!
+//! 1. integer keys, numerically, so `user:-5` < `user:2` < `user:10`;
+//! 2. string keys, byte by byte, so `user:⟨10⟩` < `user:⟨9⟩` < `user:Z` < `user:a`;
+//! 3. uuid keys;
+//! 4. array keys, element by element, with a shorter array first when one is a prefix of the
+//!    other;
+//! 5. object keys, by their fields in name order.
+//!
+//! Table names compare byte by byte too. [`OrdId`](crate::types::OrdId) uses the same order,
+//! so merged pages can be sorted either way:
+//!
+//! ```
+//! use atopio_extra::collate;
+//! use atopio_extra::types::OrdId;
+//! use surrealdb::RecordId;
+//!
+//! let mut ids: Vec<RecordId> = vec![
+//!     "user:abc".parse().unwrap(),
+//!     "user:10".parse().unwrap(),
+//!     "post:1".parse().unwrap(),
+//!     "user:2".parse().unwrap(),
+//! ];
+//! ids.sort_by(collate);
+//! assert_eq!(
+//!     ids.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+//!     ["post:1", "user:2", "user:10", "user:abc"]
+//! );
+//!
+//! let mut ids: Vec<OrdId> = ids.into_iter().rev().map(OrdId).collect();
+//! ids.sort();
+//! assert_eq!(ids[0].0.to_string(), "post:1");
+//! ```
+
+use std::cmp::Ordering;
+use surrealdb::RecordId;
+
+/// Compares two record ids in the order SurrealDB sorts them, as described in the
+/// [module documentation](self).
+pub fn collate(a: &RecordId, b: &RecordId) -> Ordering {
+    a.table()
+        .cmp(b.table())
+        .then_with(|| a.key().into_inner_ref().cmp(b.key().into_inner_ref()))
+}
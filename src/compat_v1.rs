@@ -0,0 +1,212 @@
+//! Serde helpers for models still on surrealdb 1.x, where ids are `sql::Thing` values.
+//!
+//! Enabled by the `compat-v1` feature, which pulls in surrealdb 1.x alongside 2.x. The
+//! [`thing_full`] and [`thing_naked`] modules have the same functions as
+//! [`crate::thing_full`] and [`crate::thing_naked`], but take surrealdb 1.x's `Thing`:
+//!
+//! ```
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct User {
+//!     #[serde(with = "atopio_extra::compat_v1::thing_full")]
+//!     id: surrealdb_v1::sql::Thing,
+//! }
+//! ```
+//!
+//! Each id is converted to its surrealdb 2.x equivalent and written by the 2.x helpers, so a
+//! 1.x service and a 2.x service using [`crate::record_id_full`] or [`crate::record_id_naked`]
+//! produce byte-identical JSON. surrealdb 1.x has no uuid keys, so reading a uuid key such as
+//! `user:u'0189e3f0-...'` into a 1.x `Thing` fails.
+
+use std::fmt;
+use surrealdb::sql::Thing;
+use surrealdb_v1::sql::Thing as ThingV1;
+
+/// Converts a surrealdb 1.x `Thing` into a surrealdb 2.x `Thing`.
+///
+/// The key is carried across in the shape both versions share, so array and object keys are
+/// kept intact.
+///
+/// # Errors
+///
+/// Returns a [`CompatError`] if the key has no 2.x equivalent.
+pub fn thing_from_v1(thing: &ThingV1) -> Result<Thing, CompatError> {
+    convert(thing, thing.to_string())
+}
+
+/// Converts a surrealdb 2.x `Thing` into a surrealdb 1.x `Thing`.
+///
+/// # Errors
+///
+/// Returns a [`CompatError`] if the key cannot be represented in surrealdb 1.x, such as a uuid
+/// key.
+pub fn thing_to_v1(thing: &Thing) -> Result<ThingV1, CompatError> {
+    convert(thing, thing.to_string())
+}
+
+fn convert<A, B>(thing: &A, text: String) -> Result<B, CompatError>
+where
+    A: serde::Serialize,
+    B: serde::de::DeserializeOwned,
+{
+    serde_json::to_value(thing)
+        .and_then(serde_json::from_value::<B>)
+        .map_err(|_| CompatError(text))
+}
+
+/// The error returned when an id cannot be converted between surrealdb 1.x and 2.x.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatError(String);
+
+impl fmt::Display for CompatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} cannot be converted between surrealdb 1.x and 2.x",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for CompatError {}
+
+/// Returns the 2.x `Thing` for a 1.x one, as a serde error.
+fn to_v2<E: serde::ser::Error>(thing: &ThingV1) -> Result<Thing, E> {
+    thing_from_v1(thing).map_err(E::custom)
+}
+
+/// Returns the 1.x `Thing` for a 2.x one, as a serde error.
+fn to_v1<E: serde::de::Error>(thing: &Thing) -> Result<ThingV1, E> {
+    thing_to_v1(thing).map_err(E::custom)
+}
+
+/// Serde helpers writing a surrealdb 1.x `sql::Thing` in the full (table:key) form.
+///
+/// See [`crate::thing_full`].
+pub mod thing_full {
+    use serde::{Deserializer, Serializer};
+    use surrealdb_v1::sql::Thing;
+
+    /// Serialize a surrealdb 1.x `Thing` as its full string representation.
+    ///
+    /// The output is exactly what [`crate::record_id_full::serialize`] writes for the same
+    /// id.
+    ///
+    /// # Errors
+    ///
+    /// Returns a serialization error if the id has no surrealdb 2.x equivalent.
+    pub fn serialize<S>(thing: &Thing, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        crate::thing_full::serialize(&super::to_v2(thing)?, serializer)
+    }
+
+    /// Deserialize a full (table:key) string into a surrealdb 1.x `Thing`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a string, if the string is not a
+    /// valid SurrealDB record id, or if the id cannot be represented in surrealdb 1.x.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Thing, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::to_v1(&crate::thing_full::deserialize(deserializer)?)
+    }
+
+    /// `Option<Thing>` support for `#[serde(with = "...")]`, for surrealdb 1.x `Thing`s.
+    ///
+    /// Add `#[serde(default)]` if the field may be missing from the input entirely.
+    pub mod option {
+        use serde::{Deserializer, Serializer};
+        use surrealdb_v1::sql::Thing;
+
+        /// Serialize an `Option<Thing>` in the full (table:key) form, or `null`.
+        ///
+        /// # Errors
+        ///
+        /// Returns a serialization error if the id has no surrealdb 2.x equivalent.
+        pub fn serialize<S>(thing: &Option<Thing>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let thing = thing.as_ref().map(super::super::to_v2).transpose()?;
+            crate::thing_full::option::serialize(&thing, serializer)
+        }
+
+        /// Deserialize an `Option<Thing>` from the full (table:key) form or `null`.
+        ///
+        /// # Errors
+        ///
+        /// Returns a deserialization error if the value is not a string or `null`, if the
+        /// string is not a valid SurrealDB record id, or if the id cannot be represented in
+        /// surrealdb 1.x.
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Thing>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            crate::thing_full::option::deserialize(deserializer)?
+                .as_ref()
+                .map(super::super::to_v1)
+                .transpose()
+        }
+    }
+}
+
+/// Serde helpers writing only the key of a surrealdb 1.x `sql::Thing`.
+///
+/// See [`crate::thing_naked`].
+pub mod thing_naked {
+    use crate::Table;
+    use serde::{Deserializer, Serializer};
+    use surrealdb_v1::sql::Thing;
+
+    /// Serialize the key portion of a surrealdb 1.x `Thing`.
+    ///
+    /// The output is exactly what [`crate::record_id_naked::serialize`] writes for the same
+    /// id.
+    ///
+    /// # Errors
+    ///
+    /// Returns a serialization error if the thing does not identify a single record.
+    pub fn serialize<S>(thing: &Thing, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        crate::thing_naked::serialize(&super::to_v2(thing)?, serializer)
+    }
+
+    /// Deserialize a surrealdb 1.x `Thing` for a field annotated with this module.
+    ///
+    /// Like [`crate::thing_naked::deserialize`], this exists so `#[serde(with = "...")]`
+    /// compiles and only reads back formats that are not human readable; use
+    /// [`deserialize_for`] to read bare keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`crate::thing_naked::deserialize`], or a deserialization error
+    /// if the id cannot be represented in surrealdb 1.x.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Thing, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::to_v1(&crate::thing_naked::deserialize(deserializer)?)
+    }
+
+    /// Deserialize a naked key into a surrealdb 1.x `Thing` in the table named by `T`.
+    ///
+    /// See [`crate::record_id_naked::deserialize_for`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is neither a string nor an integer, if the
+    /// string is empty, if an unsigned integer does not fit in an `i64`, or if the key cannot be
+    /// represented in surrealdb 1.x.
+    pub fn deserialize_for<'de, T, D>(deserializer: D) -> Result<Thing, D::Error>
+    where
+        T: Table,
+        D: Deserializer<'de>,
+    {
+        super::to_v1(&crate::thing_naked::deserialize_for::<T, D>(deserializer)?)
+    }
+}
@@ -0,0 +1,274 @@
+//! Record ids derived from the content of a record.
+//!
+//! Enabled by the `sha256` or `blake3` feature. Re-importing a row with the same content gives
+//! the same id, so ingestion can `UPSERT` instead of checking for duplicates first:
+//!
+//! ```
+//! # #[cfg(feature = "sha256")] {
+//! use atopio_extra::content_id::HashAlgorithm;
+//! use atopio_extra::derive_id_from;
+//!
+//! #[derive(serde::Serialize)]
+//! struct Row {
+//!     sensor: &'static str,
+//!     value: f64,
+//! }
+//!
+//! let row = Row { sensor: "s1", value: 20.5 };
+//! let a = derive_id_from(HashAlgorithm::Sha256, "reading", &row).unwrap();
+//! let b = derive_id_from(HashAlgorithm::Sha256, "reading", &row).unwrap();
+//! assert_eq!(a, b);
+//! assert_eq!(a.key().to_string().len(), 32);
+//! # }
+//! ```
+//!
+//! There is no default algorithm: which ones exist depends on the enabled features, and an id
+//! must not change because another crate in the build turned on a feature. Pick one and keep
+//! it for as long as the ids are stored.
+//!
+//! # Canonical form
+//!
+//! The hash is taken over a canonical JSON text of the value, built as follows:
+//!
+//! 1. The value is serialized with `serde_json`, so it must be representable as JSON; maps
+//!    with non-string keys are rejected.
+//! 2. Excluded fields (see [`ContentId::exclude`]) are removed.
+//! 3. The JSON is written without any whitespace, with the fields of every object sorted by
+//!    the UTF-8 bytes of their names. Strings and numbers are written the way `serde_json`
+//!    writes them.
+//!
+//! The key is the lowercase hex digest of that text, cut to [`ContentId::length`] characters.
+//! Field order in the Rust struct therefore does not matter, but renaming a field, changing
+//! its serde attributes or switching an integer to a float changes every id. Two values with
+//! the same canonical text always get the same id, in this and later versions of the crate;
+//! telling apart values that collide after truncation is left to the caller.
+
+use serde::Serialize;
+use serde_json::Value as Json;
+use std::collections::BTreeSet;
+use std::fmt;
+use surrealdb::RecordId;
+
+/// The longest key a [`ContentId`] can produce: the full 256-bit digest in hex.
+pub const MAX_LENGTH: usize = 64;
+
+/// Derives an id in `table` from `value` with `algorithm` and otherwise default [`ContentId`]
+/// settings.
+///
+/// # Errors
+///
+/// Returns a [`DeriveIdError`] if `value` cannot be serialized as JSON.
+pub fn derive_id_from<T>(
+    algorithm: HashAlgorithm,
+    table: &str,
+    value: &T,
+) -> Result<RecordId, DeriveIdError>
+where
+    T: Serialize + ?Sized,
+{
+    ContentId::new(algorithm).derive(table, value)
+}
+
+/// The hash a [`ContentId`] uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HashAlgorithm {
+    /// SHA-256, enabled by the `sha256` feature.
+    #[cfg(feature = "sha256")]
+    Sha256,
+    /// BLAKE3, enabled by the `blake3` feature.
+    #[cfg(feature = "blake3")]
+    Blake3,
+}
+
+impl HashAlgorithm {
+    fn hex_digest(self, bytes: &[u8]) -> String {
+        let digest: [u8; 32] = match self {
+            #[cfg(feature = "sha256")]
+            Self::Sha256 => {
+                use sha2::Digest as _;
+                sha2::Sha256::digest(bytes).into()
+            }
+            #[cfg(feature = "blake3")]
+            Self::Blake3 => blake3::hash(bytes).into(),
+        };
+        digest.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
+
+/// Settings for deriving record ids from content.
+///
+/// ```
+/// # #[cfg(feature = "blake3")] {
+/// use atopio_extra::content_id::{ContentId, HashAlgorithm};
+/// use serde_json::json;
+///
+/// let ids = ContentId::new(HashAlgorithm::Blake3).length(16).exclude("imported_at");
+/// let first = ids.derive("reading", &json!({"value": 1, "imported_at": "2024-06-01"})).unwrap();
+/// let again = ids.derive("reading", &json!({"value": 1, "imported_at": "2024-06-02"})).unwrap();
+/// assert_eq!(first, again);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentId {
+    algorithm: HashAlgorithm,
+    length: usize,
+    excluded: BTreeSet<String>,
+}
+
+impl ContentId {
+    /// The key length used unless [`length`](Self::length) is called: 32 hex characters, or
+    /// 128 bits of the digest.
+    pub const DEFAULT_LENGTH: usize = 32;
+
+    /// Creates settings for `algorithm`, with keys of [`DEFAULT_LENGTH`](Self::DEFAULT_LENGTH)
+    /// characters and no excluded fields.
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        Self {
+            algorithm,
+            length: Self::DEFAULT_LENGTH,
+            excluded: BTreeSet::new(),
+        }
+    }
+
+    /// Sets the hash algorithm.
+    pub fn algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Sets the number of hex characters kept from the digest, from 1 to [`MAX_LENGTH`].
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Leaves a field out of the hash, such as a timestamp that changes on every import.
+    ///
+    /// `field` names a field of the top-level object; use dots for nested objects, as in
+    /// `meta.updated_at`. Fields that are not present are ignored.
+    pub fn exclude(mut self, field: impl Into<String>) -> Self {
+        self.excluded.insert(field.into());
+        self
+    }
+
+    /// Derives an id in `table` from `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DeriveIdError`] if the length is out of range, if `value` cannot be
+    /// serialized as JSON, or if fields are excluded but `value` is not an object.
+    pub fn derive<T>(&self, table: &str, value: &T) -> Result<RecordId, DeriveIdError>
+    where
+        T: Serialize + ?Sized,
+    {
+        if !(1..=MAX_LENGTH).contains(&self.length) {
+            return Err(DeriveIdError::InvalidLength(self.length));
+        }
+        let canonical = self.canonical(value)?;
+        let mut key = self.algorithm.hex_digest(canonical.as_bytes());
+        key.truncate(self.length);
+        Ok(RecordId::from_table_key(table, key))
+    }
+
+    /// Returns the canonical text of `value` that [`derive`](Self::derive) hashes.
+    ///
+    /// Useful for finding out why two values that look alike get different ids.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DeriveIdError`] if `value` cannot be serialized as JSON, or if fields are
+    /// excluded but `value` is not an object.
+    pub fn canonical<T>(&self, value: &T) -> Result<String, DeriveIdError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let mut json =
+            serde_json::to_value(value).map_err(|e| DeriveIdError::Serialize(e.to_string()))?;
+        if !self.excluded.is_empty() && !json.is_object() {
+            return Err(DeriveIdError::NotAnObject);
+        }
+        for field in &self.excluded {
+            remove_path(&mut json, field);
+        }
+        let mut out = String::new();
+        write_canonical(&json, &mut out);
+        Ok(out)
+    }
+}
+
+fn remove_path(json: &mut Json, path: &str) {
+    let (parents, last) = match path.rsplit_once('.') {
+        Some((parents, last)) => (Some(parents), last),
+        None => (None, path),
+    };
+    let mut target = json;
+    for name in parents.into_iter().flat_map(|parents| parents.split('.')) {
+        match target.get_mut(name) {
+            Some(child) => target = child,
+            None => return,
+        }
+    }
+    if let Json::Object(fields) = target {
+        fields.remove(last);
+    }
+}
+
+fn write_canonical(json: &Json, out: &mut String) {
+    match json {
+        Json::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Json::Object(fields) => {
+            let mut fields = fields.iter().collect::<Vec<_>>();
+            fields.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+            out.push('{');
+            for (i, (name, value)) in fields.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Json::from(name.as_str()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// The reason a [`ContentId`] could not derive an id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DeriveIdError {
+    /// The value could not be serialized as JSON. Holds the serializer's message.
+    Serialize(String),
+    /// Fields were excluded, but the value is not a JSON object.
+    NotAnObject,
+    /// The key length is `0` or longer than [`MAX_LENGTH`]. Holds the length.
+    InvalidLength(usize),
+}
+
+impl fmt::Display for DeriveIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Serialize(message) => write!(f, "value cannot be hashed: {message}"),
+            Self::NotAnObject => {
+                f.write_str("fields can only be excluded from values that serialize as objects")
+            }
+            Self::InvalidLength(length) => write!(
+                f,
+                "content id length must be between 1 and {MAX_LENGTH}, not {length}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DeriveIdError {}
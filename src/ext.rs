@@ -0,0 +1,401 @@
+use crate::record_id_full;
+use crate::record_id_naked::{self, KeyText};
+use serde::{Serialize, Serializer};
+use std::fmt;
+use surrealdb::sql::Id;
+use surrealdb::{RecordId, RecordIdKey};
+
+/// Convenience methods for `surrealdb::RecordId`.
+///
+/// ```
+/// use atopio_extra::RecordIdExt;
+///
+/// let id: surrealdb::RecordId = "user:⟨has space⟩".parse().unwrap();
+/// assert!(id.is_in_table("user"));
+/// assert_eq!(id.key_string(), "has space");
+/// assert_eq!(id.with_table("admin").to_string(), "admin:⟨has space⟩");
+/// ```
+pub trait RecordIdExt {
+    /// Returns the table name.
+    fn table_str(&self) -> &str;
+
+    /// Returns the key as unescaped text.
+    ///
+    /// String keys are returned verbatim, without the `⟨...⟩` escaping that `to_string()`
+    /// adds, so `user:⟨has space⟩` gives `has space`. Other keys use their SurrealQL text, for
+    /// example `42` or `['a', 1]`. This allocates a new `String`.
+    fn key_string(&self) -> String;
+
+    /// Returns `true` if the id belongs to `table`.
+    fn is_in_table(&self, table: &str) -> bool;
+
+    /// Splits the id into its table name and key without cloning either.
+    fn into_parts(self) -> (String, RecordIdKey);
+
+    /// Returns an id with the same key in another table.
+    ///
+    /// The key is cloned, so this allocates.
+    fn with_table(&self, table: &str) -> RecordId;
+
+    /// Returns a wrapper that displays the naked key, for URLs, log lines and cache keys.
+    ///
+    /// See [`NakedKey`].
+    fn naked(&self) -> NakedKey<'_>;
+
+    /// Returns `true` if both ids name the same record.
+    ///
+    /// Tables and keys are compared as values, never as text, so how a key was written does not
+    /// matter: `user:⟨abc⟩`, ``user:`abc` `` and `("user", "abc").into()` are all the same
+    /// record. The kind of key does matter, so the integer key `user:42` and the string key
+    /// `user:⟨42⟩` are different records, as they are in the database. Object keys compare
+    /// field by field regardless of order. Table names are case sensitive.
+    ///
+    /// ```
+    /// use atopio_extra::RecordIdExt;
+    ///
+    /// let parsed: surrealdb::RecordId = "user:⟨abc⟩".parse().unwrap();
+    /// assert!(parsed.same_record(&("user", "abc").into()));
+    /// assert!(!parsed.same_record(&("user", 42).into()));
+    /// ```
+    fn same_record(&self, other: &RecordId) -> bool;
+
+    /// Returns a wrapper that serializes the id in the full (table:key) form.
+    ///
+    /// See [`FullIdRef`].
+    fn full(&self) -> FullIdRef<'_>;
+
+    /// Returns a wrapper that displays the id with its key hidden, for logs.
+    ///
+    /// See [`RecordIdRedacted`].
+    fn redacted(&self) -> RecordIdRedacted<'_>;
+
+    /// Returns the key if it is an integer key.
+    ///
+    /// Only integer keys succeed: the string key `user:⟨42⟩` is not converted, even though its
+    /// text is a number.
+    ///
+    /// ```
+    /// use atopio_extra::RecordIdExt;
+    ///
+    /// let id: surrealdb::RecordId = "user:42".parse().unwrap();
+    /// assert_eq!(id.key_as_i64(), Ok(42));
+    ///
+    /// let id: surrealdb::RecordId = "user:⟨42⟩".parse().unwrap();
+    /// let err = id.key_as_i64().unwrap_err();
+    /// assert_eq!(err.to_string(), "expected an integer record id key, found a string key");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`KeyTypeError`] naming the actual key kind if the key is not an integer.
+    fn key_as_i64(&self) -> Result<i64, KeyTypeError>;
+
+    /// Returns the key if it is a uuid key, such as `user:u'0189e3f0-...'`.
+    ///
+    /// A string key holding uuid text is not converted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`KeyTypeError`] naming the actual key kind if the key is not a uuid.
+    fn key_as_uuid(&self) -> Result<surrealdb::Uuid, KeyTypeError>;
+
+    /// Borrows the key if it is a string key, unescaped, as in `has space` for
+    /// `user:⟨has space⟩`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`KeyTypeError`] naming the actual key kind if the key is not a string, so
+    /// `user:42` fails rather than returning `"42"`. Use [`key_string`](Self::key_string) for
+    /// the text of any key.
+    fn key_as_str(&self) -> Result<&str, KeyTypeError>;
+}
+
+impl RecordIdExt for RecordId {
+    fn table_str(&self) -> &str {
+        self.table()
+    }
+
+    fn key_string(&self) -> String {
+        match self.key().into_inner_ref() {
+            Id::String(s) => s.clone(),
+            _ => self.key().to_string(),
+        }
+    }
+
+    fn is_in_table(&self, table: &str) -> bool {
+        self.table() == table
+    }
+
+    fn into_parts(self) -> (String, RecordIdKey) {
+        let thing = self.into_inner();
+        (thing.tb, RecordIdKey::from_inner(thing.id))
+    }
+
+    fn with_table(&self, table: &str) -> RecordId {
+        RecordId::from_table_key(table, self.key().clone())
+    }
+
+    fn naked(&self) -> NakedKey<'_> {
+        NakedKey(self)
+    }
+
+    fn full(&self) -> FullIdRef<'_> {
+        FullIdRef(self)
+    }
+
+    fn redacted(&self) -> RecordIdRedacted<'_> {
+        RecordIdRedacted(self)
+    }
+
+    fn same_record(&self, other: &RecordId) -> bool {
+        self.table() == other.table() && self.key() == other.key()
+    }
+
+    fn key_as_i64(&self) -> Result<i64, KeyTypeError> {
+        match self.key().into_inner_ref() {
+            Id::Number(n) => Ok(*n),
+            other => Err(KeyTypeError::new(KeyKind::Integer, other)),
+        }
+    }
+
+    fn key_as_uuid(&self) -> Result<surrealdb::Uuid, KeyTypeError> {
+        match self.key().into_inner_ref() {
+            Id::Uuid(uuid) => Ok(uuid.0),
+            other => Err(KeyTypeError::new(KeyKind::Uuid, other)),
+        }
+    }
+
+    fn key_as_str(&self) -> Result<&str, KeyTypeError> {
+        match self.key().into_inner_ref() {
+            Id::String(s) => Ok(s),
+            other => Err(KeyTypeError::new(KeyKind::String, other)),
+        }
+    }
+}
+
+/// Serializes a borrowed record id exactly as [`crate::record_id_full::serialize`] does.
+///
+/// For building values imperatively, where there is no field to put a `#[serde(with = "...")]`
+/// attribute on:
+///
+/// ```
+/// use atopio_extra::{FullIdRef, RecordIdExt};
+///
+/// let id: surrealdb::RecordId = "user:⟨has space⟩".parse().unwrap();
+/// let body = serde_json::json!({ "id": FullIdRef(&id), "key": id.naked() });
+/// assert_eq!(body.to_string(), r#"{"id":"user:⟨has space⟩","key":"has space"}"#);
+/// ```
+///
+/// [`NakedKey`] is the counterpart for the key alone. Use [`crate::types::FullId`] to own the id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FullIdRef<'a>(pub &'a RecordId);
+
+impl fmt::Display for FullIdRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.0, f)
+    }
+}
+
+impl Serialize for FullIdRef<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        record_id_full::serialize(self.0, serializer)
+    }
+}
+
+/// Displays a record id with its key replaced by a short hash, for log lines.
+///
+/// The table name is kept and the key is hidden, so `user:⟨ada@example.com⟩` prints as
+/// `user:<redacted:1143>`. `Debug` prints the same, so the id is safe in `{:?}` too:
+///
+/// ```
+/// use atopio_extra::RecordIdExt;
+///
+/// let id: surrealdb::RecordId = "user:⟨ada@example.com⟩".parse().unwrap();
+/// let line = format!("login failed for {}", id.redacted());
+/// assert!(line.starts_with("login failed for user:<redacted:"));
+/// assert!(!line.contains("ada"));
+/// ```
+///
+/// The suffix is 16 bits of the 32-bit FNV-1a hash of the key's SurrealQL text. It is
+/// deterministic, so the same record shows the same suffix in every log line and every run,
+/// which is enough to correlate lines. It cannot be reversed, but it is not a cryptographic
+/// commitment either: anyone holding a list of candidate keys can hash them and compare, and
+/// different keys share a suffix one time in 65536. Use [`crate::record_id_redacted`] to write
+/// the same text in serialized audit payloads.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RecordIdRedacted<'a>(pub &'a RecordId);
+
+impl RecordIdRedacted<'_> {
+    /// Returns the four hex digit hash shown in place of the key.
+    pub fn suffix(&self) -> String {
+        let hash = crate::cursor::fnv1a(self.0.key().to_string().as_bytes());
+        format!("{:04x}", (hash >> 16) ^ (hash & 0xffff))
+    }
+}
+
+impl fmt::Display for RecordIdRedacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:<redacted:{}>", self.0.table(), self.suffix())
+    }
+}
+
+impl fmt::Debug for RecordIdRedacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// The kind of value a record id key holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum KeyKind {
+    /// An integer key, as in `user:42`.
+    Integer,
+    /// A string key, as in `user:abc` or `user:⟨42⟩`.
+    String,
+    /// A uuid key, as in `user:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'`.
+    Uuid,
+    /// An array key, as in `reading:['sensor1', 1717000000]`.
+    Array,
+    /// An object key, as in `event:{ city: 'london' }`.
+    Object,
+    /// A key generated by the database, as in `user:rand()`.
+    Generated,
+    /// A key range, as in `user:1..10`.
+    Range,
+}
+
+impl KeyKind {
+    /// Returns the kind of `key`.
+    pub fn of(key: &RecordIdKey) -> Self {
+        Self::of_id(key.into_inner_ref())
+    }
+
+    fn of_id(id: &Id) -> Self {
+        match id {
+            Id::Number(_) => Self::Integer,
+            Id::String(_) => Self::String,
+            Id::Uuid(_) => Self::Uuid,
+            Id::Array(_) => Self::Array,
+            Id::Object(_) => Self::Object,
+            Id::Range(_) => Self::Range,
+            // `Id` is non-exhaustive; everything else is a `Generate` placeholder.
+            _ => Self::Generated,
+        }
+    }
+
+    /// Returns the lowercase name of the kind, as used in error messages.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Integer => "integer",
+            Self::String => "string",
+            Self::Uuid => "uuid",
+            Self::Array => "array",
+            Self::Object => "object",
+            Self::Generated => "generated",
+            Self::Range => "range",
+        }
+    }
+
+    pub(crate) fn article(self) -> &'static str {
+        match self {
+            Self::Integer | Self::Array | Self::Object => "an",
+            _ => "a",
+        }
+    }
+}
+
+impl fmt::Display for KeyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A record id key is not of the kind a conversion asked for.
+///
+/// Returned by [`RecordIdExt::key_as_i64`], [`RecordIdExt::key_as_uuid`] and
+/// [`RecordIdExt::key_as_str`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyTypeError {
+    expected: KeyKind,
+    found: KeyKind,
+}
+
+impl KeyTypeError {
+    fn new(expected: KeyKind, found: &Id) -> Self {
+        Self {
+            expected,
+            found: KeyKind::of_id(found),
+        }
+    }
+
+    /// Returns the kind of key the conversion needed.
+    pub fn expected(&self) -> KeyKind {
+        self.expected
+    }
+
+    /// Returns the kind of key the record id actually has.
+    pub fn found(&self) -> KeyKind {
+        self.found
+    }
+}
+
+impl fmt::Display for KeyTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} {} record id key, found {} {} key",
+            self.expected.article(),
+            self.expected,
+            self.found.article(),
+            self.found
+        )
+    }
+}
+
+impl std::error::Error for KeyTypeError {}
+
+/// Displays the key of a record id without its table, exactly as
+/// [`crate::record_id_naked::serialize`] writes it to JSON.
+///
+/// String keys are written unescaped unless the raw text would read back as a different key,
+/// so unlike [`RecordIdExt::key_string`] the string key `⟨42⟩` keeps its escaping:
+///
+/// ```
+/// use atopio_extra::RecordIdExt;
+///
+/// let id: surrealdb::RecordId = "user:⟨has space⟩".parse().unwrap();
+/// assert_eq!(format!("/users/{}", id.naked()), "/users/has space");
+///
+/// let id: surrealdb::RecordId = "user:⟨42⟩".parse().unwrap();
+/// assert_eq!(id.naked().to_string(), "⟨42⟩");
+/// ```
+///
+/// A plain identifier key, such as `abc123`, is written as it is. Any other string key is run
+/// through the SurrealQL parser to check that its raw text reads back unchanged, which
+/// allocates, as does formatting an array or object key.
+///
+/// `NakedKey` also implements `Serialize` by calling
+/// [`crate::record_id_naked::serialize`], so `json!({ "key": id.naked() })` matches the
+/// module's JSON output, and formats that are not human readable get the same native encoding
+/// of the whole id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NakedKey<'a>(pub &'a RecordId);
+
+impl fmt::Display for NakedKey<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&KeyText(self.0.key()), f)
+    }
+}
+
+impl Serialize for NakedKey<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        record_id_naked::serialize(self.0, serializer)
+    }
+}
@@ -0,0 +1,144 @@
+//! Fields that hold either a record id or an identifier from another system.
+//!
+//! A `source` field may name a record (`article:123`) or something outside the database, such
+//! as a partner's URL or slug. [`ExternalRef`] keeps the two apart:
+//!
+//! ```
+//! use atopio_extra::ExternalRef;
+//! use atopio_extra::external_ref::RefTables;
+//!
+//! struct Sources;
+//!
+//! impl RefTables for Sources {
+//!     const TABLES: &'static [&'static str] = &["article", "post"];
+//! }
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Document {
+//!     #[serde(deserialize_with = "atopio_extra::external_ref::deserialize_in::<Sources, _>")]
+//!     source: ExternalRef,
+//! }
+//!
+//! let doc: Document = serde_json::from_str(r#"{"source":"article:123"}"#).unwrap();
+//! assert!(doc.source.is_record_in("article"));
+//!
+//! let doc: Document = serde_json::from_str(r#"{"source":"partner:feed-42"}"#).unwrap();
+//! assert_eq!(doc.source.as_external(), Some("partner:feed-42"));
+//! ```
+//!
+//! A string is read as a record id when it parses as one and its table is allowed; every other
+//! string is kept verbatim as an external identifier. Restricting the tables matters, since
+//! many foreign identifiers (`isbn:9780131103627`, `partner:feed-42`) are valid record ids too.
+//! `ExternalRef`'s own `Deserialize` allows every table. Both variants are written back as
+//! strings, in every format.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use surrealdb::RecordId;
+
+/// A record id, or an identifier that is not one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ExternalRef {
+    /// A record in the database.
+    Record(RecordId),
+    /// An identifier from another system, kept as given.
+    External(String),
+}
+
+impl ExternalRef {
+    /// Reads `text` as a record id if it is one in any table, otherwise as an external
+    /// identifier.
+    pub fn parse(text: &str) -> Self {
+        match RecordId::from_str(text) {
+            Ok(id) => Self::Record(id),
+            Err(_) => Self::External(text.to_owned()),
+        }
+    }
+
+    /// Reads `text` as a record id if it is one in one of `tables`, otherwise as an external
+    /// identifier.
+    pub fn parse_in(text: &str, tables: &[&str]) -> Self {
+        match RecordId::from_str(text) {
+            Ok(id) if tables.contains(&id.table()) => Self::Record(id),
+            _ => Self::External(text.to_owned()),
+        }
+    }
+
+    /// Returns the record id, if this is a record.
+    pub fn as_record(&self) -> Option<&RecordId> {
+        match self {
+            Self::Record(id) => Some(id),
+            Self::External(_) => None,
+        }
+    }
+
+    /// Returns the external identifier, if this is not a record.
+    pub fn as_external(&self) -> Option<&str> {
+        match self {
+            Self::Record(_) => None,
+            Self::External(text) => Some(text),
+        }
+    }
+
+    /// Returns `true` if this is a record in `table`.
+    pub fn is_record_in(&self, table: &str) -> bool {
+        self.as_record().is_some_and(|id| id.table() == table)
+    }
+}
+
+impl From<RecordId> for ExternalRef {
+    fn from(id: RecordId) -> Self {
+        Self::Record(id)
+    }
+}
+
+impl fmt::Display for ExternalRef {
+    /// Writes the record id in the full (table:key) form, or the external identifier as is.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Record(id) => fmt::Display::fmt(id, f),
+            Self::External(text) => f.write_str(text),
+        }
+    }
+}
+
+impl Serialize for ExternalRef {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExternalRef {
+    /// Reads a string, claiming it as a record id in any table. Use [`deserialize_in`] to
+    /// restrict the tables.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(|text| Self::parse(&text))
+    }
+}
+
+/// The tables whose ids [`deserialize_in`] reads as records.
+pub trait RefTables {
+    /// The table names, exactly as they appear before the `:` separator of a record id.
+    const TABLES: &'static [&'static str];
+}
+
+/// Deserialize an [`ExternalRef`], reading a string as a record id only if it is one in one of
+/// `T::TABLES`.
+///
+/// # Errors
+///
+/// Returns a deserialization error if the value is not a string.
+pub fn deserialize_in<'de, T, D>(deserializer: D) -> Result<ExternalRef, D::Error>
+where
+    T: RefTables,
+    D: Deserializer<'de>,
+{
+    String::deserialize(deserializer).map(|text| ExternalRef::parse_in(&text, T::TABLES))
+}
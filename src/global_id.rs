@@ -0,0 +1,125 @@
+//! Opaque, globally unique ids for GraphQL-style APIs.
+//!
+//! Relay and similar clients expect one opaque string per object across the whole schema. A
+//! SurrealDB record id is already globally unique, since it names its table, so a global id is
+//! just the URL-safe base64 (without padding) of the full `table:key` text:
+//!
+//! ```
+//! use atopio_extra::global_id::{from_global_id, to_global_id};
+//!
+//! let id: surrealdb::RecordId = "user:abc".parse().unwrap();
+//! assert_eq!(to_global_id(&id), "dXNlcjphYmM");
+//! assert_eq!(from_global_id("dXNlcjphYmM").unwrap(), id);
+//! ```
+//!
+//! Use [`serialize`] and [`deserialize`] with `#[serde(with = "atopio_extra::global_id")]` on
+//! `RecordId` fields, and [`typed`] on `TypedId<T>` fields to also reject ids from other
+//! tables. Global ids are strings in every format, including those that are not human
+//! readable.
+//!
+//! Unlike [`crate::cursor`], a global id carries no checksum: it is a stable name for a record,
+//! not a token to be checked. Padded base64 is rejected, so each record has exactly one global
+//! id and the ids can be used as cache keys.
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use surrealdb::RecordId;
+
+/// Returns the global id of `id`.
+pub fn to_global_id(id: &RecordId) -> String {
+    URL_SAFE_NO_PAD.encode(id.to_string())
+}
+
+/// Returns the record id named by a global id.
+///
+/// # Errors
+///
+/// Returns [`GlobalIdError::InvalidEncoding`] if the input is not URL-safe base64 without
+/// padding of UTF-8 text, and [`GlobalIdError::InvalidId`] if the text is not a valid record
+/// id.
+pub fn from_global_id(global_id: &str) -> Result<RecordId, GlobalIdError> {
+    let text = URL_SAFE_NO_PAD
+        .decode(global_id)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(|| GlobalIdError::InvalidEncoding(global_id.to_owned()))?;
+    RecordId::from_str(&text).map_err(|_| GlobalIdError::InvalidId(text))
+}
+
+/// Serialize a `surrealdb::RecordId` as its global id.
+pub fn serialize<S>(id: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&to_global_id(id))
+}
+
+/// Deserialize a global id into a `surrealdb::RecordId`.
+///
+/// # Errors
+///
+/// Returns a deserialization error if the value is not a string or is not a global id, as
+/// described in [`from_global_id`].
+pub fn deserialize<'de, D>(deserializer: D) -> Result<RecordId, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let global_id = String::deserialize(deserializer)?;
+    from_global_id(&global_id).map_err(D::Error::custom)
+}
+
+/// Serde helpers writing a `TypedId<T>` as its global id.
+///
+/// Reading checks the table, so a global id for a `post` cannot be passed where a
+/// `TypedId<User>` is expected.
+pub mod typed {
+    use crate::{Table, TypedId};
+    use serde::de::Error as _;
+    use serde::{Deserializer, Serializer};
+
+    /// Serialize a `TypedId<T>` as its global id.
+    pub fn serialize<T, S>(id: &TypedId<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize(id.id(), serializer)
+    }
+
+    /// Deserialize a global id into a `TypedId<T>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a global id, or if it names a
+    /// record in a table other than `T::NAME`.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<TypedId<T>, D::Error>
+    where
+        T: Table,
+        D: Deserializer<'de>,
+    {
+        TypedId::try_from(super::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// The reason a string could not be read as a global id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GlobalIdError {
+    /// The input is not URL-safe base64 without padding of UTF-8 text. Holds the input.
+    InvalidEncoding(String),
+    /// The decoded text is not a valid record id. Holds the text.
+    InvalidId(String),
+}
+
+impl fmt::Display for GlobalIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidEncoding(input) => write!(f, "{input:?} is not a valid global id"),
+            Self::InvalidId(text) => write!(f, "global id holds an invalid record id {text:?}"),
+        }
+    }
+}
+
+impl std::error::Error for GlobalIdError {}
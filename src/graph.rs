@@ -0,0 +1,148 @@
+use crate::types::FullId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use surrealdb::RecordId;
+
+/// A graph edge as returned by `SELECT * FROM <edge table>` after a `RELATE` statement.
+///
+/// The `id`, `in`, and `out` fields are written in the full (table:key) form like
+/// `record_id_full`, and any other fields of the edge are flattened into `P`:
+///
+/// ```
+/// use atopio_extra::graph::Edge;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Likes {
+///     weight: u8,
+/// }
+///
+/// let json = r#"{"id":"likes:1","in":"user:a","out":"post:b","weight":3}"#;
+/// let edge: Edge<Likes> = serde_json::from_str(json).unwrap();
+/// assert_eq!(edge.out.to_string(), "post:b");
+/// assert_eq!(edge.props.weight, 3);
+/// ```
+///
+/// `id` is `None` for edges built locally with [`Edge::between`], since SurrealDB assigns the
+/// id when the edge is created; it is omitted from the output in that case.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Edge<P = ()> {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::record_id_full::option"
+    )]
+    pub id: Option<RecordId>,
+    #[serde(rename = "in", with = "crate::record_id_full")]
+    pub r#in: RecordId,
+    #[serde(with = "crate::record_id_full")]
+    pub out: RecordId,
+    #[serde(flatten)]
+    pub props: P,
+}
+
+impl<P> Edge<P> {
+    /// Builds an edge from `in_id` to `out_id` carrying `props`, for use as `RELATE` content.
+    pub fn between(in_id: RecordId, out_id: RecordId, props: P) -> Self {
+        Self {
+            id: None,
+            r#in: in_id,
+            out: out_id,
+            props,
+        }
+    }
+}
+
+/// One row of a graph traversal, such as
+/// `SELECT id, ->follows->user AS neighbors FROM user`.
+///
+/// Both fields use the full (table:key) form like `record_id_full`. Name the traversal
+/// `neighbors` with `AS`; a row without the field reads as having no neighbors. Collect the
+/// rows into an adjacency map with [`build_adjacency`] or [`Adjacency`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphRow {
+    #[serde(with = "crate::record_id_full")]
+    pub id: RecordId,
+    #[serde(default, with = "crate::record_id_full::vec")]
+    pub neighbors: Vec<RecordId>,
+}
+
+/// Builds an adjacency map from traversal rows, keeping every neighbor as returned.
+///
+/// Shorthand for `Adjacency::new().build(rows)`; see [`Adjacency`] to drop duplicate neighbors
+/// or self-loops.
+pub fn build_adjacency<I>(rows: I) -> HashMap<FullId, Vec<FullId>>
+where
+    I: IntoIterator<Item = GraphRow>,
+{
+    Adjacency::new().build(rows)
+}
+
+/// Settings for turning [`GraphRow`]s into an adjacency map.
+///
+/// ```
+/// use atopio_extra::graph::{Adjacency, GraphRow};
+/// use atopio_extra::types::FullId;
+///
+/// let rows: Vec<GraphRow> = serde_json::from_str(
+///     r#"[{"id":"user:a","neighbors":["user:b","user:a","user:b"]},{"id":"user:b","neighbors":[]}]"#,
+/// )
+/// .unwrap();
+/// let graph = Adjacency::new().dedup(true).drop_self_loops(true).build(rows);
+///
+/// let a = FullId("user:a".parse().unwrap());
+/// let b = FullId("user:b".parse().unwrap());
+/// assert_eq!(graph[&a], [b.clone()]);
+/// assert!(graph[&b].is_empty());
+/// ```
+///
+/// Every row gets an entry, even one without neighbors, so nodes with no outgoing edges are
+/// kept. Rows with the same id are merged, their neighbors appended in order. Neighbors keep
+/// the order the query returned them in; with [`dedup`](Self::dedup), the first occurrence of
+/// each is kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Adjacency {
+    dedup: bool,
+    drop_self_loops: bool,
+}
+
+impl Adjacency {
+    /// Creates the default settings, which keep every neighbor.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether a neighbor listed more than once for a node is kept only once, as happens
+    /// when two edges join the same records.
+    pub fn dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Sets whether a node listed as its own neighbor is left out.
+    pub fn drop_self_loops(mut self, drop_self_loops: bool) -> Self {
+        self.drop_self_loops = drop_self_loops;
+        self
+    }
+
+    /// Builds the map from each row's id to its neighbors.
+    pub fn build<I>(&self, rows: I) -> HashMap<FullId, Vec<FullId>>
+    where
+        I: IntoIterator<Item = GraphRow>,
+    {
+        let mut graph: HashMap<FullId, Vec<FullId>> = HashMap::new();
+        for row in rows {
+            let neighbors = graph.entry(FullId(row.id.clone())).or_default();
+            for neighbor in row.neighbors {
+                if self.drop_self_loops && neighbor == row.id {
+                    continue;
+                }
+                let neighbor = FullId(neighbor);
+                if self.dedup && neighbors.contains(&neighbor) {
+                    continue;
+                }
+                neighbors.push(neighbor);
+            }
+        }
+        graph
+    }
+}
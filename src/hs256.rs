@@ -0,0 +1,116 @@
+//! Signing and verifying HS256 tokens.
+//!
+//! Enabled by the `hs256` feature. SurrealDB access methods defined with `ALGORITHM HS256`
+//! and a `KEY` accept tokens signed here, and tokens they issue verify here with the same key:
+//!
+//! ```
+//! use atopio_extra::types::SurrealJWTClaims;
+//! use atopio_extra::{sign_hs256, verify_hs256};
+//!
+//! let claims: SurrealJWTClaims<String> = serde_json::from_str(
+//!     r#"{"iat":1,"nbf":1,"exp":4102444800,"iss":"SurrealDB","jti":"j","NS":"app","DB":"main","AC":"api","ID":"user:1"}"#,
+//! ).unwrap();
+//! let token = sign_hs256(&claims, b"secret").unwrap();
+//! let verified = verify_hs256::<String>(&token, b"secret").unwrap();
+//! assert_eq!(verified.id, "user:1");
+//! assert!(verify_hs256::<String>(&token, b"other").is_err());
+//! ```
+//!
+//! [`JwtError::InvalidSignature`] and [`JwtError::UnsupportedAlgorithm`] mean the token cannot
+//! be trusted, while [`JwtError::Validation`] means it was signed with the key but is no
+//! longer, or not yet, valid, so callers can answer the two differently.
+
+use crate::clock::{Clock, SystemClock};
+use crate::jwt::{self, JwtError, JwtHeader};
+use crate::types::SurrealJWTClaims;
+use crate::validation::Validation;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use sha2::Sha256;
+use std::time::SystemTime;
+
+/// Signs `claims` with `key`, returning a token with the header `{"alg":"HS256","typ":"JWT"}`.
+///
+/// # Errors
+///
+/// Returns [`JwtError::InvalidJson`] if `T` cannot be written as JSON.
+pub fn sign_hs256<T>(claims: &SurrealJWTClaims<T>, key: &[u8]) -> Result<String, JwtError>
+where
+    T: Serialize,
+{
+    let header = JwtHeader {
+        alg: "HS256".into(),
+        typ: Some("JWT".into()),
+        kid: None,
+        extra: serde_json::Map::new(),
+    };
+    let signing_input = format!(
+        "{}.{}",
+        jwt::encode_segment(&header)?,
+        jwt::encode_segment(claims)?
+    );
+    let signature = mac(key, &signing_input).finalize().into_bytes();
+    Ok(format!(
+        "{signing_input}.{}",
+        URL_SAFE_NO_PAD.encode(signature)
+    ))
+}
+
+/// Verifies an HS256 token signed with `key` and checks that it is valid now, with no leeway.
+///
+/// # Errors
+///
+/// Returns the errors of [`verify_hs256_with`].
+pub fn verify_hs256<T>(token: &str, key: &[u8]) -> Result<SurrealJWTClaims<T>, JwtError>
+where
+    T: DeserializeOwned,
+{
+    verify_hs256_with(token, key, &Validation::new(), SystemClock.now())
+}
+
+/// Verifies an HS256 token signed with `key`, then runs `validation` on its claims at `now`.
+///
+/// The signature is compared in constant time, and is checked before the claims are decoded.
+///
+/// # Errors
+///
+/// Returns [`JwtError::UnsupportedAlgorithm`] if the header's `alg` is not `HS256`,
+/// [`JwtError::InvalidSignature`] if the signature does not match, and
+/// [`JwtError::Validation`] if the claims fail `validation`. A token that cannot be decoded
+/// fails with the errors of [`decode_parts_insecurely`](crate::decode_parts_insecurely).
+pub fn verify_hs256_with<T>(
+    token: &str,
+    key: &[u8],
+    validation: &Validation,
+    now: SystemTime,
+) -> Result<SurrealJWTClaims<T>, JwtError>
+where
+    T: DeserializeOwned,
+{
+    let [header, payload, signature] = jwt::split(token)?;
+    let alg = jwt::decode_segment::<JwtHeader>(header)?.alg;
+    if alg != "HS256" {
+        return Err(JwtError::UnsupportedAlgorithm(alg));
+    }
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(JwtError::InvalidBase64)?;
+    mac(key, &token[..header.len() + 1 + payload.len()])
+        .verify_slice(&signature)
+        .map_err(|_| JwtError::InvalidSignature)?;
+    let claims = jwt::decode_claims(payload)?;
+    validation
+        .validate(&claims, now)
+        .map_err(JwtError::Validation)?;
+    Ok(claims)
+}
+
+fn mac(key: &[u8], signing_input: &str) -> Hmac<Sha256> {
+    // HMAC takes keys of any length, hashing longer ones, so this cannot fail.
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(signing_input.as_bytes());
+    mac
+}
@@ -0,0 +1,167 @@
+//! Helpers for record ids inside untyped `serde_json::Value` responses.
+
+use crate::key_value::thing_from_json;
+use crate::record_id_naked::KeyText;
+use serde_json::Value as Json;
+
+/// Rewrites record id objects at the top level of each result object into full (table:key)
+/// strings, in place.
+///
+/// `value` may be a single object or an array of objects, as returned by the HTTP API or by
+/// `query(...).take::<serde_json::Value>()`. Every field of those objects that holds a record
+/// id in SurrealDB's raw `Thing` shape, `{"tb": ..., "id": ...}`, is replaced by its full
+/// string, which covers the `id` field and any top-level relation fields. Nested values and
+/// fields that are already strings are left untouched.
+///
+/// ```
+/// use serde_json::json;
+///
+/// let mut rows = json!([
+///     {"id": {"tb": "user", "id": {"String": "abc"}}, "name": "Ada"},
+///     {"id": "user:def", "name": "Grace"},
+/// ]);
+/// assert_eq!(atopio_extra::normalize_ids(&mut rows), 1);
+/// assert_eq!(rows[0]["id"], "user:abc");
+/// assert_eq!(rows[1]["id"], "user:def");
+/// ```
+///
+/// Returns the number of fields that were rewritten.
+pub fn normalize_ids(value: &mut Json) -> usize {
+    match value {
+        Json::Array(rows) => rows.iter_mut().map(normalize_row).sum(),
+        row => normalize_row(row),
+    }
+}
+
+fn normalize_row(row: &mut Json) -> usize {
+    let Json::Object(fields) = row else {
+        return 0;
+    };
+    fields
+        .values_mut()
+        .map(|field| usize::from(stringify_thing(field)))
+        .sum()
+}
+
+/// Replaces `value` with the full string form if it is a record id object, returning whether
+/// it did.
+fn stringify_thing(value: &mut Json) -> bool {
+    is_thing_shaped(value) && convert(value, IdMode::Full)
+}
+
+/// Replaces a record id object with its string form in `mode`, returning whether it could.
+fn convert(value: &mut Json, mode: IdMode) -> bool {
+    match thing_from_json(value.clone()) {
+        Ok(id) => {
+            let text = match mode {
+                IdMode::Full => id.to_string(),
+                IdMode::Naked => KeyText(id.key()).to_string(),
+            };
+            *value = Json::String(text);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Returns `true` for objects with exactly a string `tb` field and an `id` field.
+fn is_thing_shaped(value: &Json) -> bool {
+    match value {
+        Json::Object(map) => {
+            map.len() == 2
+                && matches!(map.get("tb"), Some(Json::String(_)))
+                && map.contains_key("id")
+        }
+        _ => false,
+    }
+}
+
+/// How [`stringify_record_ids`] writes the record ids it finds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdMode {
+    /// The full (table:key) form, as written by [`crate::record_id_full`].
+    Full,
+    /// The key only, as written by [`crate::record_id_naked`].
+    Naked,
+}
+
+/// What [`stringify_record_ids`] did to a value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StringifyStats {
+    /// Record id objects replaced by strings.
+    pub converted: usize,
+    /// Record id objects that could not be converted, plus arrays and objects left unvisited
+    /// because they were nested deeper than the limit.
+    pub skipped: usize,
+}
+
+/// The nesting depth [`stringify_record_ids`] descends to.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// Rewrites every record id object anywhere in `value` into a string, in place.
+///
+/// Unlike [`normalize_ids`], this walks the whole tree, so ids inside nested arrays, fetched
+/// relations and graph edge results (`in`, `out`) are converted too. Any object with exactly a
+/// string `tb` field and an `id` field counts as a record id; record id objects are not
+/// searched further.
+///
+/// ```
+/// use atopio_extra::json::{IdMode, stringify_record_ids};
+/// use serde_json::json;
+///
+/// let mut edge = json!({
+///     "id": {"tb": "likes", "id": {"Number": 1}},
+///     "in": {"tb": "user", "id": {"String": "abc"}},
+///     "out": [{"tb": "post", "id": {"String": "hello"}}],
+/// });
+/// let stats = stringify_record_ids(&mut edge, IdMode::Full);
+/// assert_eq!(stats.converted, 3);
+/// assert_eq!(edge, json!({"id": "likes:1", "in": "user:abc", "out": ["post:hello"]}));
+/// ```
+///
+/// Arrays and objects nested more than [`DEFAULT_MAX_DEPTH`] levels deep are left as they
+/// are; use [`stringify_record_ids_to_depth`] to pick another limit.
+pub fn stringify_record_ids(value: &mut Json, mode: IdMode) -> StringifyStats {
+    stringify_record_ids_to_depth(value, mode, DEFAULT_MAX_DEPTH)
+}
+
+/// Like [`stringify_record_ids`], descending at most `max_depth` levels into nested arrays and
+/// objects.
+///
+/// With a `max_depth` of `0` only `value` itself is converted. Every array or object beyond the
+/// limit counts once in [`StringifyStats::skipped`].
+pub fn stringify_record_ids_to_depth(
+    value: &mut Json,
+    mode: IdMode,
+    max_depth: usize,
+) -> StringifyStats {
+    let mut stats = StringifyStats::default();
+    walk(value, mode, max_depth, &mut stats);
+    stats
+}
+
+fn walk(value: &mut Json, mode: IdMode, depth: usize, stats: &mut StringifyStats) {
+    if is_thing_shaped(value) {
+        if convert(value, mode) {
+            stats.converted += 1;
+        } else {
+            stats.skipped += 1;
+        }
+        return;
+    }
+    match value {
+        Json::Array(_) | Json::Object(_) if depth == 0 => stats.skipped += 1,
+        Json::Array(values) => {
+            for child in values {
+                walk(child, mode, depth - 1, stats);
+            }
+        }
+        Json::Object(fields) => {
+            for child in fields.values_mut() {
+                walk(child, mode, depth - 1, stats);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,563 @@
+//! Reading JWTs without verifying them.
+//!
+//! These functions decode what a token says about itself. They do not check the signature or
+//! any timestamp, so nothing they return can be trusted for authorization on its own. They are
+//! meant for picking a verification key, logging and debugging.
+
+use crate::types::{
+    Borrowed, ClaimsObject, MissingField, SurrealJWTClaims, SurrealJWTClaimsPartial,
+    SurrealJWTClaimsRef,
+};
+use crate::validation::ValidationError;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The header of a JWT: the signing algorithm, the token type, the key id and any other fields.
+///
+/// ```
+/// use atopio_extra::decode_header_insecurely;
+///
+/// // {"alg":"HS512","typ":"JWT","kid":"2024-06"}
+/// let token = "eyJhbGciOiJIUzUxMiIsInR5cCI6IkpXVCIsImtpZCI6IjIwMjQtMDYifQ.e30.c2ln";
+/// let header = decode_header_insecurely(token).unwrap();
+/// assert_eq!(header.alg, "HS512");
+/// assert_eq!(header.kid.as_deref(), Some("2024-06"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JwtHeader {
+    /// The signing algorithm, such as `HS512` or `RS256`.
+    pub alg: String,
+    /// The token type, usually `JWT`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+    /// The id of the key the token was signed with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    /// Every other header field.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Decodes a JWT header without any signature or timestamp validation.
+///
+/// Use it to look at `alg` or `kid` before choosing how to verify the token.
+///
+/// # Errors
+///
+/// Returns [`JwtError::MissingSegment`] if the token has no header, that is if it is empty or
+/// starts with a `.`, [`JwtError::InvalidBase64`] if the header is not base64url without
+/// padding, and [`JwtError::InvalidJson`] if the decoded header is not a JSON object with a
+/// string `alg`.
+pub fn decode_header_insecurely(token: &str) -> Result<JwtHeader, JwtError> {
+    let header = token.split('.').next().unwrap_or_default();
+    if header.is_empty() {
+        return Err(JwtError::MissingSegment("header"));
+    }
+    decode_segment(header)
+}
+
+/// Decodes a JWT payload into any type, without any signature or timestamp validation.
+///
+/// Use it for tokens that are not SurrealDB's, such as OIDC id tokens, with a struct of their
+/// own claims. [`decode_payload_insecurely`](crate::decode_payload_insecurely) is this function
+/// with `C` set to [`SurrealJWTClaims`].
+///
+/// ```
+/// use atopio_extra::decode_custom_payload_insecurely;
+///
+/// #[derive(serde::Deserialize)]
+/// struct OidcClaims {
+///     sub: String,
+///     tenant: Option<String>,
+/// }
+///
+/// // {"sub":"abc","tenant":"acme"}
+/// let token = "eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiJhYmMiLCJ0ZW5hbnQiOiJhY21lIn0.c2ln";
+/// let claims: OidcClaims = decode_custom_payload_insecurely(token).unwrap();
+/// assert_eq!(claims.sub, "abc");
+/// assert_eq!(claims.tenant.as_deref(), Some("acme"));
+/// ```
+///
+/// # Errors
+///
+/// Returns [`JwtError::MalformedToken`] if the token does not have three segments,
+/// [`JwtError::MissingSegment`] if one of them is empty, [`JwtError::InvalidBase64`] if the
+/// payload is not base64url without padding, and [`JwtError::InvalidJson`] if it does not
+/// deserialize into `C`.
+pub fn decode_custom_payload_insecurely<C>(token: &str) -> Result<C, JwtError>
+where
+    C: DeserializeOwned,
+{
+    let [_, payload, _] = split(token)?;
+    decode_segment(payload)
+}
+
+/// Decodes a JWT payload as plain JSON without any signature or timestamp validation.
+///
+/// Unlike [`decode_payload_insecurely`](crate::decode_payload_insecurely), the payload does
+/// not need the shape of [`SurrealJWTClaims`], so this also reads tokens from other issuers:
+///
+/// ```
+/// use atopio_extra::decode_payload_value_insecurely;
+///
+/// // {"sub":"abc","tenant":"acme"}
+/// let token = "eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiJhYmMiLCJ0ZW5hbnQiOiJhY21lIn0.c2ln";
+/// let payload = decode_payload_value_insecurely(token).unwrap();
+/// assert_eq!(payload["tenant"], "acme");
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`decode_custom_payload_insecurely`]: [`JwtError::InvalidJson`]
+/// is only returned if the payload is not JSON at all.
+pub fn decode_payload_value_insecurely(token: &str) -> Result<serde_json::Value, JwtError> {
+    decode_custom_payload_insecurely(token)
+}
+
+/// Decodes a JWT payload as [`SurrealJWTClaimsPartial`], without any signature or timestamp
+/// validation.
+///
+/// Unlike [`decode_payload_insecurely`](crate::decode_payload_insecurely), any of the claims may
+/// be absent. Call [`SurrealJWTClaimsPartial::try_into_full`] to require them all later.
+///
+/// ```
+/// use atopio_extra::decode_payload_partial_insecurely;
+///
+/// // {"exp":2,"NS":"app","DB":"main","AC":"user","ID":"user:1"}
+/// let token = "eyJhbGciOiJIUzI1NiJ9.eyJleHAiOjIsIk5TIjoiYXBwIiwiREIiOiJtYWluIiwiQUMiOiJ1c2VyIiwiSUQiOiJ1c2VyOjEifQ.c2ln";
+/// let claims = decode_payload_partial_insecurely::<String>(token).unwrap();
+/// assert_eq!(claims.ac.as_deref(), Some("user"));
+/// assert_eq!(claims.jti, None);
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`decode_custom_payload_insecurely`]. [`JwtError::InvalidJson`]
+/// is returned if a claim is present with the wrong type.
+pub fn decode_payload_partial_insecurely<T>(
+    token: &str,
+) -> Result<SurrealJWTClaimsPartial<T>, JwtError>
+where
+    T: DeserializeOwned,
+{
+    decode_custom_payload_insecurely(token)
+}
+
+/// Every part of a JWT, decoded, along with the text its signature covers.
+#[derive(Debug, Clone)]
+pub struct JwtParts<'a, T> {
+    /// The decoded header.
+    pub header: JwtHeader,
+    /// The decoded payload.
+    pub claims: SurrealJWTClaims<T>,
+    /// The signature bytes, decoded from base64url.
+    pub signature: Vec<u8>,
+    /// The `header.payload` part of the token, which is what the signature signs.
+    pub signing_input: &'a str,
+}
+
+/// Decodes the header, payload and signature of a JWT without any signature or timestamp
+/// validation.
+///
+/// The token must have exactly three non-empty segments, except that the signature is empty
+/// in an unsecured token, whose `alg` is `none`. The returned
+/// [`signing_input`](JwtParts::signing_input) borrows from `token`, so the token can be handed
+/// to a verifier as it is.
+///
+/// ```
+/// use atopio_extra::decode_parts_insecurely;
+///
+/// let token = concat!(
+///     "eyJhbGciOiJIUzUxMiJ9.",
+///     "eyJpYXQiOjEsIm5iZiI6MSwiZXhwIjoyLCJpc3MiOiJTdXJyZWFsREIiLCJqdGkiOiJqIiwiTlMi",
+///     "OiJ0ZXN0IiwiREIiOiJ0ZXN0IiwiQUMiOiJ1c2VyIiwiSUQiOiJ1c2VyOmFiYyJ9.",
+///     "c2ln",
+/// );
+/// let parts = decode_parts_insecurely::<String>(token).unwrap();
+/// assert_eq!(parts.header.alg, "HS512");
+/// assert_eq!(parts.claims.id, "user:abc");
+/// assert_eq!(parts.signature, b"sig");
+/// assert_eq!(parts.signing_input, token.rsplit_once('.').unwrap().0);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`JwtError::MalformedToken`] if the token does not have three segments,
+/// [`JwtError::MissingSegment`] if one of them is empty, [`JwtError::InvalidBase64`] if one is
+/// not base64url without padding, [`JwtError::MissingClaim`] if the payload lacks a claim of
+/// [`SurrealJWTClaims`], and [`JwtError::InvalidJson`] if the header or payload is otherwise not
+/// the expected JSON.
+pub fn decode_parts_insecurely<T>(token: &str) -> Result<JwtParts<'_, T>, JwtError>
+where
+    T: DeserializeOwned,
+{
+    let [header, payload, signature] = split(token)?;
+    Ok(JwtParts {
+        header: decode_segment(header)?,
+        claims: decode_claims(payload)?,
+        signature: URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(JwtError::InvalidBase64)?,
+        signing_input: &token[..header.len() + 1 + payload.len()],
+    })
+}
+
+/// Decodes a JWT payload into [`SurrealJWTClaimsRef`] without any signature or timestamp
+/// validation, borrowing its strings from `scratch`.
+///
+/// `scratch` is cleared and refilled with the decoded payload. Reusing one buffer across calls,
+/// such as one per worker, avoids allocating: once the buffer has grown to fit the payload, a
+/// decode into `SurrealJWTClaimsRef<&str>`, or with another `T` that borrows, allocates
+/// nothing.
+/// Use [`SurrealJWTClaimsRef::into_owned`] to keep the claims past the buffer's next use.
+///
+/// ```
+/// use atopio_extra::decode_payload_borrowed;
+///
+/// let token = concat!(
+///     "eyJhbGciOiJIUzUxMiJ9.",
+///     "eyJpYXQiOjEsIm5iZiI6MSwiZXhwIjoyLCJpc3MiOiJTdXJyZWFsREIiLCJqdGkiOiJqIiwiTlMi",
+///     "OiJ0ZXN0IiwiREIiOiJ0ZXN0IiwiQUMiOiJ1c2VyIiwiSUQiOiJ1c2VyOmFiYyJ9.",
+///     "c2ln",
+/// );
+/// let mut scratch = Vec::new();
+/// let claims = decode_payload_borrowed::<&str>(token, &mut scratch).unwrap();
+/// assert_eq!(claims.id, "user:abc");
+/// assert_eq!(claims.ac, "user");
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`decode_payload_insecurely`](crate::decode_payload_insecurely).
+/// A borrowing `T` such as `&str` also fails with [`JwtError::InvalidJson`] if the payload
+/// escapes a string it would borrow.
+pub fn decode_payload_borrowed<'a, T>(
+    token: &'a str,
+    scratch: &'a mut Vec<u8>,
+) -> Result<SurrealJWTClaimsRef<'a, T>, JwtError>
+where
+    T: Deserialize<'a>,
+{
+    let [_, payload, _] = split(token)?;
+    scratch.clear();
+    URL_SAFE_NO_PAD
+        .decode_vec(payload, scratch)
+        .map_err(JwtError::InvalidBase64)?;
+    let bytes: &'a [u8] = scratch;
+    let object: ClaimsObject<Borrowed<'a>, T> =
+        serde_json::from_slice(bytes).map_err(JwtError::InvalidJson)?;
+    SurrealJWTClaimsRef::try_from(object)
+        .map_err(|MissingField(claim)| JwtError::MissingClaim(claim))
+}
+
+/// The level a token grants access at, as returned by [`SurrealJWTClaims::kind`] and
+/// [`token_kind`].
+///
+/// These mirror SurrealDB's own levels: a root user signs in with no namespace, a namespace or
+/// database user with the namespace, or namespace and database, they are defined on, and a
+/// record user through an access method of a database, as a record of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// No `NS`: a root user.
+    Root,
+    /// `NS` but no `DB`: a namespace user.
+    Namespace,
+    /// `NS` and `DB`, but not both `AC` and `ID`, or with `RL` roles: a database user.
+    Database,
+    /// `NS`, `DB`, `AC` and `ID`, and no `RL` roles: a record user.
+    Record,
+}
+
+/// Returns the level the claims grant access at, from which of `NS`, `DB`, `AC` and `ID` are
+/// present and non-empty.
+///
+/// A token without `NS` is [`TokenKind::Root`], one with `NS` but no `DB` is
+/// [`TokenKind::Namespace`], and one with both is [`TokenKind::Record`] if it also has `AC` and
+/// `ID`, or [`TokenKind::Database`] otherwise. SurrealDB writes `RL` only for system users, so
+/// a token with roles is never a record token, even if a database user signed in through an
+/// access method and got an `AC` and `ID`.
+///
+/// ```
+/// use atopio_extra::{TokenKind, decode_payload_partial_insecurely, token_kind};
+///
+/// // {"exp":2,"NS":"app","DB":"main","AC":"user","ID":"user:1"}
+/// let token = "eyJhbGciOiJIUzI1NiJ9.eyJleHAiOjIsIk5TIjoiYXBwIiwiREIiOiJtYWluIiwiQUMiOiJ1c2VyIiwiSUQiOiJ1c2VyOjEifQ.c2ln";
+/// let claims = decode_payload_partial_insecurely::<String>(token).unwrap();
+/// assert_eq!(token_kind(&claims), TokenKind::Record);
+/// ```
+pub fn token_kind<T>(claims: &SurrealJWTClaimsPartial<T>) -> TokenKind {
+    kind(
+        claims.ns.as_deref(),
+        claims.db.as_deref(),
+        claims.ac.is_some(),
+        claims.id.as_deref(),
+        claims.rl.is_some(),
+    )
+}
+
+/// The heuristic behind [`token_kind`], over claims that are absent or empty alike.
+pub(crate) fn kind(
+    ns: Option<&str>,
+    db: Option<&str>,
+    ac: bool,
+    id: Option<&str>,
+    roles: bool,
+) -> TokenKind {
+    let present = |claim: Option<&str>| claim.is_some_and(|claim| !claim.is_empty());
+    if !present(ns) {
+        TokenKind::Root
+    } else if !present(db) {
+        TokenKind::Namespace
+    } else if ac && present(id) && !roles {
+        TokenKind::Record
+    } else {
+        TokenKind::Database
+    }
+}
+
+/// Splits a token into its header, payload and signature segments, checking that there are
+/// exactly three and that none is empty.
+pub(crate) fn split(token: &str) -> Result<[&str; 3], JwtError> {
+    let mut segments = token.split('.');
+    let (Some(header), Some(payload), Some(signature), None) = (
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+    ) else {
+        return Err(JwtError::MalformedToken {
+            parts: token.split('.').count(),
+        });
+    };
+    for (segment, name) in [(header, "header"), (payload, "payload")] {
+        if segment.is_empty() {
+            return Err(JwtError::MissingSegment(name));
+        }
+    }
+    if signature.is_empty() && !is_unsecured(header) {
+        return Err(JwtError::MissingSegment("signature"));
+    }
+    Ok([header, payload, signature])
+}
+
+/// Returns whether `header` declares an unsecured token, with `alg` set to `none`, which has
+/// an empty signature.
+fn is_unsecured(header: &str) -> bool {
+    decode_segment::<JwtHeader>(header).is_ok_and(|header| header.alg == "none")
+}
+
+/// Builds an unsigned token holding `claims`, with the header `{"alg":"none"}` and an empty
+/// signature.
+///
+/// This is for tests, and for local development against a server that does not verify
+/// tokens. The decoding functions of this crate accept the empty signature because of the
+/// `none` algorithm, so the claims read back as they were written:
+///
+/// ```
+/// use atopio_extra::{decode_payload_insecurely, encode_payload_insecurely};
+/// use atopio_extra::types::SurrealJWTClaims;
+///
+/// let claims: SurrealJWTClaims<String> = serde_json::from_str(
+///     r#"{"iat":1,"nbf":1,"exp":2,"iss":"SurrealDB","jti":"j","NS":"app","DB":"main","AC":"user","ID":"user:1"}"#,
+/// ).unwrap();
+/// let token = encode_payload_insecurely(&claims).unwrap();
+/// assert_eq!(token.split('.').next(), Some("eyJhbGciOiJub25lIn0"));
+/// assert!(token.ends_with('.'));
+/// assert_eq!(decode_payload_insecurely::<String>(&token).unwrap().id, "user:1");
+/// ```
+///
+/// # Errors
+///
+/// Returns [`JwtError::InvalidJson`] if `T` cannot be written as JSON, such as a map with
+/// keys that are not strings.
+pub fn encode_payload_insecurely<T>(claims: &SurrealJWTClaims<T>) -> Result<String, JwtError>
+where
+    T: Serialize,
+{
+    let header = JwtHeader {
+        alg: "none".into(),
+        typ: None,
+        kid: None,
+        extra: serde_json::Map::new(),
+    };
+    encode_payload_with_header_insecurely(&header, claims)
+}
+
+/// Builds a token holding `claims` under `header`, with an empty signature.
+///
+/// Use it to set `typ` or `kid` on a token from [`encode_payload_insecurely`]. With an `alg`
+/// other than `none`, the token claims a signature it does not have, so the decoding functions
+/// of this crate reject it with [`JwtError::MissingSegment`].
+///
+/// # Errors
+///
+/// Returns [`JwtError::InvalidJson`] if the header or `T` cannot be written as JSON.
+pub fn encode_payload_with_header_insecurely<T>(
+    header: &JwtHeader,
+    claims: &SurrealJWTClaims<T>,
+) -> Result<String, JwtError>
+where
+    T: Serialize,
+{
+    Ok(format!(
+        "{}.{}.",
+        encode_segment(header)?,
+        encode_segment(claims)?
+    ))
+}
+
+/// The claims of [`SurrealJWTClaims`], by their names in the payload.
+pub(crate) const CLAIMS: [&str; 9] = ["iat", "nbf", "exp", "iss", "jti", "NS", "DB", "AC", "ID"];
+
+/// Decodes the payload segment of a token as [`SurrealJWTClaims`], reporting a missing claim
+/// as [`JwtError::MissingClaim`].
+///
+/// The claims object is read first and checked for missing claims after, so a field missing
+/// inside `T` stays [`JwtError::InvalidJson`].
+pub(crate) fn decode_claims<T: DeserializeOwned>(
+    segment: &str,
+) -> Result<SurrealJWTClaims<T>, JwtError> {
+    let object: ClaimsObject<String, T> = decode_segment(segment)?;
+    SurrealJWTClaims::try_from(object).map_err(|MissingField(claim)| JwtError::MissingClaim(claim))
+}
+
+/// Encodes `value` as JSON in one base64url segment of a token.
+pub(crate) fn encode_segment<T: Serialize>(value: &T) -> Result<String, JwtError> {
+    let json = serde_json::to_vec(value).map_err(JwtError::InvalidJson)?;
+    Ok(URL_SAFE_NO_PAD.encode(json))
+}
+
+/// Decodes one base64url segment of a token as JSON.
+pub(crate) fn decode_segment<T: DeserializeOwned>(segment: &str) -> Result<T, JwtError> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(JwtError::InvalidBase64)?;
+    serde_json::from_slice(&bytes).map_err(JwtError::InvalidJson)
+}
+
+/// The reason a token could not be decoded.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum JwtError {
+    /// The token is not three segments separated by `.`, such as a bare payload or a JWE with
+    /// five segments. Holds the number of segments found.
+    MalformedToken {
+        /// The number of `.`-separated segments in the token.
+        parts: usize,
+    },
+    /// A segment of the token is missing or empty, such as the header of `".e30.c2ln"`. Holds
+    /// the segment's name: `header`, `payload` or `signature`. The signature may only be empty
+    /// if the header's `alg` is `none`.
+    MissingSegment(&'static str),
+    /// A segment is not base64url without padding, for example because it holds `+`, `/` or
+    /// `=`, which belong to the standard alphabet.
+    InvalidBase64(base64::DecodeError),
+    /// A segment decoded to bytes that are not the expected JSON, such as a header without
+    /// `alg`, a payload that is not an object, or a claim of the wrong type, like a string
+    /// `exp`. When encoding, a header or claims that cannot be written as JSON.
+    InvalidJson(serde_json::Error),
+    /// The payload is a JSON object without one of the claims of [`SurrealJWTClaims`], such as
+    /// a token from another issuer with no `NS`. Holds the claim's name as it appears in the
+    /// payload.
+    MissingClaim(&'static str),
+    /// The header names an algorithm other than the one the token is verified with, such as
+    /// `none` or `RS256` for an HS256 key. Holds the header's `alg`.
+    UnsupportedAlgorithm(String),
+    /// A verification key could not be read or used, such as a PEM file holding another kind
+    /// of key. Holds the underlying error.
+    InvalidKey(Box<dyn std::error::Error + Send + Sync>),
+    /// The token names a key by `kid` that the key set has no signing key for, or names no
+    /// key at all. Holds the header's `kid`, if any.
+    UnknownKeyId(Option<String>),
+    /// The key a token names has a type this crate cannot verify with, such as `OKP` or
+    /// `oct`. Holds the key's `kty`.
+    UnsupportedKeyType(String),
+    /// The signature does not match the token and key. The token was tampered with or signed
+    /// with another key, so it should be refused as unauthenticated.
+    InvalidSignature,
+    /// The signature is valid, but the claims failed a check of
+    /// [`Validation`](crate::Validation), for example because the token has expired. Holds
+    /// every check that failed.
+    Validation(ValidationError),
+}
+
+impl fmt::Display for JwtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedToken { parts } => {
+                write!(f, "expected a token with 3 segments, found {parts}")
+            }
+            Self::MissingSegment(segment) => write!(f, "the token has no {segment}"),
+            Self::InvalidBase64(_) => f.write_str("a token segment is not valid base64url"),
+            Self::InvalidJson(_) => f.write_str("a token segment is not the expected JSON"),
+            Self::MissingClaim(claim) => write!(f, "the token payload has no `{claim}` claim"),
+            Self::UnsupportedAlgorithm(alg) => {
+                write!(
+                    f,
+                    "the token is signed with {alg:?}, which is not supported"
+                )
+            }
+            Self::InvalidKey(_) => f.write_str("the verification key is not valid"),
+            Self::UnknownKeyId(Some(kid)) => write!(f, "the key set has no signing key {kid:?}"),
+            Self::UnknownKeyId(None) => f.write_str("the token header has no `kid`"),
+            Self::UnsupportedKeyType(kty) => {
+                write!(f, "keys of type {kty:?} are not supported")
+            }
+            Self::InvalidSignature => f.write_str("the token signature is not valid"),
+            Self::Validation(_) => f.write_str("the token claims failed validation"),
+        }
+    }
+}
+
+impl std::error::Error for JwtError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MalformedToken { .. }
+            | Self::MissingSegment(_)
+            | Self::MissingClaim(_)
+            | Self::UnsupportedAlgorithm(_)
+            | Self::UnknownKeyId(_)
+            | Self::UnsupportedKeyType(_)
+            | Self::InvalidSignature => None,
+            Self::InvalidBase64(e) => Some(e),
+            Self::InvalidJson(e) => Some(e),
+            Self::InvalidKey(e) => Some(e.as_ref()),
+            Self::Validation(e) => Some(e),
+        }
+    }
+}
+
+/// The claims a [`SurrealJWTClaimsPartial`] lacks, returned by
+/// [`SurrealJWTClaimsPartial::try_into_full`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingClaims(pub(crate) Vec<&'static str>);
+
+impl MissingClaims {
+    /// Returns the JSON names of the missing claims, such as `jti` or `NS`, in the order of the
+    /// fields of [`SurrealJWTClaims`].
+    pub fn claims(&self) -> &[&'static str] {
+        &self.0
+    }
+}
+
+impl fmt::Display for MissingClaims {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the token payload has no ")?;
+        for (i, claim) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{claim}`")?;
+        }
+        f.write_str(if self.0.len() == 1 {
+            " claim"
+        } else {
+            " claims"
+        })
+    }
+}
+
+impl std::error::Error for MissingClaims {}
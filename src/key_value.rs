@@ -0,0 +1,315 @@
+//! Record ids split into a table and a tagged key, and the natural JSON shape of keys.
+//!
+//! [`RecordIdParts`] is a wire format for services that want the table and the key as discrete
+//! fields, for example to map them onto protobuf messages. It is plain serde data and does not
+//! depend on how SurrealDB serializes its own types:
+//!
+//! ```
+//! use atopio_extra::key_value::{KeyValue, RecordIdParts};
+//!
+//! let id: surrealdb::RecordId = "user:42".parse().unwrap();
+//! let parts = RecordIdParts::try_from(&id).unwrap();
+//! assert_eq!(parts.key, KeyValue::Int(42));
+//! assert_eq!(
+//!     serde_json::to_string(&parts).unwrap(),
+//!     r#"{"table":"user","key":{"kind":"int","value":42}}"#
+//! );
+//! assert_eq!(surrealdb::RecordId::try_from(parts).unwrap(), id);
+//! ```
+//!
+//! It is unrelated to [`crate::RecordIdParts`], which holds the result of validating a record
+//! id string.
+//!
+//! Inside the crate, the helpers here convert keys to and from their natural JSON shape:
+//! string keys map to JSON strings, integer keys to JSON numbers, uuid keys to canonical
+//! (lowercase, hyphenated) uuid strings, and array and object keys to JSON arrays and objects.
+//! On the way back a string is only read as a uuid key when it is exactly in the canonical
+//! form, so every key written by `KeyJson` parses back into the same variant.
+
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value as Json;
+use std::collections::BTreeMap;
+use std::fmt;
+use surrealdb::sql::{Array, Id, Number, Object, Strand, Value};
+use surrealdb::{RecordId, RecordIdKey};
+
+/// A record id with its table and key as separate fields.
+///
+/// See the [module documentation](self) for the wire format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordIdParts {
+    /// The table name, without any escaping.
+    pub table: String,
+    /// The key.
+    pub key: KeyValue,
+}
+
+/// A record id key, tagged with its kind.
+///
+/// Serialized as an object with a `kind` field (`"string"`, `"int"`, `"uuid"`, `"array"` or
+/// `"object"`) and a `value` field. Array and object keys hold their elements as JSON values,
+/// so reading them needs a self-describing format such as JSON or CBOR.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum KeyValue {
+    /// A string key, as in `user:abc`.
+    String(String),
+    /// An integer key, as in `user:42`.
+    Int(i64),
+    /// A uuid key, as in `user:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'`.
+    Uuid(surrealdb::Uuid),
+    /// An array key, as in `reading:['sensor1', 1717000000]`.
+    Array(Vec<Json>),
+    /// An object key, as in `event:{ city: 'london' }`.
+    Object(serde_json::Map<String, Json>),
+}
+
+impl TryFrom<&RecordIdKey> for KeyValue {
+    type Error = KeyValueError;
+
+    /// Converts a key.
+    ///
+    /// Fails for keys that are neither strings, integers, uuids, arrays nor objects, such as
+    /// generated or range keys, and for array and object keys holding values that JSON cannot
+    /// represent, such as datetimes or decimals.
+    fn try_from(key: &RecordIdKey) -> Result<Self, KeyValueError> {
+        let value = match key.into_inner_ref() {
+            Id::Number(n) => return Ok(Self::Int(*n)),
+            Id::String(s) => return Ok(Self::String(s.clone())),
+            Id::Uuid(u) => return Ok(Self::Uuid(u.0)),
+            Id::Array(a) => Value::Array(a.clone()),
+            Id::Object(o) => Value::Object(o.clone()),
+            _ => {
+                return Err(KeyValueError(format!(
+                    "record id key {key} is not a string, integer, uuid, array or object"
+                )));
+            }
+        };
+        let json = Json::from(value.clone());
+        if value_from_json(json.clone()).as_ref() != Ok(&value) {
+            return Err(KeyValueError(format!(
+                "record id key {key} holds values that JSON cannot represent"
+            )));
+        }
+        match json {
+            Json::Array(values) => Ok(Self::Array(values)),
+            Json::Object(map) => Ok(Self::Object(map)),
+            _ => unreachable!("arrays and objects convert to JSON arrays and objects"),
+        }
+    }
+}
+
+impl TryFrom<KeyValue> for RecordIdKey {
+    type Error = KeyValueError;
+
+    fn try_from(value: KeyValue) -> Result<Self, KeyValueError> {
+        let id = match value {
+            KeyValue::String(s) => Id::String(s),
+            KeyValue::Int(n) => Id::Number(n),
+            KeyValue::Uuid(u) => Id::Uuid(u.into()),
+            KeyValue::Array(values) => Id::Array(array_from_json(values).map_err(KeyValueError)?),
+            KeyValue::Object(map) => Id::Object(object_from_json(map).map_err(KeyValueError)?),
+        };
+        Ok(RecordIdKey::from_inner(id))
+    }
+}
+
+impl TryFrom<&RecordId> for RecordIdParts {
+    type Error = KeyValueError;
+
+    /// Splits the id, failing if its key has no [`KeyValue`] form.
+    fn try_from(id: &RecordId) -> Result<Self, KeyValueError> {
+        Ok(Self {
+            table: id.table().to_owned(),
+            key: KeyValue::try_from(id.key())?,
+        })
+    }
+}
+
+impl TryFrom<RecordId> for RecordIdParts {
+    type Error = KeyValueError;
+
+    fn try_from(id: RecordId) -> Result<Self, KeyValueError> {
+        Self::try_from(&id)
+    }
+}
+
+impl TryFrom<RecordIdParts> for RecordId {
+    type Error = KeyValueError;
+
+    fn try_from(parts: RecordIdParts) -> Result<Self, KeyValueError> {
+        let key = RecordIdKey::try_from(parts.key)?;
+        Ok(RecordId::from_table_key(parts.table, key))
+    }
+}
+
+/// The reason a record id key and a [`KeyValue`] could not be converted into each other. A
+/// key may have no [`KeyValue`] form, as described in its `TryFrom<&RecordIdKey>`, and an
+/// array or object [`KeyValue`] may hold a string with a NUL byte, which SurrealDB cannot
+/// store. Holds the message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyValueError(String);
+
+impl fmt::Display for KeyValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for KeyValueError {}
+
+/// Serializes a record id key as its natural JSON value.
+pub(crate) struct KeyJson<'a>(pub(crate) &'a RecordIdKey);
+
+impl Serialize for KeyJson<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0.into_inner_ref() {
+            Id::Number(n) => serializer.serialize_i64(*n),
+            Id::String(s) => serializer.serialize_str(s),
+            Id::Uuid(u) => serializer.collect_str(&u.0.hyphenated()),
+            Id::Array(a) => Json::from(Value::Array(a.clone())).serialize(serializer),
+            Id::Object(o) => Json::from(Value::Object(o.clone())).serialize(serializer),
+            _ => serializer.collect_str(self.0),
+        }
+    }
+}
+
+/// Rebuilds a record id key from its natural JSON value.
+pub(crate) fn key_from_json(value: Json) -> Result<RecordIdKey, String> {
+    let id = match value {
+        Json::Number(n) => match n.as_i64() {
+            Some(n) => Id::Number(n),
+            None => return Err(format!("record id key {n} is not a 64-bit integer")),
+        },
+        Json::String(s) => match parse_canonical_uuid(&s) {
+            Some(u) => Id::Uuid(u.into()),
+            None => Id::String(s),
+        },
+        Json::Array(values) => Id::Array(array_from_json(values)?),
+        Json::Object(map) => Id::Object(object_from_json(map)?),
+        Json::Null | Json::Bool(_) => {
+            return Err(format!("{value} is not a valid record id key"));
+        }
+    };
+    Ok(RecordIdKey::from_inner(id))
+}
+
+/// Rebuilds a record id from SurrealDB's raw `Thing` object, `{"tb": ..., "id": ...}`.
+///
+/// The `id` may be a plain JSON key as accepted by [`key_from_json`], or tagged with its
+/// `sql::Id` variant name (`{"String": "abc"}`, `{"Number": 42}`, `{"Uuid": "..."}`,
+/// `{"Array": [...]}`, `{"Object": {...}}`) as written by SurrealDB's own serializer.
+pub(crate) fn thing_from_json(value: Json) -> Result<RecordId, String> {
+    let Json::Object(mut map) = value else {
+        return Err(format!("expected a record id object, got {value}"));
+    };
+    let table = match map.remove("tb") {
+        Some(Json::String(table)) => table,
+        Some(other) => return Err(format!("record id table must be a string, got {other}")),
+        None => return Err("missing field `tb`".to_owned()),
+    };
+    let Some(id) = map.remove("id") else {
+        return Err("missing field `id`".to_owned());
+    };
+    if let Some(field) = map.keys().next() {
+        return Err(format!("unknown field `{field}`, expected `tb` or `id`"));
+    }
+
+    let key = match id {
+        Json::Object(tagged) if tagged.len() == 1 => {
+            let (tag, inner) = tagged.into_iter().next().expect("one entry");
+            tagged_key_from_json(&table, tag, inner)?
+        }
+        id => key_from_json(id)?,
+    };
+    Ok(RecordId::from_table_key(table, key))
+}
+
+fn tagged_key_from_json(table: &str, tag: String, inner: Json) -> Result<RecordIdKey, String> {
+    let id = match (tag.as_str(), inner) {
+        ("String", Json::String(s)) => Id::String(s),
+        ("Number", Json::Number(n)) => match n.as_i64() {
+            Some(n) => Id::Number(n),
+            None => return Err(format!("record id key {n} is not a 64-bit integer")),
+        },
+        ("Uuid", Json::String(s)) => match surrealdb::Uuid::try_parse(&s) {
+            Ok(u) => Id::Uuid(u.into()),
+            Err(e) => return Err(format!("invalid uuid record id key {s:?}: {e}")),
+        },
+        ("Array" | "Object", inner) => {
+            // SurrealDB writes nested values in its own tagged encoding; let it read them back
+            // before falling back to treating the contents as plain JSON.
+            let native = Json::Object(
+                [
+                    ("tb".to_owned(), Json::String(table.to_owned())),
+                    (
+                        "id".to_owned(),
+                        Json::Object([(tag.clone(), inner.clone())].into_iter().collect()),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            );
+            if let Ok(id) = serde_json::from_value::<RecordId>(native) {
+                return Ok(id.key().clone());
+            }
+            match inner {
+                inner @ (Json::Array(_) | Json::Object(_)) => return key_from_json(inner),
+                other => return Err(format!("invalid array or object record id key {other}")),
+            }
+        }
+        ("String" | "Number" | "Uuid", other) => {
+            return Err(format!("invalid {tag} record id key {other}"));
+        }
+        (tag, inner) => {
+            // Not a variant tag, so this is an ordinary object key with a single field.
+            let mut map = serde_json::Map::new();
+            map.insert(tag.to_owned(), inner);
+            return key_from_json(Json::Object(map));
+        }
+    };
+    Ok(RecordIdKey::from_inner(id))
+}
+
+/// Parses `s` as a uuid only if it is in the canonical lowercase hyphenated form.
+pub(crate) fn parse_canonical_uuid(s: &str) -> Option<surrealdb::Uuid> {
+    let uuid = surrealdb::Uuid::try_parse(s).ok()?;
+    let mut buf = surrealdb::Uuid::encode_buffer();
+    (uuid.hyphenated().encode_lower(&mut buf) == s).then_some(uuid)
+}
+
+fn value_from_json(value: Json) -> Result<Value, String> {
+    Ok(match value {
+        Json::Null => Value::Null,
+        Json::Bool(b) => Value::Bool(b),
+        Json::Number(n) => match n.as_i64() {
+            Some(i) => Value::Number(Number::Int(i)),
+            None => Value::Number(Number::Float(n.as_f64().unwrap_or(f64::NAN))),
+        },
+        // `Strand` panics on interior NUL bytes, so refuse them before converting.
+        Json::String(s) if s.contains('\0') => {
+            return Err(format!("record id key string {s:?} contains a NUL byte"));
+        }
+        Json::String(s) => Value::Strand(Strand::from(s)),
+        Json::Array(values) => Value::Array(array_from_json(values)?),
+        Json::Object(map) => Value::Object(object_from_json(map)?),
+    })
+}
+
+fn array_from_json(values: Vec<Json>) -> Result<Array, String> {
+    values
+        .into_iter()
+        .map(value_from_json)
+        .collect::<Result<Vec<_>, _>>()
+        .map(Array::from)
+}
+
+fn object_from_json(map: serde_json::Map<String, Json>) -> Result<Object, String> {
+    map.into_iter()
+        .map(|(k, v)| Ok((k, value_from_json(v)?)))
+        .collect::<Result<BTreeMap<_, _>, String>>()
+        .map(Object::from)
+}
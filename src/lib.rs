@@ -1,153 +1,117 @@
+#[cfg(feature = "axum")]
+pub mod axum;
+pub mod builder;
+pub mod claims_builder;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod clock;
+pub mod collate;
+#[cfg(feature = "compat-v1")]
+pub mod compat_v1;
+#[cfg(any(feature = "sha256", feature = "blake3"))]
+pub mod content_id;
+pub mod cursor;
+pub mod ext;
+pub mod external_ref;
+pub mod global_id;
+pub mod graph;
+#[cfg(feature = "hs256")]
+pub mod hs256;
+pub mod json;
+pub mod jwt;
+pub mod key_value;
+pub mod link;
+pub mod parse;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+pub mod range;
+pub mod record_id_full;
+pub mod record_id_key;
+pub mod record_id_naked;
+pub mod record_id_object;
+pub mod record_id_redacted;
+pub mod record_table;
+#[cfg(feature = "rkyv")]
+pub mod rkyv;
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "serde_with")]
+pub mod serde_as;
+pub mod slug;
+pub mod surrealql;
+pub mod table;
+pub mod thing;
+pub mod thing_full;
+pub mod thing_naked;
+pub mod typed_id;
 pub mod types;
+#[cfg(any(feature = "ts-rs", feature = "specta"))]
+mod typescript;
+#[cfg(feature = "uuid")]
+pub mod uuid_v7;
+pub mod validation;
+#[cfg(feature = "verify")]
+pub mod verify;
+
+#[cfg(feature = "derive")]
+pub use atopio_extra_derive::{SurrealTable, surreal_model};
+pub use builder::{RecordIdBuildError, RecordIdBuilder};
+pub use claims_builder::{ClaimsBuildError, SurrealJWTClaimsBuilder};
+pub use collate::collate;
+#[cfg(any(feature = "sha256", feature = "blake3"))]
+pub use content_id::{DeriveIdError, derive_id_from};
+pub use ext::{FullIdRef, KeyKind, KeyTypeError, NakedKey, RecordIdExt, RecordIdRedacted};
+pub use external_ref::ExternalRef;
+#[cfg(feature = "hs256")]
+pub use hs256::{sign_hs256, verify_hs256, verify_hs256_with};
+pub use json::{IdMode, normalize_ids, stringify_record_ids};
+pub use jwt::{
+    JwtError, JwtHeader, JwtParts, MissingClaims, TokenKind, decode_custom_payload_insecurely,
+    decode_header_insecurely, decode_parts_insecurely, decode_payload_borrowed,
+    decode_payload_partial_insecurely, decode_payload_value_insecurely, encode_payload_insecurely,
+    encode_payload_with_header_insecurely, token_kind,
+};
+pub use key_value::KeyValue;
+pub use parse::{
+    RecordIdParseError, RecordIdParts, parse_record_id_lenient, validate_record_id_str,
+};
+pub use range::RecordIdRange;
+pub use surrealql::{key_to_surrealql, to_surrealql, to_surrealql_param_name};
+pub use table::{Table, TableAliasMap, TableAliasSeed};
+pub use typed_id::TypedId;
+#[cfg(feature = "uuid")]
+pub use uuid_v7::{key_uuid_timestamp, new_uuid_v7_id};
+pub use validation::{
+    ExpectationError, ExpectationErrorRedacted, Expectations, Validation, ValidationError,
+    ValidationFailure,
+};
+#[cfg(feature = "verify")]
+pub use verify::{
+    Jwk, Jwks, VerifyingKey, verify_with_jwks, verify_with_jwks_at, verify_with_key,
+    verify_with_key_at,
+};
 
 use crate::types::SurrealJWTClaims;
-use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
-use serde::Deserialize;
+use serde::Serialize;
 use serde::de::DeserializeOwned;
-use serde::{Deserializer, Serialize, de::Error};
 
 /// Decodes a JWT payload without any signature or timestamp validation.
 ///
+/// This is the lightweight counterpart of [`decode_parts_insecurely`]: the header and signature
+/// are checked to be present but are not decoded. For payloads that are not
+/// [`SurrealJWTClaims`], use [`decode_custom_payload_insecurely`].
+///
 /// # Errors
-/// This function will return an error if:
-/// - The token does not have three parts separated by dots.
-/// - The payload is not valid Base64Url.
-/// - The decoded payload is not valid JSON or doesn't match the Claims struct.
-pub fn decode_payload_insecurely<T>(
-    token: &str,
-) -> Result<SurrealJWTClaims<T>, Box<dyn std::error::Error>>
+///
+/// Returns [`JwtError::MalformedToken`] if the token does not have three segments,
+/// [`JwtError::MissingSegment`] if one of them is empty, [`JwtError::InvalidBase64`] if the
+/// payload is not base64url without padding, [`JwtError::MissingClaim`] if it lacks a claim
+/// of [`SurrealJWTClaims`], and [`JwtError::InvalidJson`] if it is otherwise not the expected
+/// JSON.
+pub fn decode_payload_insecurely<T>(token: &str) -> Result<SurrealJWTClaims<T>, JwtError>
 where
     T: DeserializeOwned + Serialize,
 {
-    let mut parts = token.split('.');
-
-    let payload_b64 = parts.nth(1).ok_or("Invalid JWT format: missing payload")?;
-
-    let decoded_payload_bytes = URL_SAFE_NO_PAD.decode(payload_b64)?;
-
-    let claims: SurrealJWTClaims<T> = serde_json::from_slice(&decoded_payload_bytes)?;
-
-    Ok(claims)
-}
-
-pub mod record_id_full {
-    use super::*;
-    use std::str::FromStr;
-
-    /// Serialize a `surrealdb::RecordId` as its full string representation.
-    ///
-    /// This helper is intended for use with `#[serde(with = "...")]` on fields of type
-    /// `surrealdb::RecordId`. It serializes the ID to a JSON string using the format produced
-    /// by `RecordId::to_string()`, which includes the table and key (for example: `"user:abc123"`).
-    pub fn serialize<S>(id: &surrealdb::RecordId, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_str(&id.to_string())
-    }
-
-    /// Serialize an `Option<surrealdb::RecordId>` in the full (table:key) form.
-    ///
-    /// This helper is intended for use with `#[serde(with = "...")]` on fields of type
-    /// `Option<surrealdb::RecordId>`. When the option is `Some`, the contained `RecordId`
-    /// is serialized as the full textual representation produced by `RecordId::to_string()`
-    /// (for example: `"user:abc123"`). When the option is `None`, a JSON `null` is emitted.
-    pub fn serialize_opt<S>(
-        id: &Option<surrealdb::RecordId>,
-        serializer: S,
-    ) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        match id {
-            Some(record_id) => serializer.serialize_str(&record_id.to_string()),
-            None => serializer.serialize_none(),
-        }
-    }
-
-    /// Deserialize a JSON string into a `surrealdb::RecordId`.
-    ///
-    /// This is the counterpart to `serialize` and expects the JSON value to be a string
-    /// containing the full record id (table:key). If the input string cannot be parsed by
-    /// `surrealdb::RecordId::from_str`, this function converts the parsing error into a
-    /// `serde` deserialization error.
-    ///
-    /// # Errors
-    ///
-    /// Returns a deserialization error if the provided JSON value is not a string or
-    /// if the string is not a valid SurrealDB record id.
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<surrealdb::RecordId, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        surrealdb::RecordId::from_str(&s).map_err(D::Error::custom)
-    }
-
-    /// Deserialize an `Option<surrealdb::RecordId>` from the full (table:key) form.
-    ///
-    /// This is the counterpart to `serialize_opt` and expects either a JSON string
-    /// containing the full record id (for example `"user:abc123"`) or `null`.
-    /// If a string is provided, it attempts to parse it with `surrealdb::RecordId::from_str`.
-    /// If parsing fails, the error is converted into a serde deserialization error.
-    ///
-    /// # Errors
-    ///
-    /// Returns a deserialization error if the JSON value is not a string or `null`, or if
-    /// the string is not a valid SurrealDB `RecordId`.
-    pub fn deserialize_opt<'de, D>(deserializer: D) -> Result<Option<surrealdb::RecordId>, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let opt = Option::<String>::deserialize(deserializer)?;
-        match opt {
-            Some(s) => {
-                let record_id = surrealdb::RecordId::from_str(&s).map_err(D::Error::custom)?;
-                Ok(Some(record_id))
-            }
-            None => Ok(None),
-        }
-    }
-}
-
-pub mod record_id_naked {
-
-    /// Serialize the key portion of a `surrealdb::RecordId` (the "naked" id).
-    ///
-    /// This helper is intended for use with `#[serde(with = "...")]` on fields of type
-    /// `surrealdb::RecordId`. It serializes only the key portion (the part after the table
-    /// separator) as a JSON string — akin to traditional SQL IDs where only the numeric or
-    /// key portion is stored or referenced.
-    pub fn serialize<S>(id: &surrealdb::RecordId, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let naked_id = id.key().to_string();
-        serializer.serialize_str(&naked_id)
-    }
-
-    /// Serialize an `Option<surrealdb::RecordId>` as the naked key (key only).
-    ///
-    /// Intended for use with `#[serde(with = "...")]` on fields of type
-    /// `Option<surrealdb::RecordId>`. When the option is `Some`, only the key portion
-    /// (the part after the table separator) is serialized as a JSON string (for example:
-    /// `"abc123"`). When the option is `None`, a JSON `null` is emitted.
-    ///
-    /// This shape is useful when you want IDs to resemble single-column identifiers,
-    /// akin to traditional SQL IDs.
-    pub fn serialize_opt<S>(
-        id: &Option<surrealdb::RecordId>,
-        serializer: S,
-    ) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        match id {
-            Some(record_id) => {
-                let naked_id = record_id.key().to_string();
-                serializer.serialize_str(&naked_id)
-            }
-            None => serializer.serialize_none(),
-        }
-    }
+    let [_, payload, _] = jwt::split(token)?;
+    jwt::decode_claims(payload)
 }
@@ -0,0 +1,308 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use surrealdb::RecordId;
+
+/// A type that knows its own record id.
+///
+/// Implement this for record structs so containers such as [`RecordLink`] can return the id of
+/// a fetched record without having to keep a separate copy of it.
+pub trait HasId {
+    /// Returns the record id of this record.
+    fn id(&self) -> &RecordId;
+}
+
+/// A relation field that holds either a record id or the record it points to.
+///
+/// Without a `FETCH` clause SurrealDB returns a relation as a record id; with `FETCH` it
+/// returns the embedded record. `RecordLink<T>` accepts both shapes for the same field.
+/// Deserialization tries the record id forms first (a full `table:key` string, or SurrealDB's
+/// native record id encoding) and falls back to `T`. Serialization writes whichever variant is
+/// held, using the full string form for ids.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum RecordLink<T> {
+    /// An unfetched link, holding only the record id.
+    Id(#[serde(with = "crate::record_id_full")] RecordId),
+    /// A fetched link, holding the full record.
+    Record(T),
+}
+
+impl<T> RecordLink<T> {
+    /// Returns the fetched record, or `None` if only the id is present.
+    pub fn record(&self) -> Option<&T> {
+        match self {
+            Self::Id(_) => None,
+            Self::Record(record) => Some(record),
+        }
+    }
+
+    /// Consumes the link and returns the fetched record, or `None` if only the id is present.
+    pub fn into_record(self) -> Option<T> {
+        match self {
+            Self::Id(_) => None,
+            Self::Record(record) => Some(record),
+        }
+    }
+
+    /// Returns `true` if the link holds the full record.
+    pub fn is_fetched(&self) -> bool {
+        matches!(self, Self::Record(_))
+    }
+}
+
+impl<T: HasId> RecordLink<T> {
+    /// Returns the id of the linked record, whichever variant is held.
+    pub fn id(&self) -> &RecordId {
+        match self {
+            Self::Id(id) => id,
+            Self::Record(record) => record.id(),
+        }
+    }
+}
+
+impl<T> From<RecordId> for RecordLink<T> {
+    fn from(value: RecordId) -> Self {
+        Self::Id(value)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for RecordLink<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr<T> {
+            Full(#[serde(with = "crate::record_id_full")] RecordId),
+            Native(RecordId),
+            Record(T),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Full(id) | Repr::Native(id) => Self::Id(id),
+            Repr::Record(record) => Self::Record(record),
+        })
+    }
+}
+
+/// A to-one relation field that may be fetched, and is always written back as an id.
+///
+/// Reads like [`RecordLink`]: either a record id or, after `FETCH`, the record itself. Unlike
+/// `RecordLink`, it serializes as the full id string whichever shape was read, so a model read
+/// with `FETCH` can be sent straight back in an `UPDATE` without embedding the related record.
+///
+/// ```
+/// use atopio_extra::link::{HasId, LinkOne};
+///
+/// #[derive(serde::Deserialize)]
+/// struct User {
+///     #[serde(with = "atopio_extra::record_id_full")]
+///     id: surrealdb::RecordId,
+///     name: String,
+/// }
+///
+/// impl HasId for User {
+///     fn id(&self) -> &surrealdb::RecordId {
+///         &self.id
+///     }
+/// }
+///
+/// let author: LinkOne<User> = serde_json::from_str(r#"{"id":"user:abc","name":"Ada"}"#).unwrap();
+/// assert_eq!(author.record().map(|u| u.name.as_str()), Some("Ada"));
+/// assert_eq!(serde_json::to_string(&author).unwrap(), r#""user:abc""#);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkOne<T> {
+    id: RecordId,
+    record: Option<T>,
+}
+
+impl<T> LinkOne<T> {
+    /// Creates an unfetched link.
+    pub fn new(id: RecordId) -> Self {
+        Self { id, record: None }
+    }
+
+    /// Returns the id of the linked record.
+    pub fn id(&self) -> &RecordId {
+        &self.id
+    }
+
+    /// Returns the fetched record, or `None` if only the id is present.
+    pub fn record(&self) -> Option<&T> {
+        self.record.as_ref()
+    }
+
+    /// Consumes the link and returns the fetched record, or `None` if only the id is present.
+    pub fn into_record(self) -> Option<T> {
+        self.record
+    }
+
+    /// Returns `true` if the link holds the full record.
+    pub fn is_fetched(&self) -> bool {
+        self.record.is_some()
+    }
+
+    /// Points the link at another record, dropping any fetched record.
+    pub fn set_id(&mut self, id: RecordId) {
+        self.id = id;
+        self.record = None;
+    }
+}
+
+impl<T: HasId> LinkOne<T> {
+    /// Creates a fetched link.
+    pub fn from_record(record: T) -> Self {
+        Self {
+            id: record.id().clone(),
+            record: Some(record),
+        }
+    }
+}
+
+impl<T> From<RecordId> for LinkOne<T> {
+    fn from(value: RecordId) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> Serialize for LinkOne<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        crate::record_id_full::serialize(&self.id, serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for LinkOne<T>
+where
+    T: HasId + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match RecordLink::deserialize(deserializer)? {
+            RecordLink::Id(id) => Self::new(id),
+            RecordLink::Record(record) => Self::from_record(record),
+        })
+    }
+}
+
+/// A to-many relation field that may be fetched, and is always written back as ids.
+///
+/// Reads an array whose elements are each a record id or a fetched record, and serializes as
+/// an array of full id strings. [`records`](Self::records) returns the records only when every
+/// element was fetched; the ids are always available.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkMany<T> {
+    ids: Vec<RecordId>,
+    records: Option<Vec<T>>,
+}
+
+impl<T> LinkMany<T> {
+    /// Creates unfetched links to `ids`.
+    pub fn from_ids(ids: impl IntoIterator<Item = RecordId>) -> Self {
+        Self {
+            ids: ids.into_iter().collect(),
+            records: None,
+        }
+    }
+
+    /// Returns the ids of the linked records, in order.
+    pub fn ids(&self) -> Vec<&RecordId> {
+        self.ids.iter().collect()
+    }
+
+    /// Returns the fetched records, or `None` unless every link was fetched.
+    ///
+    /// An empty list counts as fetched.
+    pub fn records(&self) -> Option<&[T]> {
+        self.records.as_deref()
+    }
+
+    /// Consumes the links and returns the fetched records, or `None` unless every link was
+    /// fetched.
+    pub fn into_records(self) -> Option<Vec<T>> {
+        self.records
+    }
+
+    /// Returns the number of links.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns `true` if there are no links.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Replaces the links with unfetched links to `ids`, dropping any fetched records.
+    pub fn set_ids(&mut self, ids: impl IntoIterator<Item = RecordId>) {
+        *self = Self::from_ids(ids);
+    }
+}
+
+impl<T: HasId> LinkMany<T> {
+    /// Creates fetched links to `records`.
+    pub fn from_records(records: Vec<T>) -> Self {
+        Self {
+            ids: records.iter().map(|record| record.id().clone()).collect(),
+            records: Some(records),
+        }
+    }
+}
+
+impl<T> Default for LinkMany<T> {
+    fn default() -> Self {
+        Self::from_ids([])
+    }
+}
+
+impl<T> FromIterator<RecordId> for LinkMany<T> {
+    fn from_iter<I: IntoIterator<Item = RecordId>>(iter: I) -> Self {
+        Self::from_ids(iter)
+    }
+}
+
+impl<T> Serialize for LinkMany<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        crate::record_id_full::vec::serialize(&self.ids, serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for LinkMany<T>
+where
+    T: HasId + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let links = Vec::<RecordLink<T>>::deserialize(deserializer)?;
+        let mut ids = Vec::with_capacity(links.len());
+        let mut records = Some(Vec::with_capacity(links.len()));
+        for link in links {
+            match link {
+                RecordLink::Id(id) => {
+                    ids.push(id);
+                    records = None;
+                }
+                RecordLink::Record(record) => {
+                    ids.push(record.id().clone());
+                    if let Some(records) = &mut records {
+                        records.push(record);
+                    }
+                }
+            }
+        }
+        Ok(Self { ids, records })
+    }
+}
@@ -0,0 +1,252 @@
+use std::fmt;
+use std::str::FromStr;
+use surrealdb::RecordId;
+use surrealdb::sql::Id;
+
+/// A record id string that passed [`validate_record_id_str`], split into its parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordIdParts {
+    table: String,
+    key: String,
+    id: RecordId,
+}
+
+impl RecordIdParts {
+    /// Returns the table name, without any escaping.
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Returns the key text exactly as written after the table separator, for example
+    /// `abc123`, `⟨has space⟩` or `['a', 1]`.
+    pub fn key_text(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the parsed record id.
+    pub fn record_id(&self) -> &RecordId {
+        &self.id
+    }
+
+    /// Consumes the parts and returns the parsed record id.
+    pub fn into_record_id(self) -> RecordId {
+        self.id
+    }
+}
+
+/// The reason a string is not a valid record id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RecordIdParseError {
+    /// There is no table before the separator, as in `":abc"` or `"abc"`.
+    MissingTable,
+    /// There is nothing after the separator, as in `"user:"`.
+    EmptyKey,
+    /// The table is not a valid identifier, as in `"us-er:abc"`. Holds the table text.
+    InvalidTableIdent(String),
+    /// A `⟨` or `` ` `` escape is never closed, as in `"user:⟨unclosed"`.
+    UnbalancedEscape,
+    /// The key is followed by unexpected text, as in `"user:abc def"`. Holds that text.
+    TrailingInput(String),
+    /// The key is not valid SurrealQL key syntax. Holds the parser's message.
+    InvalidKey(String),
+}
+
+impl fmt::Display for RecordIdParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingTable => f.write_str("record id is missing a table before `:`"),
+            Self::EmptyKey => f.write_str("record id is missing a key after `:`"),
+            Self::InvalidTableIdent(table) => {
+                write!(f, "record id table {table:?} is not a valid identifier")
+            }
+            Self::UnbalancedEscape => {
+                f.write_str("record id has an unclosed `⟨` or `` ` `` escape")
+            }
+            Self::TrailingInput(rest) => {
+                write!(f, "record id has unexpected trailing input {rest:?}")
+            }
+            Self::InvalidKey(reason) => write!(f, "record id key is invalid: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for RecordIdParseError {}
+
+/// Validates a full (table:key) record id string and reports what is wrong with it.
+///
+/// This accepts exactly the strings `surrealdb::RecordId::from_str` accepts (apart from
+/// generated keys such as `rand()`, which do not name a record), but instead of an opaque
+/// parser error it returns a [`RecordIdParseError`] describing the problem, which is suitable
+/// for showing to API clients:
+///
+/// ```
+/// use atopio_extra::{RecordIdParseError, validate_record_id_str};
+///
+/// let parts = validate_record_id_str("user:⟨has space⟩").unwrap();
+/// assert_eq!(parts.table(), "user");
+/// assert_eq!(parts.key_text(), "⟨has space⟩");
+///
+/// assert_eq!(validate_record_id_str("user:"), Err(RecordIdParseError::EmptyKey));
+/// assert_eq!(validate_record_id_str(":abc"), Err(RecordIdParseError::MissingTable));
+/// assert_eq!(validate_record_id_str("user:⟨unclosed"), Err(RecordIdParseError::UnbalancedEscape));
+/// ```
+///
+/// # Errors
+///
+/// Returns the first problem found, checking the table before the key.
+pub fn validate_record_id_str(s: &str) -> Result<RecordIdParts, RecordIdParseError> {
+    let input = s.trim();
+
+    let (table, rest) = split_table(input)?;
+    let key = rest.trim();
+    if key.is_empty() {
+        return Err(RecordIdParseError::EmptyKey);
+    }
+    if let Some(after) = skip_escape(key)? {
+        let after = after.trim();
+        if !after.is_empty() {
+            return Err(RecordIdParseError::TrailingInput(after.to_owned()));
+        }
+    }
+
+    let id = match RecordId::from_str(input) {
+        Ok(id) => id,
+        Err(e) => return Err(key_error(key, &e.to_string())),
+    };
+    match id.key().into_inner_ref() {
+        Id::Number(_) | Id::String(_) | Id::Uuid(_) | Id::Array(_) | Id::Object(_) => {}
+        _ => {
+            return Err(RecordIdParseError::InvalidKey(format!(
+                "{key} generates a new key instead of naming a record"
+            )));
+        }
+    }
+
+    Ok(RecordIdParts {
+        table,
+        key: key.to_owned(),
+        id,
+    })
+}
+
+/// Parses a record id pasted by a person, tolerating the usual copy/paste noise.
+///
+/// Exactly these normalizations are applied before the input is validated like
+/// [`validate_record_id_str`]:
+///
+/// 1. Leading and trailing whitespace is removed.
+/// 2. One pair of matching surrounding quotes (`"..."` or `'...'`) is removed, and whitespace
+///    inside them is trimmed again.
+///
+/// Keys may be escaped with either `⟨...⟩` or backticks, as SurrealQL allows; both parse to
+/// the same `RecordId`. Nothing else is rewritten, so the result is the id the remaining text
+/// names. Because quotes and whitespace are silently dropped, use this for input typed or
+/// pasted by trusted users, such as an admin tool, and prefer the strict parsers elsewhere.
+///
+/// ```
+/// use atopio_extra::parse_record_id_lenient;
+///
+/// let id = parse_record_id_lenient(" \"user:`weird key`\" ").unwrap();
+/// assert_eq!(id, parse_record_id_lenient("user:⟨weird key⟩").unwrap());
+/// assert_eq!(id.to_string(), "user:⟨weird key⟩");
+/// ```
+///
+/// # Errors
+///
+/// Returns the [`RecordIdParseError`] describing why the normalized text is not a record id.
+pub fn parse_record_id_lenient(input: &str) -> Result<RecordId, RecordIdParseError> {
+    let trimmed = input.trim();
+    let unquoted = ['"', '\'']
+        .into_iter()
+        .find_map(|quote| {
+            trimmed
+                .strip_prefix(quote)
+                .and_then(|rest| rest.strip_suffix(quote))
+        })
+        .unwrap_or(trimmed);
+    validate_record_id_str(unquoted).map(RecordIdParts::into_record_id)
+}
+
+/// Splits off the table and the separator, returning the unescaped table and the key text.
+fn split_table(input: &str) -> Result<(String, &str), RecordIdParseError> {
+    let (table, rest) = match skip_escape(input)? {
+        Some(rest) => {
+            let escaped = &input[..input.len() - rest.len()];
+            (unescape(escaped), rest)
+        }
+        None => {
+            let end = input
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(input.len());
+            (input[..end].to_owned(), &input[end..])
+        }
+    };
+
+    let Some(rest) = rest.trim_start().strip_prefix(':') else {
+        return Err(match input.split_once(':') {
+            Some((table, _)) if !table.trim().is_empty() => {
+                RecordIdParseError::InvalidTableIdent(table.trim().to_owned())
+            }
+            _ => RecordIdParseError::MissingTable,
+        });
+    };
+    if table.is_empty() {
+        return Err(RecordIdParseError::MissingTable);
+    }
+    if table.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(RecordIdParseError::InvalidTableIdent(table));
+    }
+    Ok((table, rest))
+}
+
+/// If `s` starts with a `⟨...⟩` or `` `...` `` escape, returns the text after it.
+fn skip_escape(s: &str) -> Result<Option<&str>, RecordIdParseError> {
+    let close = match s.chars().next() {
+        Some('⟨') => '⟩',
+        Some('`') => '`',
+        _ => return Ok(None),
+    };
+    let mut chars = s.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            c if c == close => return Ok(Some(&s[i + c.len_utf8()..])),
+            _ => {}
+        }
+    }
+    Err(RecordIdParseError::UnbalancedEscape)
+}
+
+fn unescape(escaped: &str) -> String {
+    let mut chars = escaped.chars();
+    chars.next();
+    chars.next_back();
+    let mut out = String::with_capacity(escaped.len());
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.extend(chars.next()),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Classifies a parser failure on the key text.
+fn key_error(key: &str, message: &str) -> RecordIdParseError {
+    let start = usize::from(key.starts_with('-'));
+    let end = key[start..]
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .map_or(key.len(), |i| i + start);
+    if end > start && end < key.len() {
+        return RecordIdParseError::TrailingInput(key[end..].trim_start().to_owned());
+    }
+    let reason = message
+        .lines()
+        .next()
+        .unwrap_or(message)
+        .trim_start_matches("Parse error: ");
+    RecordIdParseError::InvalidKey(reason.to_owned())
+}
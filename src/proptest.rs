@@ -0,0 +1,144 @@
+//! proptest strategies for record ids and token claims.
+//!
+//! Enabled by the `proptest` feature. The strategies produce the inputs serializers tend to get
+//! wrong: string keys holding characters that need escaping (spaces, quotes, `⟨⟩`, backticks,
+//! backslashes, `:`), string keys that look like numbers or keywords, `i64::MIN` and
+//! `i64::MAX`, uuids, and array and object keys nested a few levels deep.
+//!
+//! ```
+//! use atopio_extra::proptest::arb_record_id;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     // Add `#[test]` in a test module.
+//!     fn full_ids_round_trip(id in arb_record_id()) {
+//!         let text = serde_json::to_string(&atopio_extra::types::FullId(id.clone())).unwrap();
+//!         let back: atopio_extra::types::FullId = serde_json::from_str(&text).unwrap();
+//!         prop_assert_eq!(back.0, id);
+//!     }
+//! }
+//! # full_ids_round_trip();
+//! ```
+//!
+//! Every generated id can be written with `RecordId::to_string()` and parsed back. For that,
+//! table names are lowercase identifiers such as `user` or `order_item`, and the fields of
+//! object keys are identifiers too. String keys and the strings inside array and object keys
+//! are not limited.
+
+use crate::types::{Audience, SurrealJWTClaims};
+use ::proptest::prelude::*;
+use surrealdb::sql::{Array, Id, Number, Object, Strand, Value};
+use surrealdb::{RecordId, RecordIdKey};
+
+/// Returns a strategy for record ids in tables generated by [`arb_table`], with keys from
+/// [`arb_record_id_key`].
+pub fn arb_record_id() -> impl Strategy<Value = RecordId> {
+    (arb_table(), arb_record_id_key()).prop_map(|(table, key)| RecordId::from_table_key(table, key))
+}
+
+/// Returns a strategy for record ids in `table`, with keys from [`arb_record_id_key`].
+pub fn arb_record_id_with_table(table: &str) -> impl Strategy<Value = RecordId> + use<> {
+    let table = table.to_owned();
+    arb_record_id_key().prop_map(move |key| RecordId::from_table_key(table.as_str(), key))
+}
+
+/// Returns a strategy for table names: a lowercase letter followed by up to eleven lowercase
+/// letters, digits and `_`.
+pub fn arb_table() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9_]{0,11}".prop_filter("parses as a number", |t| t != "nan" && t != "infinity")
+}
+
+/// Returns a strategy for record id keys of every kind: strings, integers, uuids, arrays and
+/// objects.
+pub fn arb_record_id_key() -> impl Strategy<Value = RecordIdKey> {
+    prop_oneof![
+        3 => arb_key_string().prop_map(Id::String),
+        2 => arb_i64().prop_map(Id::Number),
+        1 => any::<[u8; 16]>().prop_map(|bytes| Id::Uuid(surrealdb::Uuid::from_bytes(bytes).into())),
+        1 => prop::collection::vec(arb_value(), 0..4).prop_map(|values| Id::Array(Array::from(values))),
+        1 => prop::collection::btree_map(arb_field_name(), arb_value(), 0..4)
+            .prop_map(|map| Id::Object(Object::from(map))),
+    ]
+    .prop_map(RecordIdKey::from_inner)
+}
+
+/// Returns a strategy for [`SurrealJWTClaims`] with an `AC` payload from `T`'s own strategy and
+/// an `ID` from [`arb_record_id`], with or without `RL` roles and an `aud` of either shape.
+pub fn arb_surreal_claims<T>() -> impl Strategy<Value = SurrealJWTClaims<T>>
+where
+    T: Arbitrary,
+{
+    let times = (any::<u64>(), any::<u64>(), any::<u64>());
+    let names = (".{0,12}", ".{0,12}", arb_table(), arb_table());
+    let roles = prop::option::of(prop::collection::vec("[A-Z]{1,8}", 0..3));
+    let audience = prop::option::of(prop_oneof![
+        ".{0,12}".prop_map(Audience::One),
+        prop::collection::vec(".{0,12}", 0..3).prop_map(Audience::Many),
+    ]);
+    (times, names, any::<T>(), arb_record_id(), (roles, audience)).prop_map(
+        |((iat, nbf, exp), (iss, jti, ns, db), ac, id, (rl, aud))| SurrealJWTClaims {
+            iat,
+            nbf,
+            exp,
+            iss,
+            jti,
+            ns,
+            db,
+            ac,
+            id: id.to_string(),
+            rl,
+            aud,
+        },
+    )
+}
+
+/// String keys, weighted towards text that needs escaping or reads as something else.
+fn arb_key_string() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-zA-Z_][a-zA-Z0-9_]{0,8}",
+        r"[a-z0-9 _`'\x22⟨⟩\\.:,-]{0,8}",
+        "-?[0-9]{1,4}(e[0-9])?",
+        // Any text without NUL, which SurrealDB strings cannot hold.
+        "[^\u{0}]{0,8}",
+        prop::sample::select(vec![
+            "", "NaN", "Infinity", "select", "NONE", "true", "null"
+        ])
+        .prop_map(str::to_owned),
+    ]
+}
+
+/// Field names of object keys. These are plain identifiers, which SurrealDB writes bare or
+/// quoted as needed. Its own writer gets a few other names wrong, such as `{ "": 1 }` written
+/// as `{ : 1 }` and `{ "NaN": 1 }` written as `{ NaN: 1 }`, neither of which parses back.
+fn arb_field_name() -> impl Strategy<Value = String> {
+    "[a-zA-Z_][a-zA-Z0-9_]{0,8}".prop_filter("parses as a number", |name| {
+        !name.eq_ignore_ascii_case("nan") && !name.eq_ignore_ascii_case("infinity")
+    })
+}
+
+fn arb_i64() -> impl Strategy<Value = i64> {
+    prop_oneof![
+        Just(i64::MIN),
+        Just(i64::MAX),
+        Just(0),
+        -1000i64..1000,
+        any::<i64>(),
+    ]
+}
+
+/// Values inside array and object keys, nested up to three levels.
+fn arb_value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        arb_i64().prop_map(|n| Value::Number(Number::Int(n))),
+        arb_key_string().prop_map(|s| Value::Strand(Strand::from(s))),
+    ];
+    leaf.prop_recursive(3, 16, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(|v| Value::Array(Array::from(v))),
+            prop::collection::btree_map(arb_field_name(), inner, 0..4)
+                .prop_map(|m| Value::Object(Object::from(m))),
+        ]
+    })
+}
@@ -0,0 +1,143 @@
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::Bound;
+use std::str::FromStr;
+use surrealdb::RecordIdKey;
+use surrealdb::sql::{Id, IdRange, Thing, Value};
+
+/// A range of record ids within one table, such as `user:1..100` or `temperature:⟨2024-01⟩..`.
+///
+/// SurrealDB selects every record whose key falls within the bounds. The range serializes as
+/// its textual SurrealQL form and parses back from it, including open-ended ranges
+/// (`user:100..`, `user:..100`), inclusive end bounds (`user:1..=100`) and exclusive start
+/// bounds (`user:1>..100`).
+///
+/// ```
+/// use atopio_extra::RecordIdRange;
+/// use std::ops::Bound;
+///
+/// let range: RecordIdRange = "user:1..=100".parse().unwrap();
+/// assert_eq!(range.table(), "user");
+/// assert_eq!(range.end(), &Bound::Included(100.into()));
+/// assert_eq!(range.to_string(), "user:1..=100");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordIdRange {
+    table: String,
+    start: Bound<RecordIdKey>,
+    end: Bound<RecordIdKey>,
+}
+
+impl RecordIdRange {
+    /// Creates a range over `table` between the given key bounds.
+    pub fn new(
+        table: impl Into<String>,
+        start: Bound<RecordIdKey>,
+        end: Bound<RecordIdKey>,
+    ) -> Self {
+        Self {
+            table: table.into(),
+            start,
+            end,
+        }
+    }
+
+    /// Returns the table the range selects from.
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Returns the lower bound of the range.
+    pub fn start(&self) -> &Bound<RecordIdKey> {
+        &self.start
+    }
+
+    /// Returns the upper bound of the range.
+    pub fn end(&self) -> &Bound<RecordIdKey> {
+        &self.end
+    }
+
+    fn to_thing(&self) -> Thing {
+        let inner = |key: &RecordIdKey| key.clone().into_inner();
+        let range = IdRange {
+            beg: self.start.as_ref().map(inner),
+            end: self.end.as_ref().map(inner),
+        };
+        Thing::from((self.table.clone(), range))
+    }
+}
+
+impl fmt::Display for RecordIdRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_thing(), f)
+    }
+}
+
+impl FromStr for RecordIdRange {
+    type Err = RecordIdRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RecordIdRangeError(s.to_owned());
+        let Ok(Value::Thing(thing)) = surrealdb::sql::value(s) else {
+            return Err(invalid());
+        };
+        let Id::Range(range) = thing.id else {
+            return Err(invalid());
+        };
+        let IdRange { beg, end } = *range;
+        let key = |id: Id| match id {
+            Id::Number(_) | Id::String(_) | Id::Uuid(_) | Id::Array(_) | Id::Object(_) => {
+                Ok(RecordIdKey::from_inner(id))
+            }
+            _ => Err(invalid()),
+        };
+        Ok(Self {
+            table: thing.tb,
+            start: bound(beg, key)?,
+            end: bound(end, key)?,
+        })
+    }
+}
+
+fn bound<E>(
+    bound: Bound<Id>,
+    key: impl Fn(Id) -> Result<RecordIdKey, E>,
+) -> Result<Bound<RecordIdKey>, E> {
+    Ok(match bound {
+        Bound::Included(id) => Bound::Included(key(id)?),
+        Bound::Excluded(id) => Bound::Excluded(key(id)?),
+        Bound::Unbounded => Bound::Unbounded,
+    })
+}
+
+impl Serialize for RecordIdRange {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for RecordIdRange {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+/// The error returned when a string is not a valid record id range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordIdRangeError(String);
+
+impl fmt::Display for RecordIdRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid record id range {:?}", self.0)
+    }
+}
+
+impl std::error::Error for RecordIdRangeError {}
@@ -0,0 +1,1365 @@
+use serde::de::{self, Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+use surrealdb::sql::Id;
+
+/// Serialize a `surrealdb::RecordId` as its full string representation.
+///
+/// This helper is intended for use with `#[serde(with = "...")]` on fields of type
+/// `surrealdb::RecordId`. It serializes the ID to a JSON string using the format produced
+/// by `RecordId::to_string()`, which includes the table and key (for example: `"user:abc123"`).
+///
+/// Composite keys are written in SurrealQL syntax with their strings quoted and escaped, so an
+/// array key such as `reading:['sensor1', 1717000000]` or an object key such as
+/// `event:{ city: 'london', year: 2024 }` parses back through [`deserialize`] into an equal
+/// `RecordId`, even when its strings contain commas, quotes, brackets or braces. Object fields
+/// are written in sorted order, so keys that differ only in field order compare equal. The
+/// string key `NaN` is written `user:⟨NaN⟩`, since a bare `NaN` would read back as a number.
+///
+/// Formats that are not human readable (as reported by `Serializer::is_human_readable`, for
+/// example CBOR, MessagePack, bincode or postcard) get `RecordId`'s own encoding instead of the
+/// string, so they round-trip without going through the SurrealQL parser. JSON output is
+/// unchanged.
+///
+/// The helpers in this module and in [`crate::record_id_naked`] are tested with JSON, CBOR
+/// (`ciborium`) and MessagePack (`rmp-serde`), including MessagePack configured as human
+/// readable, where the string forms are used and map keys are strings.
+pub fn serialize<S>(id: &surrealdb::RecordId, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if !serializer.is_human_readable() {
+        return id.serialize(serializer);
+    }
+    serializer.collect_str(&FullText(id, id.key().into_inner_ref()))
+}
+
+/// Serialize an `Option<surrealdb::RecordId>` in the full (table:key) form.
+///
+/// This helper is intended for use with `#[serde(with = "...")]` on fields of type
+/// `Option<surrealdb::RecordId>`. When the option is `Some`, the contained `RecordId`
+/// is serialized as the full textual representation produced by `RecordId::to_string()`
+/// (for example: `"user:abc123"`). When the option is `None`, a JSON `null` is emitted.
+pub fn serialize_opt<S>(id: &Option<surrealdb::RecordId>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if !serializer.is_human_readable() {
+        return id.serialize(serializer);
+    }
+    match id {
+        Some(record_id) => serialize(record_id, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Deserialize a JSON string into a `surrealdb::RecordId`.
+///
+/// This is the counterpart to `serialize` and expects the JSON value to be a string
+/// containing the full record id (table:key). If the input string cannot be parsed by
+/// `surrealdb::RecordId::from_str`, this function converts the parsing error into a
+/// `serde` deserialization error. The string is parsed in place, so no owned copy is made
+/// when the deserializer can lend it.
+///
+/// Formats that are not human readable are read in `RecordId`'s own encoding, matching
+/// [`serialize`].
+///
+/// # Errors
+///
+/// Returns a deserialization error if the provided JSON value is not a string or
+/// if the string is not a valid SurrealDB record id. The message quotes the string, cut short
+/// if it is long, as in `invalid record id "user123" (expected "table:key")`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<surrealdb::RecordId, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if !deserializer.is_human_readable() {
+        return surrealdb::RecordId::deserialize(deserializer);
+    }
+    deserializer.deserialize_str(FullVisitor)
+}
+
+/// Deserialize an `Option<surrealdb::RecordId>` from the full (table:key) form.
+///
+/// This is the counterpart to `serialize_opt` and expects either a JSON string
+/// containing the full record id (for example `"user:abc123"`) or `null`.
+/// If a string is provided, it attempts to parse it with `surrealdb::RecordId::from_str`.
+/// If parsing fails, the error is converted into a serde deserialization error.
+///
+/// # Errors
+///
+/// Returns a deserialization error if the JSON value is not a string or `null`, or if
+/// the string is not a valid SurrealDB `RecordId`.
+pub fn deserialize_opt<'de, D>(deserializer: D) -> Result<Option<surrealdb::RecordId>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if !deserializer.is_human_readable() {
+        return Option::<surrealdb::RecordId>::deserialize(deserializer);
+    }
+    deserializer.deserialize_option(OptionFullVisitor)
+}
+
+struct FullVisitor;
+
+impl Visitor<'_> for FullVisitor {
+    type Value = surrealdb::RecordId;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a record id string in table:key form")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        parse(v)
+    }
+}
+
+/// Writes a record id the way its `Display` does, except for the string key `NaN`, which
+/// `Display` leaves bare although the parser reads a bare `NaN` as a number. That key is
+/// written `⟨NaN⟩` instead. Takes the key separately so that `Thing` can share it.
+pub(crate) struct FullText<'a, T>(pub(crate) &'a T, pub(crate) &'a Id);
+
+impl<T: fmt::Display> fmt::Display for FullText<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.1 {
+            Id::String(s) if s == "NaN" => {
+                let text = self.0.to_string();
+                write!(f, "{}⟨NaN⟩", text.strip_suffix("NaN").unwrap_or(&text))
+            }
+            _ => self.0.fmt(f),
+        }
+    }
+}
+
+/// Parses a full (table:key) record id, naming the input in the error.
+pub(crate) fn parse<E: de::Error>(s: &str) -> Result<surrealdb::RecordId, E> {
+    surrealdb::RecordId::from_str(s).map_err(|_| E::custom(Invalid(s)))
+}
+
+/// Inputs longer than this many characters are cut short in error messages.
+const MAX_SHOWN: usize = 64;
+
+/// Describes a string that is not a full record id, as in
+/// `invalid record id "user123" (expected "table:key")`.
+pub(crate) struct Invalid<'a>(pub(crate) &'a str);
+
+impl fmt::Display for Invalid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.char_indices().nth(MAX_SHOWN) {
+            Some((end, _)) => write!(f, "invalid record id {:?}…", &self.0[..end])?,
+            None => write!(f, "invalid record id {:?}", self.0)?,
+        }
+        f.write_str(" (expected \"table:key\")")
+    }
+}
+
+/// Prepares an input string for parsing; see [`trimmed`].
+type Clean = fn(&str) -> &str;
+
+fn as_is(s: &str) -> &str {
+    s
+}
+
+struct OptionFullVisitor;
+
+impl<'de> Visitor<'de> for OptionFullVisitor {
+    type Value = Option<surrealdb::RecordId>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a record id string in table:key form or null")
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer).map(Some)
+    }
+}
+
+/// A string that borrows from the input when the deserializer allows it.
+struct Text<'de>(Cow<'de, str>);
+
+impl<'de> Deserialize<'de> for Text<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(TextVisitor)
+    }
+}
+
+struct TextVisitor;
+
+impl<'de> Visitor<'de> for TextVisitor {
+    type Value = Text<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Text(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Text(Cow::Owned(v.to_owned())))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Text(Cow::Owned(v)))
+    }
+}
+
+/// Deserialize a full (table:key) string that must name the table of `T`.
+///
+/// Reads like [`deserialize`], then checks the table, so an id of the wrong entity pasted
+/// into a field is caught while reading the request rather than deep inside a query.
+/// Serialization is unchanged; pair it with [`serialize`]:
+///
+/// ```
+/// use atopio_extra::Table;
+///
+/// struct User;
+///
+/// impl Table for User {
+///     const NAME: &'static str = "user";
+/// }
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Session {
+///     #[serde(
+///         serialize_with = "atopio_extra::record_id_full::serialize",
+///         deserialize_with = "atopio_extra::record_id_full::expect::<User, _>"
+///     )]
+///     user: surrealdb::RecordId,
+/// }
+///
+/// let error = serde_json::from_str::<Session>(r#"{"user":"post:abc"}"#).err().unwrap();
+/// assert!(error.to_string().starts_with(r#"expected table "user", got "post""#));
+/// ```
+///
+/// Use [`crate::TypedId`] instead when the table should also be part of the field's type.
+///
+/// # Errors
+///
+/// Returns a deserialization error if the value is not a valid full record id, as
+/// [`deserialize`] does, or if its table is not `T::NAME`.
+pub fn expect<'de, T, D>(deserializer: D) -> Result<surrealdb::RecordId, D::Error>
+where
+    T: crate::Table,
+    D: Deserializer<'de>,
+{
+    let id = deserialize(deserializer)?;
+    if id.table() != T::NAME {
+        return Err(D::Error::custom(crate::typed_id::TableMismatch {
+            expected: T::NAME,
+            found: id.table().to_owned(),
+        }));
+    }
+    Ok(id)
+}
+
+/// Deserialize a full (table:key) string, falling back to the table named by `T` for bare keys.
+///
+/// The string is first parsed with `surrealdb::RecordId::from_str`, so `"user:abc123"` and
+/// `"post:abc123"` both keep the table they name. Only a string with no table separator at all,
+/// such as `"abc123"` or `"42"`, is read as a naked key (see
+/// [`crate::record_id_naked::deserialize_for`]) in table `T`. This lets a field accept both
+/// forms while clients migrate from one to the other:
+///
+/// ```
+/// use atopio_extra::Table;
+///
+/// struct User;
+///
+/// impl Table for User {
+///     const NAME: &'static str = "user";
+/// }
+///
+/// #[derive(serde::Deserialize)]
+/// struct Session {
+///     #[serde(deserialize_with = "atopio_extra::record_id_full::lenient_for::<User, _>")]
+///     user: surrealdb::RecordId,
+/// }
+///
+/// let a: Session = serde_json::from_str(r#"{"user":"abc123"}"#)?;
+/// let b: Session = serde_json::from_str(r#"{"user":"user:abc123"}"#)?;
+/// assert_eq!(a.user, b.user);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns a deserialization error if the value is not a string, if the string is empty, or if
+/// it contains a table separator but is not a valid SurrealDB record id.
+pub fn lenient_for<'de, T, D>(deserializer: D) -> Result<surrealdb::RecordId, D::Error>
+where
+    T: crate::Table,
+    D: Deserializer<'de>,
+{
+    if !deserializer.is_human_readable() {
+        return surrealdb::RecordId::deserialize(deserializer);
+    }
+    let Text(s) = Text::deserialize(deserializer)?;
+    parse_lenient::<T>(&s).ok_or_else(|| D::Error::custom(Invalid(&s)))
+}
+
+/// Parses a full string, or a bare key in table `T`, as [`lenient_for`] describes.
+fn parse_lenient<T: crate::Table>(s: &str) -> Option<surrealdb::RecordId> {
+    match surrealdb::RecordId::from_str(s) {
+        Ok(id) => Some(id),
+        Err(_) if !s.is_empty() && !s.contains(':') => Some(surrealdb::RecordId::from_table_key(
+            T::NAME,
+            crate::record_id_naked::parse_key(s),
+        )),
+        Err(_) => None,
+    }
+}
+
+/// Deserialize an array mixing full (table:key) strings and bare keys, such as
+/// `["user:abc", "def"]`.
+///
+/// Each element is read as [`lenient_for`] reads a single id: full strings keep their table
+/// and bare keys are put in table `T`. Pair it with [`vec::serialize`], which always writes
+/// the full form, so stored documents converge on it as they are rewritten:
+///
+/// ```
+/// use atopio_extra::Table;
+///
+/// struct User;
+///
+/// impl Table for User {
+///     const NAME: &'static str = "user";
+/// }
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Team {
+///     #[serde(
+///         serialize_with = "atopio_extra::record_id_full::vec::serialize",
+///         deserialize_with = "atopio_extra::record_id_full::vec_lenient_for::<User, _>"
+///     )]
+///     members: Vec<surrealdb::RecordId>,
+/// }
+///
+/// let team: Team = serde_json::from_str(r#"{"members":["user:abc","def"]}"#)?;
+/// assert_eq!(serde_json::to_string(&team)?, r#"{"members":["user:abc","user:def"]}"#);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns a deserialization error if the value is not an array of strings, or if any element
+/// is neither a valid record id nor a bare key. The error lists every such element with its
+/// index, not just the first.
+pub fn vec_lenient_for<'de, T, D>(deserializer: D) -> Result<Vec<surrealdb::RecordId>, D::Error>
+where
+    T: crate::Table,
+    D: Deserializer<'de>,
+{
+    if !deserializer.is_human_readable() {
+        return Vec::<surrealdb::RecordId>::deserialize(deserializer);
+    }
+    deserializer.deserialize_seq(LenientSeqVisitor::<T>(std::marker::PhantomData))
+}
+
+struct LenientSeqVisitor<T>(std::marker::PhantomData<fn() -> T>);
+
+impl<'de, T: crate::Table> Visitor<'de> for LenientSeqVisitor<T> {
+    type Value = Vec<surrealdb::RecordId>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array of record ids in table:key form or bare keys")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut ids = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        let mut errors = Vec::new();
+        let mut index = 0;
+        while let Some(Text(s)) = seq.next_element::<Text>()? {
+            match parse_lenient::<T>(&s) {
+                Some(id) => ids.push(id),
+                None => errors.push(format!("{} at index {index}", Invalid(&s))),
+            }
+            index += 1;
+        }
+        if errors.is_empty() {
+            Ok(ids)
+        } else {
+            Err(A::Error::custom(errors.join("; ")))
+        }
+    }
+}
+
+/// JSON schema for a field serialized with this module: a string in table:key form.
+///
+/// Use it with `#[schemars(schema_with = "atopio_extra::record_id_full::json_schema")]`. The
+/// pattern only checks for a plain table name followed by `:` and a key; escaped table names
+/// such as `` `my table`:1 `` are valid record ids but do not match it.
+#[cfg(feature = "schemars")]
+pub fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::full(None)
+}
+
+/// `Option<surrealdb::RecordId>` support for `#[serde(with = "...")]`.
+///
+/// Serde only looks for functions named `serialize` and `deserialize` in a `with` module, so
+/// this submodule exposes `serialize_opt`/`deserialize_opt` under those names:
+///
+/// ```
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Post {
+///     #[serde(
+///         default,
+///         skip_serializing_if = "Option::is_none",
+///         with = "atopio_extra::record_id_full::option"
+///     )]
+///     author: Option<surrealdb::RecordId>,
+/// }
+/// ```
+///
+/// Add `#[serde(default)]` if the field may be missing from the input entirely; a `with`
+/// module on its own makes the field required, even when it is an `Option`.
+pub mod option {
+    use serde::{Deserializer, Serializer};
+    use surrealdb::RecordId;
+
+    /// Serialize an `Option<surrealdb::RecordId>` in the full (table:key) form, or `null`.
+    ///
+    /// See [`super::serialize_opt`].
+    pub fn serialize<S>(id: &Option<RecordId>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_opt(id, serializer)
+    }
+
+    /// Deserialize an `Option<surrealdb::RecordId>` from the full (table:key) form or `null`.
+    ///
+    /// See [`super::deserialize_opt`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a string or `null`, or if the
+    /// string is not a valid SurrealDB record id.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<RecordId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_opt(deserializer)
+    }
+    /// JSON schema for a field serialized with this module: a full (table:key) string or
+    /// `null`.
+    #[cfg(feature = "schemars")]
+    pub fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::schema::nullable(super::json_schema(generator))
+    }
+}
+
+/// `Option<surrealdb::RecordId>` support that reads an empty string as `None`.
+///
+/// Some clients send `""` rather than `null` for a cleared relation. This module reads `""`,
+/// or a string of only whitespace, as `None`, as well as `null`; any other string must be a
+/// valid full (table:key) record id. Output is the same as [`option`], so `None` is always
+/// written as `null`, never as `""`:
+///
+/// ```
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Post {
+///     #[serde(default, with = "atopio_extra::record_id_full::option_empty_as_none")]
+///     author: Option<surrealdb::RecordId>,
+/// }
+///
+/// let post: Post = serde_json::from_str(r#"{"author":""}"#)?;
+/// assert_eq!(post.author, None);
+/// assert_eq!(serde_json::to_string(&post)?, r#"{"author":null}"#);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// Use [`option`] where empty strings should keep being rejected.
+pub mod option_empty_as_none {
+    use super::Text;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use surrealdb::RecordId;
+
+    /// Serialize an `Option<surrealdb::RecordId>` in the full (table:key) form, or `null`.
+    ///
+    /// See [`super::serialize_opt`].
+    pub fn serialize<S>(id: &Option<RecordId>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_opt(id, serializer)
+    }
+
+    /// Deserialize `null`, an empty or blank string, or a full (table:key) string.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a string or `null`, or if a
+    /// non-blank string is not a valid SurrealDB record id.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<RecordId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return Option::<RecordId>::deserialize(deserializer);
+        }
+        match Option::<Text>::deserialize(deserializer)? {
+            Some(Text(s)) if !s.trim().is_empty() => super::parse(&s).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// `Option<surrealdb::RecordId>` support that reads the strings `"NONE"` and `"NULL"` as `None`.
+///
+/// Data exported from SurrealQL scripts sometimes holds an absent id as the literal text
+/// `NONE` or `NULL`. This module reads those strings, in any case, as `None`, as well as
+/// `null`; any other string must be a valid full (table:key) record id. Output is the same as
+/// [`option`], so `None` is written as `null`:
+///
+/// ```
+/// #[derive(serde::Deserialize)]
+/// struct Post {
+///     #[serde(default, with = "atopio_extra::record_id_full::option_surreal_none")]
+///     author: Option<surrealdb::RecordId>,
+/// }
+///
+/// let post: Post = serde_json::from_str(r#"{"author":"NONE"}"#)?;
+/// assert_eq!(post.author, None);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// A full record id always has a table and a `:`, so the bare text `NONE` can never be a real
+/// id and nothing valid is lost: `none:abc`, a record in a table named `none`, still parses
+/// normally. The risk is in the other direction. If the same data also holds bare keys (see
+/// [`lenient_for`]), a record whose key is the string `NONE` in that table becomes `None` here,
+/// so only use this module for fields that are always written in the full form.
+///
+/// With the `tracing` feature, every fallback logs a `WARN` event with the string that was
+/// read, to help find the source of the data.
+pub mod option_surreal_none {
+    use super::Text;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use surrealdb::RecordId;
+
+    /// Serialize an `Option<surrealdb::RecordId>` in the full (table:key) form, or `null`.
+    ///
+    /// See [`super::serialize_opt`].
+    pub fn serialize<S>(id: &Option<RecordId>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_opt(id, serializer)
+    }
+
+    /// Deserialize `null`, `"NONE"`, `"NULL"` or a full (table:key) string.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a string or `null`, or if any other
+    /// string is not a valid SurrealDB record id.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<RecordId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return Option::<RecordId>::deserialize(deserializer);
+        }
+        let Some(Text(s)) = Option::<Text>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+        let literal = s.trim();
+        if literal.eq_ignore_ascii_case("NONE") || literal.eq_ignore_ascii_case("NULL") {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(value = %s, "read a SurrealQL {literal} literal string as an absent record id");
+            return Ok(None);
+        }
+        super::parse(&s).map(Some)
+    }
+}
+
+/// `Option<Option<surrealdb::RecordId>>` support for PATCH-style updates.
+///
+/// The outer option says whether the field was sent at all and the inner one whether it was
+/// set, so a request body can leave a relation alone, clear it or change it:
+///
+/// | JSON                      | Value                |
+/// |---------------------------|----------------------|
+/// | field missing             | `None`               |
+/// | `"author": null`          | `Some(None)`         |
+/// | `"author": "user:abc"`    | `Some(Some(id))`     |
+///
+/// Serde cannot tell a missing field from `null` on its own, so the field needs
+/// `#[serde(default)]` to read a missing key as `None`, and
+/// `skip_serializing_if = "atopio_extra::record_id_full::double_option::is_unset"` to leave
+/// it out again on output:
+///
+/// ```
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct PostPatch {
+///     #[serde(
+///         default,
+///         skip_serializing_if = "atopio_extra::record_id_full::double_option::is_unset",
+///         with = "atopio_extra::record_id_full::double_option"
+///     )]
+///     author: Option<Option<surrealdb::RecordId>>,
+/// }
+///
+/// let patch: PostPatch = serde_json::from_str(r#"{"author":null}"#)?;
+/// assert_eq!(patch.author, Some(None));
+/// assert_eq!(serde_json::to_string(&patch)?, r#"{"author":null}"#);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+pub mod double_option {
+    use serde::{Deserializer, Serializer};
+    use surrealdb::RecordId;
+
+    /// Returns `true` when the field was not sent, for use with `skip_serializing_if`.
+    pub fn is_unset(id: &Option<Option<RecordId>>) -> bool {
+        id.is_none()
+    }
+
+    /// Serialize the inner `Option<surrealdb::RecordId>` as a full (table:key) string or `null`.
+    ///
+    /// An unset (outer `None`) field is also written as `null`; pair this with
+    /// `skip_serializing_if` and [`is_unset`] to leave it out instead.
+    pub fn serialize<S>(id: &Option<Option<RecordId>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match id {
+            Some(id) => super::serialize_opt(id, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize a present field: `null` becomes `Some(None)` and a full (table:key) string
+    /// becomes `Some(Some(id))`.
+    ///
+    /// Serde only calls this for fields that are present, so a missing field needs
+    /// `#[serde(default)]` to become `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a string or `null`, or if the
+    /// string is not a valid SurrealDB record id.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Option<RecordId>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_opt(deserializer).map(Some)
+    }
+}
+
+/// `Vec<surrealdb::RecordId>` support for `#[serde(with = "...")]`.
+///
+/// Each element is written in the full (table:key) form, so `vec![user:a, user:b]` becomes
+/// `["user:a","user:b"]`, and read back with `surrealdb::RecordId::from_str`.
+pub mod vec {
+    use super::Text;
+    use serde::de::{Error, SeqAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::fmt;
+    use std::str::FromStr;
+    use surrealdb::RecordId;
+
+    /// Serialize a slice of `surrealdb::RecordId`s as an array of full (table:key) strings.
+    pub fn serialize<S>(ids: &[RecordId], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(ids.iter().map(Full))
+    }
+
+    /// Deserialize an array of full (table:key) strings into a `Vec<surrealdb::RecordId>`.
+    ///
+    /// An empty array yields an empty vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not an array of strings, or if any
+    /// element is not a valid SurrealDB record id. The error names the index of the first
+    /// element that failed to parse.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<RecordId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_cleaned(deserializer, super::as_is)
+    }
+
+    /// Like [`deserialize`], passing each string through `clean` before parsing it.
+    pub(super) fn deserialize_cleaned<'de, D>(
+        deserializer: D,
+        clean: super::Clean,
+    ) -> Result<Vec<RecordId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return Vec::<RecordId>::deserialize(deserializer);
+        }
+        deserializer.deserialize_seq(FullSeqVisitor(clean))
+    }
+
+    pub(super) struct Full<'a>(pub(super) &'a RecordId);
+
+    impl Serialize for Full<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            super::serialize(self.0, serializer)
+        }
+    }
+
+    struct FullSeqVisitor(super::Clean);
+
+    impl<'de> Visitor<'de> for FullSeqVisitor {
+        type Value = Vec<RecordId>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an array of record ids in table:key form")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut ids = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+            while let Some(Text(s)) = seq.next_element::<Text>()? {
+                let s = (self.0)(&s);
+                let id = RecordId::from_str(s).map_err(|_| {
+                    A::Error::custom(format_args!("{} at index {}", super::Invalid(s), ids.len()))
+                })?;
+                ids.push(id);
+            }
+            Ok(ids)
+        }
+    }
+}
+
+/// `Option<Vec<surrealdb::RecordId>>` support for `#[serde(with = "...")]`.
+///
+/// `None` is written as `null`, and `Some` as an array of full (table:key) strings. On input,
+/// `null` becomes `None` and `[]` becomes `Some(vec![])`. Add `#[serde(default)]` if the
+/// field may be missing entirely.
+pub mod option_vec {
+    use super::vec::Full;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use surrealdb::RecordId;
+
+    /// Serialize an `Option<Vec<surrealdb::RecordId>>` as `null` or an array of full strings.
+    pub fn serialize<S>(ids: &Option<Vec<RecordId>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return ids.serialize(serializer);
+        }
+        match ids {
+            Some(ids) => serializer.collect_seq(ids.iter().map(Full)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize `null` or an array of full (table:key) strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is neither `null` nor an array, or if an
+    /// element is not a valid SurrealDB record id. The element error names its index.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<RecordId>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<FullVec>::deserialize(deserializer)?.map(|ids| ids.0))
+    }
+
+    struct FullVec(Vec<RecordId>);
+
+    impl<'de> Deserialize<'de> for FullVec {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            super::vec::deserialize(deserializer).map(FullVec)
+        }
+    }
+}
+
+/// `HashSet<FullId>` support for `#[serde(with = "...")]` with deterministic output.
+///
+/// A `HashSet` iterates in an arbitrary order, which makes JSON snapshots unstable. This
+/// module writes the elements as a sorted array of full (table:key) strings. Input may be in
+/// any order, and duplicates collapse into a single element.
+pub mod set {
+    use crate::types::FullId;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::collections::HashSet;
+    use std::hash::BuildHasher;
+
+    /// Serialize a `HashSet<FullId>` as an array of full strings, sorted by record id.
+    pub fn serialize<S, H>(ids: &HashSet<FullId, H>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        H: BuildHasher,
+    {
+        let mut sorted: Vec<&FullId> = ids.iter().collect();
+        sorted.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        serializer.collect_seq(sorted)
+    }
+
+    /// Deserialize an array of full (table:key) strings into a `HashSet<FullId>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not an array of strings, or if any
+    /// element is not a valid SurrealDB record id.
+    pub fn deserialize<'de, D, H>(deserializer: D) -> Result<HashSet<FullId, H>, D::Error>
+    where
+        D: Deserializer<'de>,
+        H: BuildHasher + Default,
+    {
+        HashSet::deserialize(deserializer)
+    }
+}
+
+/// `HashMap<surrealdb::RecordId, V>` support for `#[serde(with = "...")]`.
+///
+/// JSON object keys must be strings, so each key is written in the full (table:key) form and
+/// parsed back with `surrealdb::RecordId::from_str`. Values use their own serde
+/// implementations.
+pub mod map_key {
+    use super::Text;
+    use super::vec::Full;
+    use serde::de::{Error, MapAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::hash::BuildHasher;
+    use std::marker::PhantomData;
+    use surrealdb::RecordId;
+
+    /// Serialize a map keyed by `surrealdb::RecordId` using full (table:key) string keys.
+    pub fn serialize<S, V, H>(
+        map: &HashMap<RecordId, V, H>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        V: Serialize,
+        H: BuildHasher,
+    {
+        serializer.collect_map(map.iter().map(|(id, value)| (Full(id), value)))
+    }
+
+    /// Deserialize a map with full (table:key) string keys into a map keyed by `RecordId`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a map, if a key is not a valid
+    /// SurrealDB record id, or if two keys parse to the same record id (for example
+    /// `"user:abc"` and `"user:⟨abc⟩"`), rather than silently keeping only one of the values.
+    pub fn deserialize<'de, D, V, H>(deserializer: D) -> Result<HashMap<RecordId, V, H>, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Deserialize<'de>,
+        H: BuildHasher + Default,
+    {
+        deserialize_cleaned(deserializer, super::as_is)
+    }
+
+    /// Like [`deserialize`], passing each key through `clean` before parsing it.
+    pub(super) fn deserialize_cleaned<'de, D, V, H>(
+        deserializer: D,
+        clean: super::Clean,
+    ) -> Result<HashMap<RecordId, V, H>, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Deserialize<'de>,
+        H: BuildHasher + Default,
+    {
+        if !deserializer.is_human_readable() {
+            return HashMap::<RecordId, V, H>::deserialize(deserializer);
+        }
+        deserializer.deserialize_map(FullKeyMapVisitor(clean, PhantomData))
+    }
+
+    struct FullKeyMapVisitor<V, H>(super::Clean, PhantomData<fn() -> (V, H)>);
+
+    impl<'de, V, H> Visitor<'de> for FullKeyMapVisitor<V, H>
+    where
+        V: Deserialize<'de>,
+        H: BuildHasher + Default,
+    {
+        type Value = HashMap<RecordId, V, H>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a map keyed by record ids in table:key form")
+        }
+
+        fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut map = HashMap::with_capacity_and_hasher(
+                access.size_hint().unwrap_or(0).min(4096),
+                H::default(),
+            );
+            while let Some((Text(key), value)) = access.next_entry::<Text, V>()? {
+                let id = super::parse((self.0)(&key))?;
+                if map.contains_key(&id) {
+                    return Err(A::Error::custom(format_args!(
+                        "duplicate record id key {key:?} (parsed as {id})"
+                    )));
+                }
+                map.insert(id, value);
+            }
+            Ok(map)
+        }
+    }
+}
+
+/// Full (table:key) serde helpers that also accept SurrealDB's raw `Thing` object form.
+///
+/// Ids returned through the HTTP and raw JSON interfaces are sometimes encoded as objects,
+/// `{"tb": "user", "id": "abc123"}` or `{"tb": "user", "id": {"String": "abc123"}}`, instead of
+/// a `table:key` string. This module reads any of these shapes, with `String`, `Number`,
+/// `Uuid`, `Array` and `Object` keys, and still writes the plain string:
+///
+/// ```
+/// #[derive(serde::Deserialize)]
+/// struct User {
+///     #[serde(with = "atopio_extra::record_id_full::lenient")]
+///     id: surrealdb::RecordId,
+/// }
+///
+/// let user: User = serde_json::from_str(r#"{"id":{"tb":"user","id":{"String":"abc123"}}}"#)?;
+/// assert_eq!(user.id.to_string(), "user:abc123");
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// Reading the object form requires a self-describing format such as JSON.
+pub mod lenient {
+    use serde::{Deserializer, Serializer};
+    use surrealdb::RecordId;
+
+    /// Serialize a `surrealdb::RecordId` as its full string representation.
+    ///
+    /// See [`super::serialize`].
+    pub fn serialize<S>(id: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize(id, serializer)
+    }
+
+    /// Deserialize a `surrealdb::RecordId` from a full (table:key) string or a `Thing` object.
+    ///
+    /// See [`super::any::deserialize`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is neither a string nor an object, if the
+    /// string is not a valid SurrealDB record id, or if the object is not a valid `Thing`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::any::deserialize(deserializer)
+    }
+}
+
+/// Full (table:key) serde helpers accepting every representation a record id arrives in.
+///
+/// Depending on whether data flows through the SDK, the HTTP API or an export file, the same
+/// field may hold a `table:key` string, SurrealDB's `Thing` object (see [`lenient`]), or the
+/// string as raw UTF-8 bytes in binary formats. [`deserialize`] accepts all of them and
+/// [`serialize`] always writes the plain string. When nothing matches, the error lists the
+/// representations that were tried.
+pub mod any {
+    use crate::key_value::thing_from_json;
+    use serde::de::{self, MapAccess, Unexpected, Visitor};
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde_json::Value as Json;
+    use std::fmt;
+    use surrealdb::RecordId;
+
+    /// Serialize a `surrealdb::RecordId` as its full string representation.
+    ///
+    /// See [`super::serialize`].
+    pub fn serialize<S>(id: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize(id, serializer)
+    }
+
+    /// Deserialize a `surrealdb::RecordId` from a full (table:key) string, a `Thing` object or
+    /// the UTF-8 bytes of a full string.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error naming the representations that were tried if the value
+    /// is none of them, or describing the problem if it has one of their shapes but is invalid.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return RecordId::deserialize(deserializer);
+        }
+        deserializer.deserialize_any(AnyVisitor)
+    }
+
+    struct AnyVisitor;
+
+    impl<'de> Visitor<'de> for AnyVisitor {
+        type Value = RecordId;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str(
+                "a record id as a full (table:key) string, a {\"tb\", \"id\"} object, \
+                 or the UTF-8 bytes of a full string",
+            )
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            super::parse(v)
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match std::str::from_utf8(v) {
+                Ok(s) => self.visit_str(s),
+                Err(_) => Err(E::invalid_value(Unexpected::Bytes(v), &self)),
+            }
+        }
+
+        fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut map = serde_json::Map::new();
+            while let Some((key, value)) = access.next_entry::<String, Json>()? {
+                map.insert(key, value);
+            }
+            thing_from_json(Json::Object(map))
+                .map_err(|e| de::Error::custom(format_args!("invalid record id object: {e}")))
+        }
+
+        fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(self)
+        }
+    }
+}
+
+/// Full (table:key) serde helpers that ignore ASCII whitespace around the id.
+///
+/// The API matches the top level of this module, with `serialize`/`deserialize`,
+/// `serialize_opt`/`deserialize_opt` and the [`option`](trimmed::option),
+/// [`vec`](trimmed::vec), [`option_vec`](trimmed::option_vec) and
+/// [`map_key`](trimmed::map_key) submodules. Output is identical to the base helpers; on input,
+/// leading and trailing ASCII whitespace is removed before the string is parsed, so
+/// `"user:abc "` reads as `user:abc`:
+///
+/// ```
+/// #[derive(serde::Deserialize)]
+/// struct Row {
+///     #[serde(with = "atopio_extra::record_id_full::trimmed")]
+///     id: surrealdb::RecordId,
+/// }
+///
+/// let row: Row = serde_json::from_str(r#"{"id":" user:⟨ padded ⟩\t"}"#)?;
+/// assert_eq!(row.id, surrealdb::RecordId::from(("user", " padded ")));
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// Only the ends of the string are trimmed, which always lie outside any escape, so whitespace
+/// inside an escaped key such as `⟨ padded ⟩` is kept.
+///
+/// The SurrealQL parser behind the base helpers currently skips surrounding whitespace too, but
+/// that is a detail of the parser rather than something this crate promises. Use this module
+/// when the input is known to be padded, such as CSV-derived data, so it keeps being accepted.
+pub mod trimmed {
+    use super::Text;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use surrealdb::RecordId;
+
+    fn trim(s: &str) -> &str {
+        s.trim_ascii()
+    }
+
+    /// Serialize a `surrealdb::RecordId` as its full string representation.
+    ///
+    /// See [`super::serialize`].
+    pub fn serialize<S>(id: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize(id, serializer)
+    }
+
+    /// Serialize an `Option<surrealdb::RecordId>` in the full (table:key) form, or `null`.
+    ///
+    /// See [`super::serialize_opt`].
+    pub fn serialize_opt<S>(id: &Option<RecordId>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_opt(id, serializer)
+    }
+
+    /// Deserialize a full (table:key) string, ignoring surrounding ASCII whitespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a string, or if the trimmed string
+    /// is not a valid SurrealDB record id.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return RecordId::deserialize(deserializer);
+        }
+        let Text(s) = Text::deserialize(deserializer)?;
+        super::parse(trim(&s))
+    }
+
+    /// Deserialize `null` or a full (table:key) string, ignoring surrounding ASCII whitespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a string or `null`, or if the
+    /// trimmed string is not a valid SurrealDB record id.
+    pub fn deserialize_opt<'de, D>(deserializer: D) -> Result<Option<RecordId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<Trimmed>::deserialize(deserializer)?.map(|id| id.0))
+    }
+
+    struct Trimmed(RecordId);
+
+    impl<'de> Deserialize<'de> for Trimmed {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize(deserializer).map(Trimmed)
+        }
+    }
+
+    /// `Option<surrealdb::RecordId>` support for `#[serde(with = "...")]`, trimming on input.
+    pub mod option {
+        use serde::{Deserializer, Serializer};
+        use surrealdb::RecordId;
+
+        /// See [`super::serialize_opt`].
+        pub fn serialize<S>(id: &Option<RecordId>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            super::serialize_opt(id, serializer)
+        }
+
+        /// See [`super::deserialize_opt`].
+        ///
+        /// # Errors
+        ///
+        /// Returns a deserialization error if the value is not a string or `null`, or if the
+        /// trimmed string is not a valid SurrealDB record id.
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<RecordId>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            super::deserialize_opt(deserializer)
+        }
+    }
+
+    /// `Vec<surrealdb::RecordId>` support for `#[serde(with = "...")]`, trimming each element.
+    pub mod vec {
+        use serde::{Deserializer, Serializer};
+        use surrealdb::RecordId;
+
+        /// See [`crate::record_id_full::vec::serialize`].
+        pub fn serialize<S>(ids: &[RecordId], serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            crate::record_id_full::vec::serialize(ids, serializer)
+        }
+
+        /// Deserialize an array of full (table:key) strings, ignoring whitespace around each.
+        ///
+        /// # Errors
+        ///
+        /// Returns a deserialization error if the value is not an array of strings, or if any
+        /// trimmed element is not a valid SurrealDB record id. The error names its index.
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<RecordId>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            crate::record_id_full::vec::deserialize_cleaned(deserializer, super::trim)
+        }
+    }
+
+    /// `Option<Vec<surrealdb::RecordId>>` support for `#[serde(with = "...")]`, trimming each
+    /// element.
+    pub mod option_vec {
+        use serde::{Deserialize, Deserializer, Serializer};
+        use surrealdb::RecordId;
+
+        /// See [`crate::record_id_full::option_vec::serialize`].
+        pub fn serialize<S>(ids: &Option<Vec<RecordId>>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            crate::record_id_full::option_vec::serialize(ids, serializer)
+        }
+
+        /// Deserialize `null` or an array of full strings, ignoring whitespace around each.
+        ///
+        /// # Errors
+        ///
+        /// Returns a deserialization error if the value is neither `null` nor an array, or if a
+        /// trimmed element is not a valid SurrealDB record id. The error names its index.
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<RecordId>>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Ok(Option::<TrimmedVec>::deserialize(deserializer)?.map(|ids| ids.0))
+        }
+
+        struct TrimmedVec(Vec<RecordId>);
+
+        impl<'de> Deserialize<'de> for TrimmedVec {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                super::vec::deserialize(deserializer).map(TrimmedVec)
+            }
+        }
+    }
+
+    /// `HashMap<surrealdb::RecordId, V>` support for `#[serde(with = "...")]`, trimming each
+    /// key.
+    pub mod map_key {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+        use std::collections::HashMap;
+        use std::hash::BuildHasher;
+        use surrealdb::RecordId;
+
+        /// See [`crate::record_id_full::map_key::serialize`].
+        pub fn serialize<S, V, H>(
+            map: &HashMap<RecordId, V, H>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+            V: Serialize,
+            H: BuildHasher,
+        {
+            crate::record_id_full::map_key::serialize(map, serializer)
+        }
+
+        /// Deserialize a map with full (table:key) string keys, ignoring whitespace around each
+        /// key.
+        ///
+        /// # Errors
+        ///
+        /// Returns a deserialization error if the value is not a map, if a trimmed key is not a
+        /// valid SurrealDB record id, or if two keys parse to the same record id.
+        pub fn deserialize<'de, D, V, H>(
+            deserializer: D,
+        ) -> Result<HashMap<RecordId, V, H>, D::Error>
+        where
+            D: Deserializer<'de>,
+            V: Deserialize<'de>,
+            H: BuildHasher + Default,
+        {
+            crate::record_id_full::map_key::deserialize_cleaned(deserializer, super::trim)
+        }
+    }
+}
+
+/// Full (table:key) serde helpers that rewrite renamed tables while reading.
+///
+/// [`deserialize`] reads like [`super::deserialize`], then moves the id to the new name of its
+/// table using the [`TableAliasMap`](crate::TableAliasMap) installed with
+/// [`TableAliasMap::scope`](crate::TableAliasMap::scope). Outside a scope, ids are read
+/// unchanged. [`serialize`] writes the id as it is, so data read this way is written back
+/// under the new names. See [`TableAliasMap::seed`](crate::TableAliasMap::seed) for passing
+/// the map explicitly instead.
+pub mod with_aliases {
+    use crate::TableAliasMap;
+    use serde::{Deserializer, Serializer};
+    use surrealdb::RecordId;
+
+    /// Serialize a `surrealdb::RecordId` as its full string representation.
+    ///
+    /// See [`super::serialize`].
+    pub fn serialize<S>(id: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize(id, serializer)
+    }
+
+    /// Deserialize a `surrealdb::RecordId` from the full (table:key) form, rewriting the table
+    /// if the map in scope has an alias for it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a valid record id, as described in
+    /// [`super::deserialize`].
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize(deserializer).map(TableAliasMap::rewrite_in_scope)
+    }
+}
@@ -0,0 +1,111 @@
+use crate::key_value::{KeyJson, key_from_json};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use surrealdb::RecordIdKey;
+
+struct Owned(RecordIdKey);
+
+impl<'de> Deserialize<'de> for Owned {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer).map(Owned)
+    }
+}
+
+/// Serialize a `surrealdb::RecordIdKey` as its natural JSON value.
+///
+/// This helper is intended for use with `#[serde(with = "...")]` on fields of type
+/// `surrealdb::RecordIdKey`, where the table is implied by the containing struct and no
+/// `RecordId` is ever built. String keys are written as strings and integer keys as numbers,
+/// so `abc123` becomes `"abc123"` and `42` becomes `42`. Uuid keys are written as canonical
+/// uuid strings, and array and object keys as JSON arrays and objects.
+pub fn serialize<S>(key: &RecordIdKey, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    KeyJson(key).serialize(serializer)
+}
+
+/// Deserialize a `surrealdb::RecordIdKey` from its natural JSON value.
+///
+/// This is the counterpart to `serialize`. Numbers become integer keys, strings become string
+/// keys (or uuid keys when the string is a canonical lowercase uuid), and arrays and objects
+/// become array and object keys.
+///
+/// # Errors
+///
+/// Returns a deserialization error if the value is `null` or a boolean, or if a number is not
+/// a 64-bit integer.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<RecordIdKey, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    key_from_json(serde_json::Value::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+/// `Option<surrealdb::RecordIdKey>` support for `#[serde(with = "...")]`.
+///
+/// `None` is written as `null`. Add `#[serde(default)]` if the field may be missing entirely.
+pub mod option {
+    use super::Owned;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use surrealdb::RecordIdKey;
+
+    /// Serialize an `Option<surrealdb::RecordIdKey>` as its JSON value or `null`.
+    pub fn serialize<S>(key: &Option<RecordIdKey>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match key {
+            Some(key) => super::serialize(key, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize a record id key or `null`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error under the same conditions as [`super::deserialize`],
+    /// except that `null` is read as `None`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<RecordIdKey>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<Owned>::deserialize(deserializer)?.map(|key| key.0))
+    }
+}
+
+/// `Vec<surrealdb::RecordIdKey>` support for `#[serde(with = "...")]`.
+pub mod vec {
+    use super::Owned;
+    use crate::key_value::KeyJson;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use surrealdb::RecordIdKey;
+
+    /// Serialize a slice of `surrealdb::RecordIdKey`s as an array of JSON values.
+    pub fn serialize<S>(keys: &[RecordIdKey], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(keys.iter().map(KeyJson))
+    }
+
+    /// Deserialize an array of record id keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not an array, or if any element fails
+    /// as described in [`super::deserialize`].
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<RecordIdKey>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<Owned>::deserialize(deserializer)?
+            .into_iter()
+            .map(|key| key.0)
+            .collect())
+    }
+}
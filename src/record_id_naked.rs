@@ -0,0 +1,1074 @@
+use crate::Table;
+use serde::de::{self, Unexpected, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use surrealdb::sql::Id;
+use surrealdb::{RecordId, RecordIdKey};
+
+/// Serialize the key portion of a `surrealdb::RecordId` (the "naked" id).
+///
+/// This helper is intended for use with `#[serde(with = "...")]` on fields of type
+/// `surrealdb::RecordId`. It serializes only the key portion (the part after the table
+/// separator) as a JSON string — akin to traditional SQL IDs where only the numeric or
+/// key portion is stored or referenced.
+///
+/// String keys are written as their raw text, without the `⟨...⟩` escaping SurrealQL needs, so
+/// `user:⟨has space⟩` becomes `"has space"`. The escaped form is only kept when the raw text
+/// would read back as a different key, such as the string key `⟨42⟩`, which would otherwise be
+/// indistinguishable from the numeric key `42`.
+///
+/// Formats that are not human readable (as reported by `Serializer::is_human_readable`) get
+/// `RecordId`'s own encoding of the whole id instead, like [`crate::record_id_full::serialize`].
+/// The `deserialize_for` functions read it back into their table. JSON output is unchanged.
+pub fn serialize<S>(id: &surrealdb::RecordId, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if !serializer.is_human_readable() {
+        return id.serialize(serializer);
+    }
+    serializer.collect_str(&KeyText(id.key()))
+}
+
+/// Serialize an `Option<surrealdb::RecordId>` as the naked key (key only).
+///
+/// Intended for use with `#[serde(with = "...")]` on fields of type
+/// `Option<surrealdb::RecordId>`. When the option is `Some`, only the key portion
+/// (the part after the table separator) is serialized as a JSON string (for example:
+/// `"abc123"`). When the option is `None`, a JSON `null` is emitted.
+///
+/// This shape is useful when you want IDs to resemble single-column identifiers,
+/// akin to traditional SQL IDs.
+pub fn serialize_opt<S>(id: &Option<surrealdb::RecordId>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if !serializer.is_human_readable() {
+        return id.serialize(serializer);
+    }
+    match id {
+        Some(record_id) => serializer.collect_str(&KeyText(record_id.key())),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Deserialize a `surrealdb::RecordId` for a field annotated with `record_id_naked`.
+///
+/// The naked form does not carry a table, so this function cannot rebuild a record id from
+/// a bare key. It exists so `#[serde(with = "...")]` compiles on structs deriving both
+/// `Serialize` and `Deserialize`, and only reads back what [`serialize`] writes for formats
+/// that are not human readable, `RecordId`'s own encoding of the whole id. To read bare keys
+/// such as `"abc123"`, use [`deserialize_for`] or the `types::Naked` wrapper, which take the
+/// table from a [`Table`] implementation.
+///
+/// # Errors
+///
+/// Returns a deserialization error for every value of a human readable format such as JSON,
+/// naming [`deserialize_for`] and `Naked<T>`. A full id such as `"user:abc123"` fails too, as
+/// it cannot be told apart from the naked string key `user:abc123`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<RecordId, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Tableless::deserialize(deserializer).map(|id| id.0)
+}
+
+/// Deserialize a naked key into a `surrealdb::RecordId` in the table named by `T`.
+///
+/// This is the table-aware counterpart to `serialize`. It accepts the key as either a JSON
+/// string or an integer and rebuilds the id as `RecordId::from((T::NAME, key))`. Integers
+/// become numeric keys, and strings are read the same way SurrealDB reads the part after the
+/// table separator, so everything `serialize` emits parses back to the same key (for example
+/// `"42"` is a numeric key while `"⟨42⟩"` is the string key `42`). Strings that are not valid
+/// key syntax, such as the raw text `"has space"` or `"a:b"`, are kept verbatim as string keys
+/// and are never interpreted as a full id.
+///
+/// Use it with `#[serde(deserialize_with = "atopio_extra::record_id_naked::deserialize_for::<User, _>")]`.
+///
+/// # Errors
+///
+/// Returns a deserialization error if the value is neither a string nor an integer, if the
+/// string is empty, or if an unsigned integer does not fit in an `i64`.
+pub fn deserialize_for<'de, T, D>(deserializer: D) -> Result<RecordId, D::Error>
+where
+    T: Table,
+    D: Deserializer<'de>,
+{
+    if !deserializer.is_human_readable() {
+        return native_for::<T, D>(deserializer);
+    }
+    let key = deserializer.deserialize_any(KeyVisitor)?;
+    Ok(RecordId::from_table_key(T::NAME, key))
+}
+
+/// JSON schema for a field serialized with this module: the key as a string.
+///
+/// Use it with `#[schemars(schema_with = "atopio_extra::record_id_naked::json_schema")]`.
+#[cfg(feature = "schemars")]
+pub fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::naked(None)
+}
+
+/// A record id read without a table: `RecordId`'s own encoding in formats that are not human
+/// readable, and an error pointing to the table-aware functions in the others.
+#[derive(PartialEq, Eq, Hash)]
+struct Tableless(RecordId);
+
+impl<'de> Deserialize<'de> for Tableless {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            return Err(de::Error::custom(
+                "a naked record id key has no table to read it back into; use \
+                 `record_id_naked::deserialize_for::<T, _>` or `Naked<T>` instead",
+            ));
+        }
+        RecordId::deserialize(deserializer).map(Tableless)
+    }
+}
+
+/// Reads a record id in `RecordId`'s own encoding and moves its key into the table named by
+/// `T`, for formats that are not human readable.
+fn native_for<'de, T, D>(deserializer: D) -> Result<RecordId, D::Error>
+where
+    T: Table,
+    D: Deserializer<'de>,
+{
+    RecordId::deserialize(deserializer).map(in_table::<T>)
+}
+
+fn in_table<T: Table>(id: RecordId) -> RecordId {
+    RecordId::from_table_key(T::NAME, RecordIdKey::from_inner(id.into_inner().id))
+}
+
+/// Displays the naked text of a key: the raw string for string keys that read back unchanged
+/// through [`parse_key`], and the SurrealQL form of `RecordIdKey`'s `Display` otherwise.
+pub(crate) struct KeyText<'a>(pub(crate) &'a RecordIdKey);
+
+impl fmt::Display for KeyText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.into_inner_ref() {
+            // Plain identifiers always read back as string keys, so skip the parser for them.
+            Id::String(raw) if is_ident(raw) || (!raw.is_empty() && parse_key(raw) == *self.0) => {
+                f.write_str(raw)
+            }
+            _ => fmt::Display::fmt(self.0, f),
+        }
+    }
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut bytes = s.bytes();
+    matches!(bytes.next(), Some(b) if b.is_ascii_alphabetic() || b == b'_')
+        && bytes.all(|b| b.is_ascii_alphanumeric() || b == b'_')
+}
+
+/// Parses the textual key portion of a record id, as produced by `RecordIdKey::to_string()`.
+///
+/// Text that SurrealDB would not accept as a key (or that would generate a new id, such as
+/// `rand()`) is taken literally as a string key.
+pub(crate) fn parse_key(s: &str) -> RecordIdKey {
+    match RecordId::from_str(&format!("t:{s}")) {
+        Ok(id) => match id.key().into_inner_ref() {
+            Id::Number(_) | Id::String(_) | Id::Uuid(_) | Id::Array(_) | Id::Object(_) => {
+                id.key().clone()
+            }
+            _ => RecordIdKey::from(s),
+        },
+        Err(_) => RecordIdKey::from(s),
+    }
+}
+
+struct KeyDe(RecordIdKey);
+
+impl<'de> Deserialize<'de> for KeyDe {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(KeyVisitor).map(KeyDe)
+    }
+}
+
+struct KeyVisitor;
+
+impl Visitor<'_> for KeyVisitor {
+    type Value = RecordIdKey;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a record id key as a string or an integer")
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(RecordIdKey::from(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        i64::try_from(v)
+            .map(RecordIdKey::from)
+            .map_err(|_| E::invalid_value(Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if v.is_empty() {
+            return Err(E::invalid_value(
+                Unexpected::Str(v),
+                &"a non-empty record id key",
+            ));
+        }
+        Ok(parse_key(v))
+    }
+}
+
+/// `Option<surrealdb::RecordId>` support for `#[serde(with = "...")]`.
+///
+/// Serde only looks for functions named `serialize` and `deserialize` in a `with` module, so
+/// this submodule exposes the option helpers under those names. As with the scalar
+/// [`super::deserialize`], [`deserialize`] cannot read a naked key back because it carries no
+/// table; use [`deserialize_for`] to read bare keys, for example in PATCH bodies:
+///
+/// ```
+/// # struct User;
+/// # impl atopio_extra::Table for User { const NAME: &'static str = "user"; }
+/// #[derive(serde::Deserialize)]
+/// struct PatchPost {
+///     #[serde(
+///         default,
+///         deserialize_with = "atopio_extra::record_id_naked::option::deserialize_for::<User, _>"
+///     )]
+///     author: Option<surrealdb::RecordId>,
+/// }
+///
+/// let patch: PatchPost = serde_json::from_str(r#"{"author":"abc"}"#)?;
+/// assert_eq!(patch.author, Some(("user", "abc").into()));
+/// let patch: PatchPost = serde_json::from_str("{}")?;
+/// assert_eq!(patch.author, None);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// Add `#[serde(default)]` if the field may be missing from the input entirely; a `with`
+/// module on its own makes the field required, even when it is an `Option`.
+pub mod option {
+    use super::{KeyDe, Tableless};
+    use crate::Table;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use surrealdb::RecordId;
+
+    /// Serialize an `Option<surrealdb::RecordId>` as the naked key, or `null`.
+    ///
+    /// See [`super::serialize_opt`].
+    pub fn serialize<S>(id: &Option<RecordId>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_opt(id, serializer)
+    }
+
+    /// Deserialize `null` into `None`, for a field annotated with this module.
+    ///
+    /// A key is only read back in formats that are not human readable, as
+    /// [`super::deserialize`] reads it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`super::deserialize`] for any value but `null`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<RecordId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<Tableless>::deserialize(deserializer)?.map(|id| id.0))
+    }
+
+    /// Deserialize `null` or a naked key into an `Option<surrealdb::RecordId>` in the table
+    /// named by `T`.
+    ///
+    /// `null` becomes `None`, and a key is read like [`super::deserialize_for`] and becomes
+    /// `Some`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is neither `null`, a string nor an
+    /// integer, if the string is empty, or if an unsigned integer does not fit in an `i64`.
+    pub fn deserialize_for<'de, T, D>(deserializer: D) -> Result<Option<RecordId>, D::Error>
+    where
+        T: Table,
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return Ok(Option::<RecordId>::deserialize(deserializer)?.map(super::in_table::<T>));
+        }
+        Ok(Option::<KeyDe>::deserialize(deserializer)?
+            .map(|KeyDe(key)| RecordId::from_table_key(T::NAME, key)))
+    }
+    /// JSON schema for a field serialized with this module: the key as a string, or `null`.
+    #[cfg(feature = "schemars")]
+    pub fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::schema::nullable(super::json_schema(generator))
+    }
+}
+
+/// `Vec<surrealdb::RecordId>` support for `#[serde(with = "...")]`.
+///
+/// Each element is written as its naked key, so `vec![user:abc, user:def]` becomes
+/// `["abc","def"]`. Use [`deserialize_for`] to rebuild the ids from bare keys; the plain
+/// [`deserialize`] cannot, as [`super::deserialize`] cannot.
+pub mod vec {
+    use super::{KeyDe, Tableless};
+    use crate::Table;
+    use serde::de::{Error, SeqAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::fmt;
+    use std::marker::PhantomData;
+    use surrealdb::RecordId;
+
+    /// Serialize a slice of `surrealdb::RecordId`s as an array of naked keys.
+    pub fn serialize<S>(ids: &[RecordId], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(ids.iter().map(Key))
+    }
+
+    /// Deserialize an array for a field annotated with this module, reading each element as
+    /// [`super::deserialize`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not an array, and the errors of
+    /// [`super::deserialize`] for any element.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<RecordId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let ids = Vec::<Tableless>::deserialize(deserializer)?;
+        Ok(ids.into_iter().map(|id| id.0).collect())
+    }
+
+    /// Deserialize an array of naked keys into `surrealdb::RecordId`s in the table named by `T`.
+    ///
+    /// Elements are read like [`super::deserialize_for`], so strings and integers may be
+    /// mixed in the same array (`["abc", 42]`).
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not an array, or if any element is not
+    /// a valid key. The error names the index of the element that failed.
+    pub fn deserialize_for<'de, T, D>(deserializer: D) -> Result<Vec<RecordId>, D::Error>
+    where
+        T: Table,
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            let ids = Vec::<RecordId>::deserialize(deserializer)?;
+            return Ok(ids.into_iter().map(super::in_table::<T>).collect());
+        }
+        deserializer.deserialize_seq(KeySeqVisitor::<T>(PhantomData))
+    }
+
+    pub(super) struct Key<'a>(pub(super) &'a RecordId);
+
+    impl Serialize for Key<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            super::serialize(self.0, serializer)
+        }
+    }
+
+    pub(super) struct KeySeqVisitor<T>(pub(super) PhantomData<fn() -> T>);
+
+    impl<'de, T: Table> Visitor<'de> for KeySeqVisitor<T> {
+        type Value = Vec<RecordId>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an array of record id keys")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut ids = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+            while let Some(KeyDe(key)) = seq.next_element().map_err(|e| {
+                A::Error::custom(format_args!(
+                    "invalid record id key at index {}: {e}",
+                    ids.len()
+                ))
+            })? {
+                ids.push(RecordId::from_table_key(T::NAME, key));
+            }
+            Ok(ids)
+        }
+    }
+}
+
+/// `Option<Vec<surrealdb::RecordId>>` support for `#[serde(with = "...")]`.
+///
+/// `None` is written as `null`, and `Some` as an array of naked keys. On input, `null`
+/// becomes `None` and `[]` becomes `Some(vec![])`; use [`deserialize_for`] to reattach the
+/// table to bare keys. Add `#[serde(default)]` if the field may be missing entirely.
+pub mod option_vec {
+    use super::Tableless;
+    use super::vec::Key;
+    use crate::Table;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::marker::PhantomData;
+    use surrealdb::RecordId;
+
+    /// Serialize an `Option<Vec<surrealdb::RecordId>>` as `null` or an array of naked keys.
+    pub fn serialize<S>(ids: &Option<Vec<RecordId>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return ids.serialize(serializer);
+        }
+        match ids {
+            Some(ids) => serializer.collect_seq(ids.iter().map(Key)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize `null` or an array, reading each element as [`super::deserialize`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is neither `null` nor an array, and the
+    /// errors of [`super::deserialize`] for any element.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<RecordId>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let ids = Option::<Vec<Tableless>>::deserialize(deserializer)?;
+        Ok(ids.map(|ids| ids.into_iter().map(|id| id.0).collect()))
+    }
+
+    /// Deserialize `null` or an array of naked keys in the table named by `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is neither `null` nor an array, or if an
+    /// element is not a valid key. The element error names its index.
+    pub fn deserialize_for<'de, T, D>(deserializer: D) -> Result<Option<Vec<RecordId>>, D::Error>
+    where
+        T: Table,
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<KeyVec<T>>::deserialize(deserializer)?.map(|ids| ids.0))
+    }
+
+    struct KeyVec<T>(Vec<RecordId>, PhantomData<fn() -> T>);
+
+    impl<'de, T: Table> Deserialize<'de> for KeyVec<T> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let ids = super::vec::deserialize_for::<T, D>(deserializer)?;
+            Ok(KeyVec(ids, PhantomData))
+        }
+    }
+}
+
+/// `HashMap<surrealdb::RecordId, V>` support with naked keys, for example `{"abc123": ...}`.
+///
+/// Use [`deserialize_for`] to reattach the table to each key; the plain [`deserialize`]
+/// cannot, as [`super::deserialize`] cannot.
+pub mod map_key {
+    use super::vec::Key;
+    use super::{KeyDe, Tableless};
+    use crate::Table;
+    use serde::de::{Error as _, MapAccess, Visitor};
+    use serde::ser::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::hash::BuildHasher;
+    use std::marker::PhantomData;
+    use surrealdb::{RecordId, RecordIdKey};
+
+    /// Serialize a map keyed by `surrealdb::RecordId` using naked keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns a serialization error if two distinct record ids share the same key (for
+    /// example `user:abc` and `post:abc`), since both would collapse into one JSON key.
+    pub fn serialize<S, V, H>(
+        map: &HashMap<RecordId, V, H>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        V: Serialize,
+        H: BuildHasher,
+    {
+        if !serializer.is_human_readable() {
+            return map.serialize(serializer);
+        }
+        let mut seen: HashMap<&RecordIdKey, &RecordId> = HashMap::with_capacity(map.len());
+        for id in map.keys() {
+            if let Some(other) = seen.insert(id.key(), id) {
+                return Err(S::Error::custom(format_args!(
+                    "record ids {other} and {id} both serialize to the naked key \"{}\"",
+                    super::KeyText(id.key())
+                )));
+            }
+        }
+        serializer.collect_map(map.iter().map(|(id, value)| (Key(id), value)))
+    }
+
+    /// Deserialize a map for a field annotated with this module, reading each key as
+    /// [`super::deserialize`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a map, and the errors of
+    /// [`super::deserialize`] for any key.
+    pub fn deserialize<'de, D, V, H>(deserializer: D) -> Result<HashMap<RecordId, V, H>, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Deserialize<'de>,
+        H: BuildHasher + Default,
+    {
+        let map = HashMap::<Tableless, V>::deserialize(deserializer)?;
+        Ok(map.into_iter().map(|(id, value)| (id.0, value)).collect())
+    }
+
+    /// Deserialize a map with naked keys into a map keyed by ids in the table named by `T`.
+    ///
+    /// Keys are read like [`super::deserialize_for`], so `"42"` becomes a numeric key.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a map, if a key is empty, or if two
+    /// keys parse to the same record id (for example `"42"` and `"42"` written twice).
+    pub fn deserialize_for<'de, T, D, V, H>(
+        deserializer: D,
+    ) -> Result<HashMap<RecordId, V, H>, D::Error>
+    where
+        T: Table,
+        D: Deserializer<'de>,
+        V: Deserialize<'de>,
+        H: BuildHasher + Default,
+    {
+        if !deserializer.is_human_readable() {
+            let native = HashMap::<RecordId, V, H>::deserialize(deserializer)?;
+            let mut map = HashMap::with_capacity_and_hasher(native.len(), H::default());
+            for (id, value) in native {
+                let id = super::in_table::<T>(id);
+                if map.contains_key(&id) {
+                    return Err(D::Error::custom(format_args!("duplicate record id {id}")));
+                }
+                map.insert(id, value);
+            }
+            return Ok(map);
+        }
+        deserializer.deserialize_map(KeyMapVisitor::<T, V, H>(PhantomData))
+    }
+
+    struct KeyMapVisitor<T, V, H>(PhantomData<(T, V, H)>);
+
+    impl<'de, T, V, H> Visitor<'de> for KeyMapVisitor<T, V, H>
+    where
+        T: Table,
+        V: Deserialize<'de>,
+        H: BuildHasher + Default,
+    {
+        type Value = HashMap<RecordId, V, H>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a map keyed by record id keys")
+        }
+
+        fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut map = HashMap::with_capacity_and_hasher(
+                access.size_hint().unwrap_or(0).min(4096),
+                H::default(),
+            );
+            while let Some((KeyDe(key), value)) = access.next_entry::<KeyDe, V>()? {
+                let id = RecordId::from_table_key(T::NAME, key);
+                if map.contains_key(&id) {
+                    return Err(A::Error::custom(format_args!(
+                        "duplicate record id key {id}"
+                    )));
+                }
+                map.insert(id, value);
+            }
+            Ok(map)
+        }
+    }
+}
+
+/// Naked serde helpers that keep integer keys as numbers.
+///
+/// The root functions stringify every key, so `order:42` becomes `"42"`. This module writes
+/// integer keys as JSON numbers (`42`) and every other key as a string, which suits clients
+/// that expect numeric ids. String keys are written as their raw text, so `order:⟨007⟩`
+/// becomes `"007"`; uuid, array and object keys use their SurrealQL text.
+///
+/// ```
+/// #[derive(serde::Serialize)]
+/// struct Order {
+///     #[serde(with = "atopio_extra::record_id_naked::typed")]
+///     id: surrealdb::RecordId,
+/// }
+///
+/// let order = Order { id: ("order", 42).into() };
+/// assert_eq!(serde_json::to_string(&order)?, r#"{"id":42}"#);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+pub mod typed {
+    use super::parse_key;
+    use crate::Table;
+    use serde::de::{self, Unexpected, Visitor};
+    use serde::{Deserializer, Serialize, Serializer};
+    use std::fmt;
+    use surrealdb::sql::Id;
+    use surrealdb::{RecordId, RecordIdKey};
+
+    /// Serialize the key of a `surrealdb::RecordId` as a number if it is an integer, and as a
+    /// string otherwise.
+    pub fn serialize<S>(id: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return id.serialize(serializer);
+        }
+        match id.key().into_inner_ref() {
+            Id::Number(n) => serializer.serialize_i64(*n),
+            Id::String(s) => serializer.serialize_str(s),
+            _ => serializer.collect_str(id.key()),
+        }
+    }
+
+    /// Deserialize a `surrealdb::RecordId` for a field annotated with this module.
+    ///
+    /// Like [`super::deserialize`], this exists so `#[serde(with = "...")]` compiles and only
+    /// reads back formats that are not human readable; use
+    /// [`deserialize_for`] to read the naked keys written by [`serialize`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`super::deserialize`].
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize(deserializer)
+    }
+
+    /// Deserialize a number or string key into a `surrealdb::RecordId` in the table named by
+    /// `T`.
+    ///
+    /// Integers become numeric keys. A string is only parsed when it is uuid, array or object
+    /// syntax; anything else, including digit strings like `"007"` or `"42"`, is kept as a
+    /// string key.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is neither a string nor an integer, if the
+    /// string is empty, or if an unsigned integer does not fit in an `i64`.
+    pub fn deserialize_for<'de, T, D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        T: Table,
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return super::native_for::<T, D>(deserializer);
+        }
+        let key = deserializer.deserialize_any(TypedKeyVisitor)?;
+        Ok(RecordId::from_table_key(T::NAME, key))
+    }
+
+    struct TypedKeyVisitor;
+
+    impl Visitor<'_> for TypedKeyVisitor {
+        type Value = RecordIdKey;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a record id key as an integer or a string")
+        }
+
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(RecordIdKey::from(v))
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            i64::try_from(v)
+                .map(RecordIdKey::from)
+                .map_err(|_| E::invalid_value(Unexpected::Unsigned(v), &self))
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            if v.is_empty() {
+                return Err(E::invalid_value(
+                    Unexpected::Str(v),
+                    &"a non-empty record id key",
+                ));
+            }
+            let key = parse_key(v);
+            Ok(match key.into_inner_ref() {
+                Id::Uuid(_) | Id::Array(_) | Id::Object(_) => key,
+                _ => RecordIdKey::from(v),
+            })
+        }
+    }
+}
+
+/// Naked helpers that read numbers into string-keyed tables, and the other way round.
+///
+/// Some systems send `{"id": 12345}` for a record whose key is the string `"12345"`.
+/// [`deserialize_for`] accepts a string or an integer and converts it to the key kind declared
+/// by [`Table::KEY_KIND`]:
+///
+/// ```
+/// use atopio_extra::{KeyKind, Table};
+///
+/// struct Customer;
+///
+/// impl Table for Customer {
+///     const NAME: &'static str = "customer";
+///     const KEY_KIND: KeyKind = KeyKind::String;
+/// }
+///
+/// #[derive(serde::Deserialize)]
+/// struct Import {
+///     #[serde(deserialize_with = "atopio_extra::record_id_naked::flexible::deserialize_for::<Customer, _>")]
+///     id: surrealdb::RecordId,
+/// }
+///
+/// let row: Import = serde_json::from_str(r#"{"id":12345}"#)?;
+/// assert_eq!(row.id, ("customer", "12345").into());
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// Writing is the same as [`super::typed::serialize`], with integer keys as numbers and string
+/// keys exactly as they are, so every key written reads back unchanged.
+pub mod flexible {
+    use super::parse_key;
+    use crate::{KeyKind, Table};
+    use serde::de::{self, Unexpected, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::fmt;
+    use surrealdb::{RecordId, RecordIdKey};
+
+    /// Serialize the key of a `surrealdb::RecordId` as a number if it is an integer, and as a
+    /// string otherwise, like [`super::typed::serialize`].
+    pub fn serialize<S>(id: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::typed::serialize(id, serializer)
+    }
+
+    /// Deserialize a `surrealdb::RecordId` for a field annotated with this module.
+    ///
+    /// Like [`super::deserialize`], this exists so `#[serde(with = "...")]` compiles and only
+    /// reads back formats that are not human readable; use
+    /// [`deserialize_for`] to read naked keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`super::deserialize`].
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize(deserializer)
+    }
+
+    /// Deserialize a string or integer key into a `surrealdb::RecordId` in the table named by
+    /// `T`, converting it to `T::KEY_KIND`.
+    ///
+    /// - For [`KeyKind::String`] tables, integers become their decimal text and strings are
+    ///   kept exactly as given, so `"00123"` stays `"00123"`.
+    /// - For [`KeyKind::Integer`] tables, integers are kept and strings must be the decimal
+    ///   text of an `i64`, without a `+` sign or leading zeros.
+    /// - For any other kind, integers are rejected and strings are read like
+    ///   [`super::deserialize_for`] reads them.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is a float or any other type that is
+    /// neither a string nor an integer, if the string is empty, if an unsigned integer does not
+    /// fit in an `i64`, or if the value cannot be converted to `T::KEY_KIND`.
+    pub fn deserialize_for<'de, T, D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        T: Table,
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return super::native_for::<T, D>(deserializer);
+        }
+        let key = deserializer.deserialize_any(FlexibleKeyVisitor(T::KEY_KIND))?;
+        Ok(RecordId::from_table_key(T::NAME, key))
+    }
+
+    struct FlexibleKeyVisitor(KeyKind);
+
+    impl FlexibleKeyVisitor {
+        fn integer<E: de::Error>(&self, v: i64) -> Result<RecordIdKey, E> {
+            match self.0 {
+                KeyKind::Integer => Ok(RecordIdKey::from(v)),
+                KeyKind::String => Ok(RecordIdKey::from(v.to_string())),
+                kind => Err(E::invalid_type(
+                    Unexpected::Signed(v),
+                    &format!("{} {kind} record id key", kind.article()).as_str(),
+                )),
+            }
+        }
+    }
+
+    impl Visitor<'_> for FlexibleKeyVisitor {
+        type Value = RecordIdKey;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                formatter,
+                "a record id key as a string or an integer, for a table with {} keys",
+                self.0
+            )
+        }
+
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            self.integer(v)
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let v =
+                i64::try_from(v).map_err(|_| E::invalid_value(Unexpected::Unsigned(v), &self))?;
+            self.integer(v)
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            if v.is_empty() {
+                return Err(E::invalid_value(
+                    Unexpected::Str(v),
+                    &"a non-empty record id key",
+                ));
+            }
+            match self.0 {
+                KeyKind::String => Ok(RecordIdKey::from(v)),
+                KeyKind::Integer => v
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|n| n.to_string() == v)
+                    .map(RecordIdKey::from)
+                    .ok_or_else(|| {
+                        E::invalid_value(
+                            Unexpected::Str(v),
+                            &"an integer record id key without leading zeros",
+                        )
+                    }),
+                _ => Ok(parse_key(v)),
+            }
+        }
+    }
+}
+
+/// Naked serde helpers writing the key as its natural JSON value.
+///
+/// String keys are written as strings, integer keys as numbers, uuid keys as canonical
+/// (lowercase, hyphenated) uuid strings, and array and object keys as JSON arrays and objects,
+/// so no SurrealQL syntax such as `⟨...⟩` or `u'...'` reaches the output:
+///
+/// ```
+/// #[derive(serde::Serialize)]
+/// struct Reading {
+///     #[serde(with = "atopio_extra::record_id_naked::value")]
+///     id: surrealdb::RecordId,
+/// }
+///
+/// let reading = Reading { id: "reading:['sensor1', 1717000000]".parse().unwrap() };
+/// assert_eq!(serde_json::to_string(&reading)?, r#"{"id":["sensor1",1717000000]}"#);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// On input a string is only read as a uuid key when it is exactly in the canonical form, so a
+/// string key that happens to look like an uppercase uuid stays a string. Reading keys back
+/// requires a self-describing format such as JSON, or one that is not human readable, in which
+/// case the whole id is written in `RecordId`'s own encoding.
+pub mod value {
+    use crate::Table;
+    use crate::key_value::{KeyJson, key_from_json};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value as Json;
+    use surrealdb::RecordId;
+
+    /// Serialize the key of a `surrealdb::RecordId` as its natural JSON value.
+    pub fn serialize<S>(id: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return id.serialize(serializer);
+        }
+        KeyJson(id.key()).serialize(serializer)
+    }
+
+    /// Deserialize a `surrealdb::RecordId` for a field annotated with this module.
+    ///
+    /// Like [`super::deserialize`], this exists so `#[serde(with = "...")]` compiles and only
+    /// reads back formats that are not human readable; use
+    /// [`deserialize_for`] to read the keys written by [`serialize`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`super::deserialize`].
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize(deserializer)
+    }
+
+    /// Deserialize a JSON key into a `surrealdb::RecordId` in the table named by `T`.
+    ///
+    /// Each JSON shape maps back to the key variant [`serialize`] writes it from.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is `null` or a boolean, or if a number is
+    /// not a 64-bit integer.
+    pub fn deserialize_for<'de, T, D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        T: Table,
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return super::native_for::<T, D>(deserializer);
+        }
+        let key = key_from_json(Json::deserialize(deserializer)?).map_err(D::Error::custom)?;
+        Ok(RecordId::from_table_key(T::NAME, key))
+    }
+}
+
+/// Naked serde helpers for record ids with uuid keys.
+///
+/// The key is written as a plain canonical uuid string, with no table and no `u'...'`
+/// wrapper, so `user:u'018f3c1e-0000-7000-8000-000000000000'` becomes
+/// `"018f3c1e-0000-7000-8000-000000000000"`. Input may be in any casing and in the simple,
+/// braced or `urn:uuid:` forms.
+pub mod uuid {
+    use crate::Table;
+    use serde::de::{self, Unexpected, Visitor};
+    use serde::ser::Error as _;
+    use serde::{Deserializer, Serialize, Serializer};
+    use std::fmt;
+    use surrealdb::sql::Id;
+    use surrealdb::{RecordId, RecordIdKey, Uuid};
+
+    /// Serialize the uuid key of a `surrealdb::RecordId` as a canonical uuid string.
+    ///
+    /// # Errors
+    ///
+    /// Returns a serialization error if the key is not a uuid.
+    pub fn serialize<S>(id: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match id.key().into_inner_ref() {
+            Id::Uuid(_) if !serializer.is_human_readable() => id.serialize(serializer),
+            Id::Uuid(u) => serializer.collect_str(&u.0.hyphenated()),
+            _ => Err(S::Error::custom(format_args!(
+                "record id {id} does not have a uuid key"
+            ))),
+        }
+    }
+
+    /// Deserialize a `surrealdb::RecordId` for a field annotated with this module.
+    ///
+    /// Like [`super::deserialize`], this exists so `#[serde(with = "...")]` compiles and only
+    /// reads back formats that are not human readable; use
+    /// [`deserialize_for`] to read the uuid strings written by [`serialize`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`super::deserialize`].
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize(deserializer)
+    }
+
+    /// Deserialize a uuid string into a `surrealdb::RecordId` in the table named by `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a string or if the string is not a
+    /// valid uuid.
+    pub fn deserialize_for<'de, T, D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        T: Table,
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            let id = super::native_for::<T, D>(deserializer)?;
+            return match id.key().into_inner_ref() {
+                Id::Uuid(_) => Ok(id),
+                _ => Err(de::Error::custom(format_args!(
+                    "record id {id} does not have a uuid key"
+                ))),
+            };
+        }
+        let uuid = deserializer.deserialize_str(UuidVisitor)?;
+        Ok(RecordId::from_table_key(
+            T::NAME,
+            RecordIdKey::from_inner(Id::Uuid(uuid.into())),
+        ))
+    }
+
+    struct UuidVisitor;
+
+    impl Visitor<'_> for UuidVisitor {
+        type Value = Uuid;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a uuid string")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Uuid::try_parse(v).map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
+        }
+    }
+}
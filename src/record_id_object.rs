@@ -0,0 +1,140 @@
+use crate::key_value::{KeyJson, key_from_json};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use surrealdb::RecordId;
+
+#[derive(Serialize)]
+struct ObjectRef<'a> {
+    table: &'a str,
+    key: KeyJson<'a>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ObjectOwned {
+    table: String,
+    key: serde_json::Value,
+}
+
+struct Borrowed<'a>(&'a RecordId);
+
+impl Serialize for Borrowed<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize(self.0, serializer)
+    }
+}
+
+struct Owned(RecordId);
+
+impl<'de> Deserialize<'de> for Owned {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer).map(Owned)
+    }
+}
+
+/// Serialize a `surrealdb::RecordId` as a structured `{"table": ..., "key": ...}` object.
+///
+/// This helper is intended for use with `#[serde(with = "...")]` on fields of type
+/// `surrealdb::RecordId`, for consumers that want the table and key as separate fields. The
+/// key keeps its natural JSON type: `user:abc123` becomes `{"table":"user","key":"abc123"}`
+/// and `order:42` becomes `{"table":"order","key":42}`. Uuid keys are written as canonical
+/// uuid strings, and array and object keys as JSON arrays and objects.
+pub fn serialize<S>(id: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    ObjectRef {
+        table: id.table(),
+        key: KeyJson(id.key()),
+    }
+    .serialize(serializer)
+}
+
+/// Deserialize a `{"table": ..., "key": ...}` object into a `surrealdb::RecordId`.
+///
+/// This is the counterpart to `serialize`. Numbers become integer keys, strings become string
+/// keys (or uuid keys when the string is a canonical lowercase uuid), and arrays and objects
+/// become array and object keys.
+///
+/// # Errors
+///
+/// Returns a deserialization error if the value is not an object, if `table` or `key` is
+/// missing, if any other field is present, or if the key is not a valid record id key (for
+/// example `null` or a float).
+pub fn deserialize<'de, D>(deserializer: D) -> Result<RecordId, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let ObjectOwned { table, key } = ObjectOwned::deserialize(deserializer)?;
+    let key = key_from_json(key).map_err(D::Error::custom)?;
+    Ok(RecordId::from_table_key(table, key))
+}
+
+/// `Option<surrealdb::RecordId>` support for `#[serde(with = "...")]`.
+///
+/// `None` is written as `null`. Add `#[serde(default)]` if the field may be missing entirely.
+pub mod option {
+    use super::Owned;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use surrealdb::RecordId;
+
+    /// Serialize an `Option<surrealdb::RecordId>` as a `{"table", "key"}` object or `null`.
+    pub fn serialize<S>(id: &Option<RecordId>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match id {
+            Some(id) => super::serialize(id, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize a `{"table", "key"}` object or `null`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error under the same conditions as [`super::deserialize`].
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<RecordId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<Owned>::deserialize(deserializer)?.map(|id| id.0))
+    }
+}
+
+/// `Vec<surrealdb::RecordId>` support for `#[serde(with = "...")]`.
+pub mod vec {
+    use super::{Borrowed, Owned};
+    use serde::{Deserialize, Deserializer, Serializer};
+    use surrealdb::RecordId;
+
+    /// Serialize a slice of `surrealdb::RecordId`s as an array of `{"table", "key"}` objects.
+    pub fn serialize<S>(ids: &[RecordId], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(ids.iter().map(Borrowed))
+    }
+
+    /// Deserialize an array of `{"table", "key"}` objects.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not an array, or if any element fails
+    /// as described in [`super::deserialize`].
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<RecordId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<Owned>::deserialize(deserializer)?
+            .into_iter()
+            .map(|id| id.0)
+            .collect())
+    }
+}
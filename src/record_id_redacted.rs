@@ -0,0 +1,81 @@
+//! Serialize-only helpers writing record ids with their keys hidden.
+//!
+//! For audit payloads and other records that must not hold raw keys. Each id is written as the
+//! string [`RecordIdRedacted`] displays, such as `"user:<redacted:1143>"`, in every format:
+//!
+//! ```
+//! #[derive(serde::Serialize)]
+//! struct AuditEvent {
+//!     action: &'static str,
+//!     #[serde(serialize_with = "atopio_extra::record_id_redacted::serialize")]
+//!     actor: surrealdb::RecordId,
+//! }
+//!
+//! let event = AuditEvent { action: "login", actor: "user:⟨ada@example.com⟩".parse().unwrap() };
+//! let json = serde_json::to_string(&event).unwrap();
+//! assert!(json.starts_with(r#"{"action":"login","actor":"user:<redacted:"#));
+//! ```
+//!
+//! There is no `deserialize`: a redacted id cannot be turned back into a record id.
+
+use crate::RecordIdRedacted;
+use serde::{Serialize, Serializer};
+use surrealdb::RecordId;
+
+/// Serialize a `surrealdb::RecordId` with its key redacted.
+pub fn serialize<S>(id: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(&RecordIdRedacted(id))
+}
+
+/// A record id that serializes with its key redacted, as [`serialize`] writes it.
+struct Redacted<'a>(&'a RecordId);
+
+impl Serialize for Redacted<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize(self.0, serializer)
+    }
+}
+
+/// `Option<surrealdb::RecordId>` support for `#[serde(serialize_with = "...")]`.
+pub mod option {
+    use super::Redacted;
+    use serde::Serializer;
+    use surrealdb::RecordId;
+
+    /// Serialize an `Option<surrealdb::RecordId>` with its key redacted, or `null`.
+    pub fn serialize<S>(id: &Option<RecordId>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match id {
+            Some(id) => serializer.serialize_some(&Redacted(id)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+/// `Vec<surrealdb::RecordId>` support for `#[serde(serialize_with = "...")]`.
+pub mod vec {
+    use super::Redacted;
+    use serde::Serializer;
+    use serde::ser::SerializeSeq;
+    use surrealdb::RecordId;
+
+    /// Serialize a slice of `surrealdb::RecordId`s with their keys redacted.
+    pub fn serialize<S>(ids: &[RecordId], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(ids.len()))?;
+        for id in ids {
+            seq.serialize_element(&Redacted(id))?;
+        }
+        seq.end()
+    }
+}
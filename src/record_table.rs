@@ -0,0 +1,76 @@
+//! Serde helpers writing only the table a `surrealdb::RecordId` points at.
+//!
+//! Useful for audit events and metrics, where `user:abc123` should be recorded as `"user"`.
+//! The key is discarded, so a record id cannot be rebuilt from the output: [`deserialize`]
+//! exists only so `#[serde(with = "...")]` compiles, and always fails.
+//!
+//! ```
+//! #[derive(serde::Serialize)]
+//! struct AuditEvent {
+//!     #[serde(with = "atopio_extra::record_table")]
+//!     target: surrealdb::RecordId,
+//! }
+//!
+//! let event = AuditEvent { target: ("user", "abc123").into() };
+//! assert_eq!(serde_json::to_string(&event)?, r#"{"target":"user"}"#);
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+
+use serde::de::Error;
+use serde::{Deserializer, Serializer};
+use surrealdb::RecordId;
+
+/// Serialize the table name of a `surrealdb::RecordId` as a string.
+pub fn serialize<S>(id: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(id.table())
+}
+
+/// Always fails, because the key cannot be reconstructed from a table name.
+///
+/// # Errors
+///
+/// Always returns a deserialization error.
+pub fn deserialize<'de, D>(_deserializer: D) -> Result<RecordId, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Err(D::Error::custom(
+        "cannot deserialize a record id from its table name alone; record_table only serializes",
+    ))
+}
+
+/// `Option<surrealdb::RecordId>` support for `#[serde(with = "...")]`, writing the table name
+/// or `null`.
+pub mod option {
+    use serde::de::Error;
+    use serde::{Deserializer, Serializer};
+    use surrealdb::RecordId;
+
+    /// Serialize the table name of an `Option<surrealdb::RecordId>`, or `null` for `None`.
+    pub fn serialize<S>(id: &Option<RecordId>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match id {
+            Some(id) => serializer.serialize_some(id.table()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Always fails, because the key cannot be reconstructed from a table name.
+    ///
+    /// # Errors
+    ///
+    /// Always returns a deserialization error.
+    pub fn deserialize<'de, D>(_deserializer: D) -> Result<Option<RecordId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Err(D::Error::custom(
+            "cannot deserialize a record id from its table name alone; record_table only serializes",
+        ))
+    }
+}
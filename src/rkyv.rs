@@ -0,0 +1,254 @@
+//! rkyv archive support for the record id wrapper types.
+//!
+//! Enabled by the `rkyv` feature. [`TypedId`], [`FullId`], [`OrdId`], [`Naked`] and
+//! [`RecordIdParts`] implement rkyv's `Archive`, `Serialize` and `Deserialize`, so they can be
+//! fields of archived structs. Every one of them archives as a [`RawRecordId`]: the table name
+//! and the key, not a formatted string. The archived form can be read in place:
+//!
+//! ```
+//! use atopio_extra::rkyv::ArchivedRawKey;
+//! use atopio_extra::types::FullId;
+//!
+//! let id = FullId("user:42".parse().unwrap());
+//! let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&id).unwrap();
+//!
+//! let archived = rkyv::access::<rkyv::Archived<FullId>, rkyv::rancor::Error>(&bytes).unwrap();
+//! assert_eq!(archived.table.as_str(), "user");
+//! assert!(matches!(archived.key, ArchivedRawKey::Int(n) if n == 42));
+//!
+//! let back = rkyv::from_bytes::<FullId, rkyv::rancor::Error>(&bytes).unwrap();
+//! assert_eq!(back, id);
+//! ```
+//!
+//! Reading a [`TypedId`] or [`Naked`] back fails if the archived table is not `T::NAME`.
+//!
+//! Array and object keys are not archived structurally: [`RawKey::Other`] holds their
+//! SurrealQL text, such as `['s1', 1]`, which [`ArchivedRawRecordId::to_record_id`] parses
+//! back. Reading such a key in place gives that text, not the values inside it.
+
+use crate::key_value::RecordIdParts;
+use crate::typed_id::TableMismatch;
+use crate::types::{FullId, Naked, OrdId};
+use crate::{Table, TypedId};
+use ::rkyv::rancor::{Fallible, Source};
+use ::rkyv::{Archive, Deserialize, Place, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use surrealdb::sql::Id;
+use surrealdb::{RecordId, RecordIdKey};
+
+/// The archived layout of a record id: its table and its key.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+pub struct RawRecordId {
+    /// The table name, without any escaping.
+    pub table: String,
+    /// The key.
+    pub key: RawKey,
+}
+
+/// The archived layout of a record id key.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+pub enum RawKey {
+    /// A string key.
+    String(String),
+    /// An integer key.
+    Int(i64),
+    /// A uuid key, as its 16 bytes.
+    Uuid([u8; 16]),
+    /// An array, object or other key, as its SurrealQL text, such as `['s1', 1]`.
+    Other(String),
+}
+
+impl From<&RecordId> for RawRecordId {
+    fn from(id: &RecordId) -> Self {
+        let key = match id.key().into_inner_ref() {
+            Id::String(s) => RawKey::String(s.clone()),
+            Id::Number(n) => RawKey::Int(*n),
+            Id::Uuid(u) => RawKey::Uuid(u.0.into_bytes()),
+            _ => RawKey::Other(id.key().to_string()),
+        };
+        Self {
+            table: id.table().to_owned(),
+            key,
+        }
+    }
+}
+
+impl TryFrom<RawRecordId> for RecordId {
+    type Error = RawKeyError;
+
+    /// Rebuilds the record id, parsing the SurrealQL text of [`RawKey::Other`] keys.
+    fn try_from(raw: RawRecordId) -> Result<Self, RawKeyError> {
+        let key = match raw.key {
+            RawKey::String(s) => RecordIdKey::from(s),
+            RawKey::Int(n) => RecordIdKey::from(n),
+            RawKey::Uuid(bytes) => RecordIdKey::from(surrealdb::Uuid::from_bytes(bytes)),
+            RawKey::Other(text) => parse_key(&text)?,
+        };
+        Ok(RecordId::from_table_key(raw.table, key))
+    }
+}
+
+impl ArchivedRawRecordId {
+    /// Rebuilds the record id from the archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an [`RawKey::Other`] key is not valid SurrealQL, which only happens
+    /// if the archive was not written by this crate.
+    pub fn to_record_id(&self) -> Result<RecordId, RawKeyError> {
+        let key = match &self.key {
+            ArchivedRawKey::String(s) => RecordIdKey::from(s.as_str()),
+            ArchivedRawKey::Int(n) => RecordIdKey::from(n.to_native()),
+            ArchivedRawKey::Uuid(bytes) => RecordIdKey::from(surrealdb::Uuid::from_bytes(*bytes)),
+            ArchivedRawKey::Other(text) => parse_key(text)?,
+        };
+        Ok(RecordId::from_table_key(self.table.as_str(), key))
+    }
+}
+
+/// Parses the SurrealQL text of a key on its own, without a table.
+fn parse_key(text: &str) -> Result<RecordIdKey, RawKeyError> {
+    RecordId::from_str(&format!("t:{text}"))
+        .map(|id| id.key().clone())
+        .map_err(|_| RawKeyError(text.to_owned()))
+}
+
+/// The reason an archived id could not be rebuilt: the text of a [`RawKey::Other`] key is not
+/// a SurrealQL record id key. Holds the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawKeyError(String);
+
+impl fmt::Display for RawKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is not a valid record id key", self.0)
+    }
+}
+
+impl std::error::Error for RawKeyError {}
+
+/// Implements `Archive` and `Serialize` for a type by archiving the [`RawRecordId`] built by
+/// `$raw(self)`.
+macro_rules! impl_archive {
+    ($([$($param:ident)?] $ty:ty => $raw:expr),* $(,)?) => {$(
+        impl$(<$param>)? Archive for $ty {
+            type Archived = ArchivedRawRecordId;
+            type Resolver = RawRecordIdResolver;
+
+            fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+                $raw(self).resolve(resolver, out);
+            }
+        }
+
+        impl<$($param,)? S> Serialize<S> for $ty
+        where
+            S: Fallible + ?Sized,
+            S::Error: Source,
+            RawRecordId: Serialize<S>,
+        {
+            fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+                $raw(self).serialize(serializer)
+            }
+        }
+    )*};
+}
+
+impl_archive! {
+    [] FullId => |id: &FullId| RawRecordId::from(&id.0),
+    [] OrdId => |id: &OrdId| RawRecordId::from(&id.0),
+    [T] TypedId<T> => |id: &TypedId<T>| RawRecordId::from(id.id()),
+    [T] Naked<T> => |id: &Naked<T>| RawRecordId::from(id.id()),
+}
+
+impl<D> Deserialize<FullId, D> for ArchivedRawRecordId
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<FullId, D::Error> {
+        self.to_record_id().map(FullId).map_err(Source::new)
+    }
+}
+
+impl<D> Deserialize<OrdId, D> for ArchivedRawRecordId
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<OrdId, D::Error> {
+        self.to_record_id().map(OrdId).map_err(Source::new)
+    }
+}
+
+impl<T: Table, D> Deserialize<TypedId<T>, D> for ArchivedRawRecordId
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<TypedId<T>, D::Error> {
+        let id = self.to_record_id().map_err(Source::new)?;
+        TypedId::try_from(id).map_err(Source::new)
+    }
+}
+
+impl<T: Table, D> Deserialize<Naked<T>, D> for ArchivedRawRecordId
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<Naked<T>, D::Error> {
+        let id = self.to_record_id().map_err(Source::new)?;
+        if id.table() != T::NAME {
+            return Err(Source::new(TableMismatch {
+                expected: T::NAME,
+                found: id.table().to_owned(),
+            }));
+        }
+        Ok(Naked::new(id.key().clone()))
+    }
+}
+
+/// The resolver of [`RecordIdParts`]: the [`RawRecordId`] built from the parts when they were
+/// serialized, and its own resolver.
+pub struct RecordIdPartsResolver {
+    raw: RawRecordId,
+    resolver: RawRecordIdResolver,
+}
+
+impl Archive for RecordIdParts {
+    type Archived = ArchivedRawRecordId;
+    type Resolver = RecordIdPartsResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        resolver.raw.resolve(resolver.resolver, out);
+    }
+}
+
+impl<S> Serialize<S> for RecordIdParts
+where
+    S: Fallible + ?Sized,
+    S::Error: Source,
+    RawRecordId: Serialize<S>,
+{
+    /// Archives the parts as the record id they name.
+    ///
+    /// Fails if the key cannot be a record id key, as described in
+    /// [`KeyValueError`](crate::key_value::KeyValueError).
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let id = RecordId::try_from(self.clone()).map_err(Source::new)?;
+        let raw = RawRecordId::from(&id);
+        let resolver = raw.serialize(serializer)?;
+        Ok(RecordIdPartsResolver { raw, resolver })
+    }
+}
+
+impl<D> Deserialize<RecordIdParts, D> for ArchivedRawRecordId
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<RecordIdParts, D::Error> {
+        let id = self.to_record_id().map_err(Source::new)?;
+        RecordIdParts::try_from(&id).map_err(Source::new)
+    }
+}
@@ -0,0 +1,181 @@
+//! `schemars::JsonSchema` support for the record id wrapper types.
+
+use crate::link::RecordLink;
+use crate::types::{FullId, Naked, OrdId};
+use crate::{RecordIdRange, Table, TypedId};
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde_json::Value as Json;
+use std::borrow::Cow;
+
+/// Matches a plain table name followed by `:` and a non-empty key.
+const FULL_PATTERN: &str = "^[A-Za-z0-9_]+:.+$";
+
+/// The schema of a full (table:key) string, optionally restricted to one table.
+pub(crate) fn full(table: Option<&str>) -> Schema {
+    match table {
+        Some(table) => json_schema!({
+            "type": "string",
+            "pattern": format!("^{}:.+$", escape(table)),
+            "description": format!(
+                "A SurrealDB record id in the `{table}` table, in table:key form, for example \
+                 `{table}:abc123`."
+            ),
+        }),
+        None => json_schema!({
+            "type": "string",
+            "pattern": FULL_PATTERN,
+            "description": "A SurrealDB record id in table:key form, for example `user:abc123`.",
+        }),
+    }
+}
+
+/// The schema of a naked key, optionally naming the table it belongs to.
+pub(crate) fn naked(table: Option<&str>) -> Schema {
+    let description = match table {
+        Some(table) => format!(
+            "The key of a SurrealDB record id in the `{table}` table, without the table, for \
+             example `abc123`."
+        ),
+        None => {
+            "The key of a SurrealDB record id, without the table, for example `abc123`.".to_owned()
+        }
+    };
+    json_schema!({
+        "type": "string",
+        "minLength": 1,
+        "description": description,
+    })
+}
+
+/// Allows `null` in addition to the type of `schema`.
+pub(crate) fn nullable(mut schema: Schema) -> Schema {
+    if let Some(Json::String(ty)) = schema.get("type") {
+        let types = Json::from(vec![ty.clone(), "null".to_owned()]);
+        schema.insert("type".to_owned(), types);
+    }
+    schema
+}
+
+/// Escapes the regex metacharacters in a table name.
+fn escape(table: &str) -> String {
+    let mut out = String::with_capacity(table.len());
+    for c in table.chars() {
+        if !(c.is_ascii_alphanumeric() || c == '_') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+impl JsonSchema for FullId {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("FullId")
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("atopio_extra::types::FullId")
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        full(None)
+    }
+}
+
+impl JsonSchema for OrdId {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("OrdId")
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("atopio_extra::types::OrdId")
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        full(None)
+    }
+}
+
+impl<T: Table> JsonSchema for TypedId<T> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Owned(format!("TypedId_{}", T::NAME))
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Owned(format!("atopio_extra::TypedId<{}>", T::NAME))
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        full(Some(T::NAME))
+    }
+}
+
+impl<T: Table> JsonSchema for Naked<T> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Owned(format!("Naked_{}", T::NAME))
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Owned(format!("atopio_extra::types::Naked<{}>", T::NAME))
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        naked(Some(T::NAME))
+    }
+}
+
+impl JsonSchema for RecordIdRange {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("RecordIdRange")
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("atopio_extra::RecordIdRange")
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "A range of SurrealDB record ids in one table, for example `user:1..100`.",
+        })
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for RecordLink<T> {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Owned(format!("RecordLink_{}", T::schema_name()))
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Owned(format!(
+            "atopio_extra::link::RecordLink<{}>",
+            T::schema_id()
+        ))
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "anyOf": [full(None), generator.subschema_for::<T>()],
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{Table, record_id_full, record_id_naked};
+use serde::{Deserializer, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+use std::marker::PhantomData;
+use surrealdb::RecordId;
+
+/// `serde_with` adapter writing a `surrealdb::RecordId` in the full (table:key) form.
+///
+/// Produces exactly the same output as `record_id_full`, but composes with nested types:
+///
+/// ```
+/// use atopio_extra::serde_as::RecordIdFull;
+/// use serde_with::serde_as;
+/// use std::collections::HashMap;
+///
+/// #[serde_as]
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Groups {
+///     #[serde_as(as = "HashMap<_, Vec<RecordIdFull>>")]
+///     members: HashMap<String, Vec<surrealdb::RecordId>>,
+/// }
+/// ```
+pub struct RecordIdFull;
+
+impl SerializeAs<RecordId> for RecordIdFull {
+    fn serialize_as<S>(source: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        record_id_full::serialize(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, RecordId> for RecordIdFull {
+    fn deserialize_as<D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        record_id_full::deserialize(deserializer)
+    }
+}
+
+/// `serde_with` adapter writing a `surrealdb::RecordId` as its naked key.
+///
+/// Produces exactly the same output as `record_id_naked`. Like `record_id_naked::deserialize`,
+/// it cannot read a naked key back in a human readable format such as JSON, as the key has no
+/// table; use [`RecordIdNakedFor`] to read bare keys.
+pub struct RecordIdNaked;
+
+impl SerializeAs<RecordId> for RecordIdNaked {
+    fn serialize_as<S>(source: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        record_id_naked::serialize(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, RecordId> for RecordIdNaked {
+    fn deserialize_as<D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        record_id_naked::deserialize(deserializer)
+    }
+}
+
+/// `serde_with` adapter writing a `surrealdb::RecordId` as its naked key and reading bare keys
+/// back into the table named by `T`.
+///
+/// Serialization matches [`RecordIdNaked`]; deserialization matches
+/// `record_id_naked::deserialize_for::<T, _>`.
+pub struct RecordIdNakedFor<T>(PhantomData<fn() -> T>);
+
+impl<T> SerializeAs<RecordId> for RecordIdNakedFor<T> {
+    fn serialize_as<S>(source: &RecordId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        record_id_naked::serialize(source, serializer)
+    }
+}
+
+impl<'de, T: Table> DeserializeAs<'de, RecordId> for RecordIdNakedFor<T> {
+    fn deserialize_as<D>(deserializer: D) -> Result<RecordId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        record_id_naked::deserialize_for::<T, D>(deserializer)
+    }
+}
@@ -0,0 +1,150 @@
+//! Record ids as single URL path segments.
+//!
+//! A slug only uses characters that never need percent-encoding (`A-Z a-z 0-9 - . _ ~`), so a
+//! route can take one path parameter for the whole id and no layer in between can
+//! double-encode it:
+//!
+//! ```
+//! use atopio_extra::slug;
+//!
+//! let id: surrealdb::RecordId = "user:⟨has spaces⟩".parse().unwrap();
+//! assert_eq!(slug::encode(&id), "user~aGFzIHNwYWNlcw");
+//! assert_eq!(slug::decode("user~aGFzIHNwYWNlcw").unwrap(), id);
+//!
+//! let id: surrealdb::RecordId = "user:abc123".parse().unwrap();
+//! assert_eq!(slug::encode(&id), "user.abc123");
+//! ```
+//!
+//! # Format
+//!
+//! The table name is followed by a separator that says how the key is written:
+//!
+//! - `.` and the key as is, for integer keys (`user.42`) and for string keys made only of
+//!   ASCII letters, digits and `_` that do not look like an integer (`user.abc123`).
+//! - `~` and the URL-safe base64 (without padding) of the UTF-8 text, for every other string
+//!   key (`user~aGFzIHNwYWNlcw`). The string key `"42"` is written `user~NDI`, so it cannot be
+//!   mistaken for the integer key `42`.
+//! - `-` and the URL-safe base64 of the key in SurrealQL form, for uuid, array and object keys
+//!   (`reading-WydzMScsIDFd` for `reading:['s1', 1]`).
+//!
+//! When the table name itself contains anything other than ASCII letters, digits and `_`, the
+//! slug is instead `~` followed by the URL-safe base64 of the whole id in SurrealQL form.
+//!
+//! The format is part of the crate's stable interface: slugs issued by this version decode to
+//! the same id in every later version.
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use std::fmt;
+use std::str::FromStr;
+use surrealdb::sql::Id;
+use surrealdb::{RecordId, RecordIdKey};
+
+/// Encodes `id` as a URL path segment.
+pub fn encode(id: &RecordId) -> String {
+    let table = id.table();
+    if !is_simple(table) {
+        return format!("~{}", URL_SAFE_NO_PAD.encode(id.to_string()));
+    }
+    match id.key().into_inner_ref() {
+        Id::Number(n) => format!("{table}.{n}"),
+        Id::String(s) if is_simple(s) && !is_integer(s) => format!("{table}.{s}"),
+        Id::String(s) => format!("{table}~{}", URL_SAFE_NO_PAD.encode(s)),
+        _ => format!("{table}-{}", URL_SAFE_NO_PAD.encode(id.key().to_string())),
+    }
+}
+
+/// Decodes a slug produced by [`encode`].
+///
+/// # Errors
+///
+/// Returns [`SlugError::Malformed`] if the input does not have the layout of a slug,
+/// [`SlugError::InvalidEncoding`] if an encoded part is not URL-safe base64 of UTF-8 text,
+/// and [`SlugError::InvalidId`] if an encoded key or id is not valid SurrealQL.
+pub fn decode(slug: &str) -> Result<RecordId, SlugError> {
+    if let Some(encoded) = slug.strip_prefix('~') {
+        let text = decode_text(encoded)?;
+        return match RecordId::from_str(&text) {
+            Ok(id) if !is_simple(id.table()) => Ok(id),
+            _ => Err(SlugError::InvalidId(text)),
+        };
+    }
+    let malformed = || SlugError::Malformed(slug.to_owned());
+    let at = slug.find(['.', '~', '-']).ok_or_else(malformed)?;
+    let (table, rest) = slug.split_at(at);
+    if !is_simple(table) {
+        return Err(malformed());
+    }
+    let (separator, key) = rest.split_at(1);
+    match separator {
+        "." if is_integer(key) => key
+            .parse::<i64>()
+            .ok()
+            .filter(|n| n.to_string() == key)
+            .map(|n| RecordId::from_table_key(table, n))
+            .ok_or_else(malformed),
+        "." if is_simple(key) => Ok(RecordId::from_table_key(table, key)),
+        "~" => Ok(RecordId::from_table_key(table, decode_text(key)?)),
+        "-" => {
+            let text = decode_text(key)?;
+            match RecordId::from_str(&format!("{table}:{text}")) {
+                Ok(id) if is_structured(id.key()) => Ok(id),
+                _ => Err(SlugError::InvalidId(text)),
+            }
+        }
+        _ => Err(malformed()),
+    }
+}
+
+fn decode_text(encoded: &str) -> Result<String, SlugError> {
+    URL_SAFE_NO_PAD
+        .decode(encoded)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(|| SlugError::InvalidEncoding(encoded.to_owned()))
+}
+
+/// Returns `true` for non-empty text made only of ASCII letters, digits and `_`.
+fn is_simple(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+}
+
+/// Returns `true` for the key kinds written with the `-` separator.
+fn is_structured(key: &RecordIdKey) -> bool {
+    matches!(
+        key.into_inner_ref(),
+        Id::Uuid(_) | Id::Array(_) | Id::Object(_)
+    )
+}
+
+/// Returns `true` for text shaped like an integer: digits with an optional leading `-`.
+fn is_integer(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// The reason a string could not be decoded as a slug.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SlugError {
+    /// The input does not have the layout of a slug. Holds the input.
+    Malformed(String),
+    /// An encoded part is not URL-safe base64 of UTF-8 text. Holds the encoded part.
+    InvalidEncoding(String),
+    /// An encoded key or id is not valid SurrealQL, or not of the kind its separator says.
+    /// Holds the decoded text.
+    InvalidId(String),
+}
+
+impl fmt::Display for SlugError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(slug) => write!(f, "{slug:?} is not a record id slug"),
+            Self::InvalidEncoding(part) => {
+                write!(f, "{part:?} is not URL-safe base64 of UTF-8 text")
+            }
+            Self::InvalidId(text) => write!(f, "slug holds an invalid record id {text:?}"),
+        }
+    }
+}
+
+impl std::error::Error for SlugError {}
@@ -0,0 +1,151 @@
+//! Record ids and parameter names written as SurrealQL source text.
+//!
+//! For raw query strings in migration scripts, fixtures and debug output. `RecordId::to_string()`
+//! is meant for display and leaves some tables bare that the query parser reads as something
+//! else, such as keywords (`select:abc`) or names starting with a digit (`1abc:x`).
+//! [`to_surrealql`] escapes those, so its output can be pasted anywhere a value is expected:
+//!
+//! ```
+//! use atopio_extra::to_surrealql;
+//!
+//! let id = surrealdb::RecordId::from_table_key("select", "abc");
+//! assert_eq!(id.to_string(), "select:abc");
+//! assert_eq!(to_surrealql(&id), "`select`:abc");
+//!
+//! let id = surrealdb::RecordId::from_table_key("user", "has space");
+//! assert_eq!(to_surrealql(&id), "user:⟨has space⟩");
+//! ```
+//!
+//! # Escaping rules
+//!
+//! - A table is written bare when it is made of ASCII letters, digits and `_`, starts with a
+//!   letter or `_`, contains a letter, and is neither a SurrealQL keyword nor `NaN` or
+//!   `Infinity` in any case. The names `fn`, `function` and `ml`, which begin function calls, are
+//!   escaped too. Otherwise it is wrapped in backticks, with `` ` `` and `\` escaped by a `\`.
+//! - A string key is written bare under the same conditions, though `fn`, `function` and `ml`
+//!   keys need no escaping. Otherwise it is wrapped in `⟨⟩`, with `⟩` and `\` escaped by a `\`.
+//! - Integer, uuid, array and object keys are written the way `RecordId::to_string()` writes
+//!   them: `42`, `u'...'`, `['a', 1]` and `{ a: 1 }`.
+//!
+//! The rules escape more than the parser strictly needs, so the output does not depend on how
+//! a particular SurrealDB version reads ambiguous text such as `1e10` or `1d`.
+
+use std::fmt;
+use surrealdb::sql::Id;
+use surrealdb::{RecordId, RecordIdKey};
+
+/// Returns `id` as a SurrealQL record id literal, escaped as described in the
+/// [module documentation](self).
+pub fn to_surrealql(id: &RecordId) -> String {
+    format!("{}:{}", EscapeTable(id.table()), key_to_surrealql(id.key()))
+}
+
+/// Returns `table` as it is written before the `:` of a SurrealQL record id literal.
+#[cfg(feature = "client")]
+pub(crate) fn table_to_surrealql(table: &str) -> String {
+    EscapeTable(table).to_string()
+}
+
+/// Returns `key` as it is written after the `:` of a SurrealQL record id literal.
+pub fn key_to_surrealql(key: &RecordIdKey) -> String {
+    match key.into_inner_ref() {
+        Id::String(s) if is_bare(s) => s.clone(),
+        Id::String(s) => format!("⟨{}⟩", escape(s, '⟩')),
+        _ => key.to_string(),
+    }
+}
+
+/// Returns `name` as a query parameter, `$` included, after checking that it can be used as
+/// one.
+///
+/// A parameter name is made of ASCII letters, digits and `_` and does not start with a digit.
+/// The names SurrealDB reserves for the session (`$access`, `$auth`, `$session` and `$token`)
+/// are rejected too, since a bound value would be shadowed by the session's own.
+///
+/// ```
+/// use atopio_extra::to_surrealql_param_name;
+///
+/// assert_eq!(to_surrealql_param_name("owner_id").unwrap(), "$owner_id");
+/// assert!(to_surrealql_param_name("owner-id").is_err());
+/// assert!(to_surrealql_param_name("auth").is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns [`ParamNameError::Invalid`] if `name` is not a valid parameter name and
+/// [`ParamNameError::Protected`] if it is reserved for the session.
+pub fn to_surrealql_param_name(name: &str) -> Result<String, ParamNameError> {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_');
+    if !valid {
+        return Err(ParamNameError::Invalid(name.to_owned()));
+    }
+    if surrealdb::cnf::PROTECTED_PARAM_NAMES.contains(&name) {
+        return Err(ParamNameError::Protected(name.to_owned()));
+    }
+    Ok(format!("${name}"))
+}
+
+/// Writes a table name, wrapped in backticks unless it can be written bare.
+struct EscapeTable<'a>(&'a str);
+
+impl fmt::Display for EscapeTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if is_bare(self.0) && !starts_function_path(self.0) {
+            f.write_str(self.0)
+        } else {
+            write!(f, "`{}`", escape(self.0, '`'))
+        }
+    }
+}
+
+/// Returns `true` for text that needs no escaping as a table name or string key.
+fn is_bare(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && text.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+        && text.bytes().any(|b| b.is_ascii_alphabetic())
+        && !surrealdb::syn::could_be_reserved_keyword(text)
+        && !text.eq_ignore_ascii_case("nan")
+        && !text.eq_ignore_ascii_case("infinity")
+}
+
+/// Returns `true` for the names that begin a function call (`fn::`, `function::` and `ml::`),
+/// which the query parser does not read as a table when followed by `:`.
+fn starts_function_path(table: &str) -> bool {
+    ["fn", "function", "ml"]
+        .iter()
+        .any(|name| table.eq_ignore_ascii_case(name))
+}
+
+fn escape(text: &str, close: char) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c == close || c == '\\' {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// The reason a name cannot be used as a query parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParamNameError {
+    /// The name is empty, starts with a digit or holds characters other than ASCII letters,
+    /// digits and `_`. Holds the name.
+    Invalid(String),
+    /// The name is reserved for the session. Holds the name.
+    Protected(String),
+}
+
+impl fmt::Display for ParamNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(name) => write!(f, "{name:?} is not a valid parameter name"),
+            Self::Protected(name) => write!(f, "${name} is reserved for the session"),
+        }
+    }
+}
+
+impl std::error::Error for ParamNameError {}
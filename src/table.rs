@@ -0,0 +1,178 @@
+use crate::{KeyKind, record_id_full};
+use serde::Deserializer;
+use serde::de::DeserializeSeed;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use surrealdb::RecordId;
+
+/// A SurrealDB table that a Rust type is stored in.
+///
+/// Implement this trait for your model types so helpers that only see the key portion of a
+/// record id (for example `record_id_naked::deserialize_for` or `types::Naked`) can attach the
+/// table again when rebuilding a `surrealdb::RecordId`.
+///
+/// ```
+/// use atopio_extra::Table;
+///
+/// struct User;
+///
+/// impl Table for User {
+///     const NAME: &'static str = "user";
+/// }
+/// ```
+pub trait Table {
+    /// The table name, exactly as it appears before the `:` separator of a record id.
+    const NAME: &'static str;
+
+    /// The kind of key the table's records have, used by
+    /// [`record_id_naked::flexible`](crate::record_id_naked::flexible) to convert incoming
+    /// keys. Defaults to [`KeyKind::String`], the kind of SurrealDB's generated ids.
+    const KEY_KIND: KeyKind = KeyKind::String;
+}
+
+/// Old table names mapped to the names that replaced them, for reading data written before a
+/// table was renamed.
+///
+/// Stored JSON, exports and webhook payloads keep the old names forever. Reading them through
+/// [`record_id_full::with_aliases`](crate::record_id_full::with_aliases), or through
+/// [`seed`](Self::seed), rewrites the table of every id whose table is a known old name. Ids
+/// in other tables are read unchanged, and writing always uses the new names, since the id
+/// itself has been rewritten:
+///
+/// ```
+/// use atopio_extra::TableAliasMap;
+/// use serde::de::DeserializeSeed;
+///
+/// let aliases = TableAliasMap::new().alias("accounts", "organisation");
+///
+/// let mut json = serde_json::Deserializer::from_str(r#""accounts:xyz""#);
+/// let id = aliases.seed().deserialize(&mut json).unwrap();
+/// assert_eq!(id.to_string(), "organisation:xyz");
+/// ```
+///
+/// Aliases are looked up once and not followed further: when a table has been renamed twice,
+/// map both old names directly to the current one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableAliasMap(HashMap<String, String>);
+
+thread_local! {
+    static ACTIVE: RefCell<Option<TableAliasMap>> = const { RefCell::new(None) };
+}
+
+impl TableAliasMap {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an alias from the `old` table name to the `new` one.
+    pub fn alias(mut self, old: impl Into<String>, new: impl Into<String>) -> Self {
+        self.insert(old, new);
+        self
+    }
+
+    /// Adds an alias from the `old` table name to the `new` one, returning the name `old` was
+    /// previously mapped to.
+    pub fn insert(&mut self, old: impl Into<String>, new: impl Into<String>) -> Option<String> {
+        self.0.insert(old.into(), new.into())
+    }
+
+    /// Returns the current name of `table`: its alias if it has one, otherwise `table` itself.
+    pub fn resolve<'a>(&'a self, table: &'a str) -> &'a str {
+        self.0.get(table).map_or(table, String::as_str)
+    }
+
+    /// Returns `id` moved to the current name of its table, with the same key.
+    pub fn rewrite(&self, id: RecordId) -> RecordId {
+        match self.0.get(id.table()) {
+            Some(new) => RecordId::from_table_key(new, id.key().clone()),
+            None => id,
+        }
+    }
+
+    /// Returns a [`DeserializeSeed`] reading a full (`table:key`) id as
+    /// [`record_id_full::deserialize`](crate::record_id_full::deserialize) does, then
+    /// rewriting its table.
+    pub fn seed(&self) -> TableAliasSeed<'_> {
+        TableAliasSeed(self)
+    }
+
+    /// Runs `f` with this map used by
+    /// [`record_id_full::with_aliases`](crate::record_id_full::with_aliases) on the current
+    /// thread.
+    ///
+    /// The previous map, if any, is put back when `f` returns or panics, so calls can be nested.
+    /// Deserialization started on other threads, or moved to another thread by an async
+    /// runtime, does not see the map; use [`seed`](Self::seed) there.
+    ///
+    /// ```
+    /// use atopio_extra::TableAliasMap;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Invoice {
+    ///     #[serde(with = "atopio_extra::record_id_full::with_aliases")]
+    ///     owner: surrealdb::RecordId,
+    /// }
+    ///
+    /// let aliases = TableAliasMap::new().alias("accounts", "organisation");
+    /// let invoice: Invoice = aliases
+    ///     .scope(|| serde_json::from_str(r#"{"owner":"accounts:xyz"}"#))
+    ///     .unwrap();
+    /// assert_eq!(invoice.owner.to_string(), "organisation:xyz");
+    /// ```
+    pub fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<TableAliasMap>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                ACTIVE.with(|active| *active.borrow_mut() = previous);
+            }
+        }
+
+        let previous = ACTIVE.with(|active| active.replace(Some(self.clone())));
+        let _restore = Restore(previous);
+        f()
+    }
+
+    /// Rewrites `id` with the map installed by [`scope`](Self::scope), if any.
+    pub(crate) fn rewrite_in_scope(id: RecordId) -> RecordId {
+        ACTIVE.with(|active| match &*active.borrow() {
+            Some(aliases) => aliases.rewrite(id),
+            None => id,
+        })
+    }
+}
+
+impl From<HashMap<String, String>> for TableAliasMap {
+    fn from(map: HashMap<String, String>) -> Self {
+        Self(map)
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for TableAliasMap {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(old, new)| (old.into(), new.into()))
+                .collect(),
+        )
+    }
+}
+
+/// Reads a full record id and rewrites its table with a [`TableAliasMap`].
+///
+/// Created by [`TableAliasMap::seed`].
+#[derive(Debug, Clone, Copy)]
+pub struct TableAliasSeed<'a>(&'a TableAliasMap);
+
+impl<'de> DeserializeSeed<'de> for TableAliasSeed<'_> {
+    type Value = RecordId;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<RecordId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        record_id_full::deserialize(deserializer).map(|id| self.0.rewrite(id))
+    }
+}
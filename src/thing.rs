@@ -0,0 +1,44 @@
+use std::fmt;
+use surrealdb::RecordId;
+use surrealdb::sql::{Id, Thing};
+
+/// Converts a `surrealdb::sql::Thing` into a `surrealdb::RecordId`.
+///
+/// The key is moved across structurally rather than printed and re-parsed, so array and
+/// object keys survive intact and nothing is lost to escaping.
+///
+/// # Errors
+///
+/// Returns a [`ConvertError`] if the thing does not name a single record, that is if its id is
+/// a generator such as `rand()` or a range such as `1..10`.
+pub fn thing_to_record_id(thing: &Thing) -> Result<RecordId, ConvertError> {
+    match thing.id {
+        Id::Number(_) | Id::String(_) | Id::Uuid(_) | Id::Array(_) | Id::Object(_) => {
+            Ok(RecordId::from_inner(thing.clone()))
+        }
+        _ => Err(ConvertError(thing.to_string())),
+    }
+}
+
+/// Converts a `surrealdb::RecordId` into a `surrealdb::sql::Thing`.
+///
+/// Like [`thing_to_record_id`], this copies the key structurally.
+pub fn record_id_to_thing(id: &RecordId) -> Thing {
+    id.clone().into_inner()
+}
+
+/// The error returned when a `Thing` cannot be represented as a `RecordId`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConvertError(String);
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} does not identify a single record and cannot be converted to a record id",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ConvertError {}
@@ -0,0 +1,73 @@
+//! Serde helpers writing a `surrealdb::sql::Thing` in the full (table:key) form.
+//!
+//! These mirror [`crate::record_id_full`] for code that still stores `Thing` values, and
+//! produce and accept exactly the same strings.
+
+use crate::record_id_full::FullText;
+use crate::thing::record_id_to_thing;
+use serde::{Deserializer, Serialize, Serializer};
+use surrealdb::sql::Thing;
+
+/// Serialize a `surrealdb::sql::Thing` as its full string representation.
+///
+/// This is the same string `record_id_full::serialize` writes for the equivalent
+/// `surrealdb::RecordId`, for example `"user:abc123"`. Formats that are not human readable get
+/// `Thing`'s own encoding, which is the same as `RecordId`'s.
+pub fn serialize<S>(thing: &Thing, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if !serializer.is_human_readable() {
+        return thing.serialize(serializer);
+    }
+    serializer.collect_str(&FullText(thing, &thing.id))
+}
+
+/// Deserialize a full (table:key) string into a `surrealdb::sql::Thing`.
+///
+/// # Errors
+///
+/// Returns a deserialization error if the value is not a string or if the string is not a
+/// valid SurrealDB record id.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Thing, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    crate::record_id_full::deserialize(deserializer).map(|id| record_id_to_thing(&id))
+}
+
+/// `Option<surrealdb::sql::Thing>` support for `#[serde(with = "...")]`.
+///
+/// Add `#[serde(default)]` if the field may be missing from the input entirely.
+pub mod option {
+    use crate::thing::record_id_to_thing;
+    use serde::{Deserializer, Serialize, Serializer};
+    use surrealdb::sql::Thing;
+
+    /// Serialize an `Option<surrealdb::sql::Thing>` in the full (table:key) form, or `null`.
+    pub fn serialize<S>(thing: &Option<Thing>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return thing.serialize(serializer);
+        }
+        match thing {
+            Some(thing) => super::serialize(thing, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize an `Option<surrealdb::sql::Thing>` from the full (table:key) form or `null`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a string or `null`, or if the
+    /// string is not a valid SurrealDB record id.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Thing>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(crate::record_id_full::deserialize_opt(deserializer)?.map(|id| record_id_to_thing(&id)))
+    }
+}
@@ -0,0 +1,58 @@
+//! Serde helpers writing only the key of a `surrealdb::sql::Thing`.
+//!
+//! These mirror [`crate::record_id_naked`] for code that still stores `Thing` values, and
+//! produce and accept exactly the same keys.
+
+use crate::Table;
+use crate::thing::{record_id_to_thing, thing_to_record_id};
+use serde::ser::Error as _;
+use serde::{Deserializer, Serializer};
+use surrealdb::sql::Thing;
+
+/// Serialize the key portion of a `surrealdb::sql::Thing`.
+///
+/// See [`crate::record_id_naked::serialize`].
+///
+/// # Errors
+///
+/// Returns a serialization error if the thing does not identify a single record (see
+/// [`crate::thing::thing_to_record_id`]).
+pub fn serialize<S>(thing: &Thing, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let id = thing_to_record_id(thing).map_err(S::Error::custom)?;
+    crate::record_id_naked::serialize(&id, serializer)
+}
+
+/// Deserialize a `surrealdb::sql::Thing` for a field annotated with this module.
+///
+/// Like [`crate::record_id_naked::deserialize`], this exists so `#[serde(with = "...")]`
+/// compiles and only reads back formats that are not human readable; use
+/// [`deserialize_for`] to read bare keys.
+///
+/// # Errors
+///
+/// Returns the errors of [`crate::record_id_naked::deserialize`].
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Thing, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    crate::record_id_naked::deserialize(deserializer).map(|id| record_id_to_thing(&id))
+}
+
+/// Deserialize a naked key into a `surrealdb::sql::Thing` in the table named by `T`.
+///
+/// See [`crate::record_id_naked::deserialize_for`].
+///
+/// # Errors
+///
+/// Returns a deserialization error if the value is neither a string nor an integer, if the
+/// string is empty, or if an unsigned integer does not fit in an `i64`.
+pub fn deserialize_for<'de, T, D>(deserializer: D) -> Result<Thing, D::Error>
+where
+    T: Table,
+    D: Deserializer<'de>,
+{
+    crate::record_id_naked::deserialize_for::<T, D>(deserializer).map(|id| record_id_to_thing(&id))
+}
@@ -0,0 +1,238 @@
+use crate::{Table, record_id_full};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use surrealdb::{RecordId, RecordIdKey};
+
+/// A `surrealdb::RecordId` that is statically known to belong to the table named by `T`.
+///
+/// The table is checked whenever a `TypedId` is built from an arbitrary record id, either
+/// through `TryFrom<RecordId>` or during deserialization, so a `TypedId<User>` can never hold
+/// a `post` id. By default it serializes in the full (table:key) form like `record_id_full`;
+/// annotate the field with `#[serde(with = "atopio_extra::typed_id::naked")]` to use the naked
+/// form instead.
+///
+/// With the `ts-rs` or `specta` feature it exports to TypeScript as `string`. For specta the
+/// table marker must implement `specta::Type` as well, which deriving it on the marker provides.
+///
+/// ```
+/// use atopio_extra::{Table, TypedId};
+///
+/// struct User;
+///
+/// impl Table for User {
+///     const NAME: &'static str = "user";
+/// }
+///
+/// let id = TypedId::<User>::new("abc123");
+/// assert_eq!(id.to_string(), "user:abc123");
+///
+/// let post: surrealdb::RecordId = "post:abc123".parse().unwrap();
+/// assert!(TypedId::<User>::try_from(post).is_err());
+/// ```
+pub struct TypedId<T> {
+    id: RecordId,
+    table: PhantomData<fn() -> T>,
+}
+
+impl<T: Table> TypedId<T> {
+    /// Creates an id in table `T` with the given key.
+    pub fn new(key: impl Into<RecordIdKey>) -> Self {
+        Self {
+            id: RecordId::from_table_key(T::NAME, key),
+            table: PhantomData,
+        }
+    }
+}
+
+impl<T> TypedId<T> {
+    /// Returns the wrapped record id.
+    pub fn id(&self) -> &RecordId {
+        &self.id
+    }
+
+    /// Returns the key portion of the record id.
+    pub fn key(&self) -> &RecordIdKey {
+        self.id.key()
+    }
+
+    /// Consumes the wrapper and returns the record id.
+    pub fn into_inner(self) -> RecordId {
+        self.id
+    }
+}
+
+impl<T> Clone for TypedId<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            table: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for TypedId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedId").field(&self.id).finish()
+    }
+}
+
+impl<T> fmt::Display for TypedId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.id, f)
+    }
+}
+
+impl<T> PartialEq for TypedId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for TypedId<T> {}
+
+impl<T> Hash for TypedId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<T> AsRef<RecordId> for TypedId<T> {
+    fn as_ref(&self) -> &RecordId {
+        &self.id
+    }
+}
+
+impl<T> From<TypedId<T>> for RecordId {
+    fn from(value: TypedId<T>) -> Self {
+        value.id
+    }
+}
+
+impl<T: Table> TryFrom<RecordId> for TypedId<T> {
+    type Error = TableMismatch;
+
+    fn try_from(id: RecordId) -> Result<Self, Self::Error> {
+        if id.table() != T::NAME {
+            return Err(TableMismatch {
+                expected: T::NAME,
+                found: id.table().to_owned(),
+            });
+        }
+        Ok(Self {
+            id,
+            table: PhantomData,
+        })
+    }
+}
+
+impl<T> Serialize for TypedId<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        record_id_full::serialize(&self.id, serializer)
+    }
+}
+
+impl<'de, T: Table> Deserialize<'de> for TypedId<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let id = record_id_full::deserialize(deserializer)?;
+        Self::try_from(id).map_err(D::Error::custom)
+    }
+}
+
+/// The error returned when a record id does not belong to the expected table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableMismatch {
+    /// The table the id was required to be in.
+    pub expected: &'static str,
+    /// The table the id was actually in.
+    pub found: String,
+}
+
+impl fmt::Display for TableMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected table \"{}\", got \"{}\"",
+            self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for TableMismatch {}
+
+/// Serde helpers writing a `TypedId<T>` in the full (table:key) form.
+///
+/// This is the same format as the default `Serialize`/`Deserialize` implementations and is
+/// provided so the choice can be spelled out on the field.
+pub mod full {
+    use super::TypedId;
+    use crate::Table;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serialize a `TypedId<T>` as its full (table:key) string.
+    pub fn serialize<T, S>(id: &TypedId<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        id.serialize(serializer)
+    }
+
+    /// Deserialize a `TypedId<T>` from its full (table:key) string.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a valid record id or if its table
+    /// is not `T::NAME`.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<TypedId<T>, D::Error>
+    where
+        T: Table,
+        D: Deserializer<'de>,
+    {
+        TypedId::deserialize(deserializer)
+    }
+}
+
+/// Serde helpers writing a `TypedId<T>` as its naked key.
+///
+/// The table is implied by `T`, so unlike `record_id_naked` this module round-trips bare keys
+/// without any extra configuration.
+pub mod naked {
+    use super::TypedId;
+    use crate::{Table, record_id_naked};
+    use serde::{Deserializer, Serializer};
+    use std::marker::PhantomData;
+
+    /// Serialize only the key portion of a `TypedId<T>`.
+    pub fn serialize<T, S>(id: &TypedId<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        record_id_naked::serialize(&id.id, serializer)
+    }
+
+    /// Deserialize a naked key into a `TypedId<T>`.
+    ///
+    /// The key is read like `record_id_naked::deserialize_for`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the value is not a valid key.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<TypedId<T>, D::Error>
+    where
+        T: Table,
+        D: Deserializer<'de>,
+    {
+        Ok(TypedId {
+            id: record_id_naked::deserialize_for::<T, D>(deserializer)?,
+            table: PhantomData,
+        })
+    }
+}
@@ -1,7 +1,26 @@
-use serde::{Deserialize, Serialize};
+use crate::claims_builder::SurrealJWTClaimsBuilder;
+use crate::clock::{Clock, SystemClock};
+use crate::jwt::{self, CLAIMS, MissingClaims, TokenKind};
+use crate::parse::{RecordIdParseError, RecordIdParts, validate_record_id_str};
+use crate::typed_id::{TableMismatch, TypedId};
+use crate::validation::{ExpectationError, Expectations};
+use crate::{Table, record_id_full, record_id_naked};
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{self, DeserializeOwned, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::fmt;
+use std::marker::PhantomData;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use surrealdb::{RecordId, RecordIdKey};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(bound(deserialize = "T: Deserialize<'de>", serialize = "T: Serialize"))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(
+    bound(deserialize = "T: Deserialize<'de>", serialize = "T: Serialize"),
+    try_from = "ClaimsObject<String, T>"
+)]
 /// Represents a set of JWT-like claims used by SurrealDB for authentication and authorization.
 ///
 /// - `iat`: Issued At — Unix timestamp (seconds) when the token was created.
@@ -14,9 +33,13 @@ use serde::{Deserialize, Serialize};
 /// - `ac` (serialized as "AC"): Access Claims — Generic payload containing permissions/roles; type `T` allows flexibility
 ///   (for example, a list of permissions, a map of scopes, or a custom claims struct).
 /// - `id` (serialized as "ID"): Subject Identifier — Identifier of the subject (user or service) the token represents.
+/// - `rl` (serialized as "RL"): Roles — Roles of a system user, such as `["OWNER"]`; absent for record users.
+/// - `aud`: Audience — Recipients the token is intended for, as an [`Audience`]; absent unless the issuer sets it.
 ///
 /// All timestamps are expected to be seconds since the Unix epoch. The `NS`, `DB`, `AC`, and `ID` serde renames
-/// ensure compatibility with SurrealDB's expected JSON field names.
+/// ensure compatibility with SurrealDB's expected JSON field names. Those four claims are also read in lowercase, as
+/// some identity providers write them, but are always written in uppercase. If a payload has both casings of a
+/// claim, the uppercase one is used.
 pub struct SurrealJWTClaims<T> {
     pub iat: u64,
     pub nbf: u64,
@@ -31,4 +54,1202 @@ pub struct SurrealJWTClaims<T> {
     pub ac: T,
     #[serde(rename = "ID")]
     pub id: String,
+    #[serde(rename = "RL", default, skip_serializing_if = "Option::is_none")]
+    pub rl: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<Audience>,
+}
+
+impl<T> SurrealJWTClaims<T> {
+    /// Starts building claims, with the times worked out from a time to live. See
+    /// [`SurrealJWTClaimsBuilder`].
+    pub fn builder() -> SurrealJWTClaimsBuilder<T> {
+        SurrealJWTClaimsBuilder::new()
+    }
+
+    /// Returns the level the token grants access at. See [`token_kind`](crate::token_kind) for
+    /// how it is worked out; `AC` always counts as present here, so an empty `NS`, `DB` or `ID`
+    /// is what tells the levels apart.
+    pub fn kind(&self) -> TokenKind {
+        jwt::kind(
+            Some(&self.ns),
+            Some(&self.db),
+            true,
+            Some(&self.id),
+            self.rl.is_some(),
+        )
+    }
+
+    /// Returns whether the token has expired, that is whether `exp` is now or in the past.
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(SystemClock.now())
+    }
+
+    /// Returns whether the token has expired at `now`. An `exp` of 0 is the epoch, so such a
+    /// token has always expired.
+    pub fn is_expired_at(&self, now: SystemTime) -> bool {
+        since_epoch(now) >= Duration::from_secs(self.exp)
+    }
+
+    /// Returns the time left until the token expires, or `None` if it has expired.
+    pub fn expires_in(&self) -> Option<Duration> {
+        self.expires_in_at(SystemClock.now())
+    }
+
+    /// Returns the time left at `now` until the token expires, or `None` if it has expired.
+    pub fn expires_in_at(&self, now: SystemTime) -> Option<Duration> {
+        Duration::from_secs(self.exp)
+            .checked_sub(since_epoch(now))
+            .filter(|left| !left.is_zero())
+    }
+
+    /// Returns how long ago the token was issued, or zero if `iat` is in the future.
+    pub fn issued_ago(&self) -> Duration {
+        self.issued_ago_at(SystemClock.now())
+    }
+
+    /// Returns how long before `now` the token was issued, or zero if `iat` is after `now`.
+    pub fn issued_ago_at(&self, now: SystemTime) -> Duration {
+        since_epoch(now).saturating_sub(Duration::from_secs(self.iat))
+    }
+
+    /// Returns whether the token is not valid yet, that is whether `nbf` is in the future.
+    pub fn not_yet_valid(&self) -> bool {
+        self.not_yet_valid_at(SystemClock.now())
+    }
+
+    /// Returns whether the token is not valid yet at `now`.
+    pub fn not_yet_valid_at(&self, now: SystemTime) -> bool {
+        since_epoch(now) < Duration::from_secs(self.nbf)
+    }
+
+    /// Checks the `iss`, `NS`, `DB` and `ID` claims against `expectations`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ExpectationError`] for the first claim, in that order, that does not hold
+    /// the expected value, or for an `ID` that is not a record id in the expected table.
+    pub fn validate_expectations(
+        &self,
+        expectations: &Expectations,
+    ) -> Result<(), ExpectationError> {
+        expectations.check(&self.iss, &self.ns, &self.db, &self.id)
+    }
+
+    /// Returns the roles in the `RL` claim, or an empty slice if the token has none.
+    pub fn roles(&self) -> &[String] {
+        self.rl.as_deref().unwrap_or_default()
+    }
+
+    /// Returns whether the `RL` claim holds `role`. Roles are compared ignoring ASCII case, as
+    /// SurrealDB does, so `has_role("Owner")` matches the `OWNER` it writes.
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles().iter().any(|r| r.eq_ignore_ascii_case(role))
+    }
+
+    /// Parses the `ID` claim of a record token, such as `user:abc123`, into a record id.
+    ///
+    /// ```
+    /// use atopio_extra::RecordIdParseError;
+    /// use atopio_extra::types::SurrealJWTClaims;
+    ///
+    /// let claims: SurrealJWTClaims<String> = serde_json::from_str(
+    ///     r#"{"iat":1,"nbf":1,"exp":2,"iss":"SurrealDB","jti":"j","NS":"app","DB":"main","AC":"user","ID":"user:abc123"}"#,
+    /// ).unwrap();
+    /// assert_eq!(claims.subject_record_id().unwrap().to_string(), "user:abc123");
+    /// assert_eq!(claims.subject_table().unwrap(), "user");
+    ///
+    /// let root: SurrealJWTClaims<String> = serde_json::from_str(
+    ///     r#"{"iat":1,"nbf":1,"exp":2,"iss":"SurrealDB","jti":"j","NS":"","DB":"","AC":"","ID":"root","RL":["Owner"]}"#,
+    /// ).unwrap();
+    /// assert_eq!(root.subject_record_id(), Err(RecordIdParseError::MissingTable));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`RecordIdParseError`] of [`validate_record_id_str`] if the `ID` is not a
+    /// record id. That is [`RecordIdParseError::MissingTable`] for system tokens, whose `ID`
+    /// is empty or a user name such as `root`.
+    pub fn subject_record_id(&self) -> Result<RecordId, RecordIdParseError> {
+        validate_record_id_str(&self.id).map(RecordIdParts::into_record_id)
+    }
+
+    /// Returns the table of the record the token is for.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`subject_record_id`](Self::subject_record_id).
+    pub fn subject_table(&self) -> Result<String, RecordIdParseError> {
+        validate_record_id_str(&self.id).map(|parts| parts.table().to_owned())
+    }
+
+    /// Returns the key of the record the token is for.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`subject_record_id`](Self::subject_record_id).
+    pub fn subject_key(&self) -> Result<RecordIdKey, RecordIdParseError> {
+        self.subject_record_id().map(|id| id.key().clone())
+    }
+
+    /// Parses the `ID` claim into an id of table `R`, such as `TypedId<User>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SubjectError::Invalid`] if the `ID` is not a record id, and
+    /// [`SubjectError::WrongTable`] if it is one in another table.
+    pub fn subject_typed<R: Table>(&self) -> Result<TypedId<R>, SubjectError> {
+        Ok(TypedId::try_from(self.subject_record_id()?)?)
+    }
+
+    /// Returns whether `other` is for the same principal with the same access: every claim
+    /// but `iat`, `nbf`, `exp` and `jti` is equal. Two tokens issued to the same user at
+    /// different times compare equal here, but not with `==`.
+    pub fn claims_eq_ignoring_times(&self, other: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iss == other.iss
+            && self.ns == other.ns
+            && self.db == other.db
+            && self.ac == other.ac
+            && self.id == other.id
+            && self.rl == other.rl
+            && self.aud == other.aud
+    }
+
+    /// Converts the `AC` claim with `f`, moving every other claim unchanged.
+    pub fn map_ac<U>(self, f: impl FnOnce(T) -> U) -> SurrealJWTClaims<U> {
+        let Ok(claims) = self.try_map_ac(|ac| Ok::<_, Infallible>(f(ac)));
+        claims
+    }
+
+    /// Converts the `AC` claim with `f`, moving every other claim unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `f`.
+    pub fn try_map_ac<U, E>(
+        self,
+        f: impl FnOnce(T) -> Result<U, E>,
+    ) -> Result<SurrealJWTClaims<U>, E> {
+        Ok(SurrealJWTClaims {
+            iat: self.iat,
+            nbf: self.nbf,
+            exp: self.exp,
+            iss: self.iss,
+            jti: self.jti,
+            ns: self.ns,
+            db: self.db,
+            ac: f(self.ac)?,
+            id: self.id,
+            rl: self.rl,
+            aud: self.aud,
+        })
+    }
+}
+
+impl SurrealJWTClaims<serde_json::Value> {
+    /// Reads the `AC` claim of claims decoded with `T = serde_json::Value` into `U`, without
+    /// decoding the token again:
+    ///
+    /// ```
+    /// use atopio_extra::types::SurrealJWTClaims;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Access {
+    ///     plan: String,
+    /// }
+    ///
+    /// let claims: SurrealJWTClaims<serde_json::Value> = serde_json::from_str(
+    ///     r#"{"iat":1,"nbf":1,"exp":2,"iss":"SurrealDB","jti":"j","NS":"app","DB":"main","AC":{"plan":"pro"},"ID":"user:1"}"#,
+    /// ).unwrap();
+    /// let claims = claims.parse_ac::<Access>().unwrap();
+    /// assert_eq!(claims.ac.plan, "pro");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `serde_json` if `AC` is not a valid `U`.
+    pub fn parse_ac<U: DeserializeOwned>(self) -> Result<SurrealJWTClaims<U>, serde_json::Error> {
+        self.try_map_ac(serde_json::from_value)
+    }
+}
+
+/// The reason the `ID` claim is not an id of the expected table, returned by
+/// [`SurrealJWTClaims::subject_typed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubjectError {
+    /// The `ID` is not a record id.
+    Invalid(RecordIdParseError),
+    /// The `ID` is a record id in another table.
+    WrongTable(TableMismatch),
+}
+
+impl From<RecordIdParseError> for SubjectError {
+    fn from(e: RecordIdParseError) -> Self {
+        Self::Invalid(e)
+    }
+}
+
+impl From<TableMismatch> for SubjectError {
+    fn from(e: TableMismatch) -> Self {
+        Self::WrongTable(e)
+    }
+}
+
+impl fmt::Display for SubjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(_) => f.write_str("the `ID` claim is not a record id"),
+            Self::WrongTable(e) => {
+                write!(f, "the `ID` claim is not in table {:?}", e.expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SubjectError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(e) => Some(e),
+            Self::WrongTable(e) => Some(e),
+        }
+    }
+}
+
+/// Returns the time from the Unix epoch to `time`, or zero for a time before it.
+///
+/// Claims are compared against this rather than converted into a `SystemTime`, which cannot
+/// hold every `u64` of seconds, so that no timestamp can overflow.
+pub(crate) fn since_epoch(time: SystemTime) -> Duration {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+/// The `aud` claim, which RFC 7519 allows as either a single string or an array of strings.
+///
+/// Both shapes deserialize, and serialization writes back the shape that was read, so a
+/// re-encoded payload matches the original:
+///
+/// ```
+/// use atopio_extra::types::Audience;
+///
+/// let one: Audience = serde_json::from_str(r#""api""#).unwrap();
+/// assert_eq!(one, Audience::One("api".into()));
+/// assert!(one.contains("api"));
+///
+/// let many: Audience = serde_json::from_str(r#"["api","admin"]"#).unwrap();
+/// assert!(many.contains("admin"));
+/// assert_eq!(serde_json::to_string(&many).unwrap(), r#"["api","admin"]"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Audience {
+    /// Returns whether `audience` is one of the recipients. Audiences are compared exactly.
+    pub fn contains(&self, audience: &str) -> bool {
+        self.as_slice().iter().any(|a| a == audience)
+    }
+
+    /// Returns the recipients as a slice, whichever shape the claim had.
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            Self::One(audience) => std::slice::from_ref(audience),
+            Self::Many(audiences) => audiences,
+        }
+    }
+}
+
+impl From<String> for Audience {
+    fn from(value: String) -> Self {
+        Self::One(value)
+    }
+}
+
+impl From<&str> for Audience {
+    fn from(value: &str) -> Self {
+        Self::One(value.to_owned())
+    }
+}
+
+impl From<Vec<String>> for Audience {
+    fn from(value: Vec<String>) -> Self {
+        Self::Many(value)
+    }
+}
+
+impl Serialize for Audience {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::One(audience) => serializer.serialize_str(audience),
+            Self::Many(audiences) => audiences.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Audience {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(AudienceVisitor)
+    }
+}
+
+struct AudienceVisitor;
+
+impl<'de> Visitor<'de> for AudienceVisitor {
+    type Value = Audience;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string or an array of strings")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Audience::One(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Audience::One(v))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut audiences = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(64));
+        while let Some(audience) = seq.next_element::<String>()? {
+            audiences.push(audience);
+        }
+        Ok(Audience::Many(audiences))
+    }
+}
+
+/// [`SurrealJWTClaims`] along with every other claim in the payload.
+///
+/// Claims that [`SurrealJWTClaims`] has no field for, such as `tenant` or `plan`, are kept in
+/// `extra` on decode and written back after the Surreal claims on serialize, so one decode reads
+/// both. The JSON shape is that of the payload itself:
+///
+/// ```
+/// use atopio_extra::types::SurrealJWTClaimsExt;
+///
+/// let json = r#"{"iat":1,"nbf":1,"exp":2,"iss":"SurrealDB","jti":"j","NS":"app","DB":"main","AC":"user","ID":"user:1","tenant":"acme"}"#;
+/// let claims: SurrealJWTClaimsExt<String> = serde_json::from_str(json).unwrap();
+/// assert_eq!(claims.claims.ns, "app");
+/// assert_eq!(claims.extra["tenant"], "acme");
+/// assert_eq!(serde_json::to_string(&claims).unwrap(), json);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>", serialize = "T: Serialize"))]
+pub struct SurrealJWTClaimsExt<T> {
+    #[serde(flatten)]
+    pub claims: SurrealJWTClaims<T>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl<T> From<SurrealJWTClaims<T>> for SurrealJWTClaimsExt<T> {
+    fn from(claims: SurrealJWTClaims<T>) -> Self {
+        Self {
+            claims,
+            extra: serde_json::Map::new(),
+        }
+    }
+}
+
+impl<T> From<SurrealJWTClaimsExt<T>> for SurrealJWTClaims<T> {
+    fn from(claims: SurrealJWTClaimsExt<T>) -> Self {
+        claims.claims
+    }
+}
+
+/// [`SurrealJWTClaims`] with every claim optional.
+///
+/// Tokens from other issuers often leave out `jti`, `nbf` or even `iss`, which makes decoding
+/// them as [`SurrealJWTClaims`] fail. This shape reads whatever claims are present, under the
+/// same JSON names, and [`try_into_full`](Self::try_into_full) checks for the rest later.
+/// Absent claims are also left out when serializing. Lowercase `ns`, `db`, `ac` and `id` are
+/// read as in [`SurrealJWTClaims`].
+///
+/// ```
+/// use atopio_extra::types::SurrealJWTClaimsPartial;
+///
+/// let claims: SurrealJWTClaimsPartial<()> =
+///     serde_json::from_str(r#"{"exp":2,"NS":"app","DB":"main","ID":"user:1"}"#).unwrap();
+/// assert_eq!(claims.ns.as_deref(), Some("app"));
+///
+/// let missing = claims.try_into_full().unwrap_err();
+/// assert_eq!(missing.claims(), ["iat", "nbf", "iss", "jti", "AC"]);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    bound(deserialize = "T: Deserialize<'de>", serialize = "T: Serialize"),
+    from = "ClaimsObject<String, T>"
+)]
+pub struct SurrealJWTClaimsPartial<T> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+    #[serde(rename = "NS", default, skip_serializing_if = "Option::is_none")]
+    pub ns: Option<String>,
+    #[serde(rename = "DB", default, skip_serializing_if = "Option::is_none")]
+    pub db: Option<String>,
+    #[serde(rename = "AC", default, skip_serializing_if = "Option::is_none")]
+    pub ac: Option<T>,
+    #[serde(rename = "ID", default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "RL", default, skip_serializing_if = "Option::is_none")]
+    pub rl: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<Audience>,
+}
+
+impl<T> Default for SurrealJWTClaimsPartial<T> {
+    fn default() -> Self {
+        Self {
+            iat: None,
+            nbf: None,
+            exp: None,
+            iss: None,
+            jti: None,
+            ns: None,
+            db: None,
+            ac: None,
+            id: None,
+            rl: None,
+            aud: None,
+        }
+    }
+}
+
+impl<T> SurrealJWTClaimsPartial<T> {
+    /// Converts the claims into [`SurrealJWTClaims`] if every claim is present. `RL` and `aud`
+    /// are optional in both shapes, so they are never reported missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MissingClaims`] naming every absent claim by its JSON name, in the order of
+    /// the fields.
+    pub fn try_into_full(self) -> Result<SurrealJWTClaims<T>, MissingClaims> {
+        match self {
+            Self {
+                iat: Some(iat),
+                nbf: Some(nbf),
+                exp: Some(exp),
+                iss: Some(iss),
+                jti: Some(jti),
+                ns: Some(ns),
+                db: Some(db),
+                ac: Some(ac),
+                id: Some(id),
+                rl,
+                aud,
+            } => Ok(SurrealJWTClaims {
+                iat,
+                nbf,
+                exp,
+                iss,
+                jti,
+                ns,
+                db,
+                ac,
+                id,
+                rl,
+                aud,
+            }),
+            partial => {
+                let present = [
+                    partial.iat.is_some(),
+                    partial.nbf.is_some(),
+                    partial.exp.is_some(),
+                    partial.iss.is_some(),
+                    partial.jti.is_some(),
+                    partial.ns.is_some(),
+                    partial.db.is_some(),
+                    partial.ac.is_some(),
+                    partial.id.is_some(),
+                ];
+                let claims = CLAIMS
+                    .into_iter()
+                    .zip(present)
+                    .filter_map(|(claim, present)| (!present).then_some(claim))
+                    .collect();
+                Err(MissingClaims(claims))
+            }
+        }
+    }
+}
+
+impl<T> From<SurrealJWTClaims<T>> for SurrealJWTClaimsPartial<T> {
+    fn from(claims: SurrealJWTClaims<T>) -> Self {
+        Self {
+            iat: Some(claims.iat),
+            nbf: Some(claims.nbf),
+            exp: Some(claims.exp),
+            iss: Some(claims.iss),
+            jti: Some(claims.jti),
+            ns: Some(claims.ns),
+            db: Some(claims.db),
+            ac: Some(claims.ac),
+            id: Some(claims.id),
+            rl: claims.rl,
+            aud: claims.aud,
+        }
+    }
+}
+
+/// Claims of a token minted by SurrealDB 1.x.
+///
+/// SurrealDB 1.x names the scope a record user signed in to in `SC`, and the `DEFINE TOKEN`
+/// that verified a custom token in `TK`, where 2.x has a single `AC` claim for the access
+/// method. `ID` is only set for record users, and system users get no `NS` or `DB`, so every
+/// claim is optional. Decode with
+/// [`decode_custom_payload_insecurely`](crate::decode_custom_payload_insecurely), then check
+/// [`is_legacy`](Self::is_legacy) and convert:
+///
+/// ```
+/// use atopio_extra::types::{SurrealJWTClaimsPartial, SurrealJWTClaimsV1};
+///
+/// let claims: SurrealJWTClaimsV1 =
+///     serde_json::from_str(r#"{"exp":2,"NS":"app","DB":"main","SC":"user","ID":"user:1"}"#).unwrap();
+/// assert!(claims.is_legacy());
+///
+/// let current = SurrealJWTClaimsPartial::from(claims);
+/// assert_eq!(current.ac.as_deref(), Some("user"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SurrealJWTClaimsV1 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+    #[serde(rename = "NS", default, skip_serializing_if = "Option::is_none")]
+    pub ns: Option<String>,
+    #[serde(rename = "DB", default, skip_serializing_if = "Option::is_none")]
+    pub db: Option<String>,
+    #[serde(rename = "SC", default, skip_serializing_if = "Option::is_none")]
+    pub sc: Option<String>,
+    #[serde(rename = "TK", default, skip_serializing_if = "Option::is_none")]
+    pub tk: Option<String>,
+    #[serde(rename = "ID", default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+impl SurrealJWTClaimsV1 {
+    /// Returns whether the token has an `SC` or `TK` claim, which only SurrealDB 1.x writes.
+    ///
+    /// A 2.x token decoded as this type has neither, since its `AC` claim is not read.
+    pub fn is_legacy(&self) -> bool {
+        self.sc.is_some() || self.tk.is_some()
+    }
+}
+
+/// Maps the claims onto the 2.x names. `AC` is taken from `SC`, or from `TK` for a custom
+/// token with no scope, since both named what 2.x calls the access method.
+impl From<SurrealJWTClaimsV1> for SurrealJWTClaimsPartial<String> {
+    fn from(claims: SurrealJWTClaimsV1) -> Self {
+        Self {
+            iat: claims.iat,
+            nbf: claims.nbf,
+            exp: claims.exp,
+            iss: claims.iss,
+            jti: claims.jti,
+            ns: claims.ns,
+            db: claims.db,
+            ac: claims.sc.or(claims.tk),
+            id: claims.id,
+            rl: None,
+            aud: None,
+        }
+    }
+}
+
+/// [`SurrealJWTClaims`] borrowing its strings from the decoded payload.
+///
+/// Returned by [`decode_payload_borrowed`](crate::decode_payload_borrowed), which decodes into
+/// a buffer the caller keeps and reuses, so the string claims are not copied. A string is only
+/// copied if the payload escapes it, for example a `\/` in a JSON string. `RL` and `aud` are
+/// still read into owned values when present, and `T` allocates as it otherwise would. The
+/// fields and their JSON names are those of [`SurrealJWTClaims`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    bound(
+        deserialize = "T: Deserialize<'de>, 'de: 'a",
+        serialize = "T: Serialize"
+    ),
+    try_from = "ClaimsObject<Borrowed<'a>, T>"
+)]
+pub struct SurrealJWTClaimsRef<'a, T> {
+    pub iat: u64,
+    pub nbf: u64,
+    pub exp: u64,
+    pub iss: Cow<'a, str>,
+    pub jti: Cow<'a, str>,
+    #[serde(rename = "NS")]
+    pub ns: Cow<'a, str>,
+    #[serde(rename = "DB")]
+    pub db: Cow<'a, str>,
+    #[serde(rename = "AC")]
+    pub ac: T,
+    #[serde(rename = "ID")]
+    pub id: Cow<'a, str>,
+    #[serde(rename = "RL", default, skip_serializing_if = "Option::is_none")]
+    pub rl: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<Audience>,
+}
+
+impl<T: Clone> SurrealJWTClaimsRef<'_, T> {
+    /// Copies the claims into an owned [`SurrealJWTClaims`].
+    pub fn to_claims(&self) -> SurrealJWTClaims<T> {
+        self.clone().into_owned()
+    }
+}
+
+impl<T> SurrealJWTClaimsRef<'_, T> {
+    /// Converts the claims into an owned [`SurrealJWTClaims`], copying only the strings that
+    /// are still borrowed.
+    pub fn into_owned(self) -> SurrealJWTClaims<T> {
+        SurrealJWTClaims {
+            iat: self.iat,
+            nbf: self.nbf,
+            exp: self.exp,
+            iss: self.iss.into_owned(),
+            jti: self.jti.into_owned(),
+            ns: self.ns.into_owned(),
+            db: self.db.into_owned(),
+            ac: self.ac,
+            id: self.id.into_owned(),
+            rl: self.rl,
+            aud: self.aud,
+        }
+    }
+}
+
+/// The claims as read from a payload, before the uppercase and lowercase spellings of `NS`, `DB`,
+/// `AC` and `ID` are merged. `S` is `String`, or [`Borrowed`] for [`SurrealJWTClaimsRef`].
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "S: Deserialize<'de>, T: Deserialize<'de>"))]
+struct CasedClaims<S, T> {
+    #[serde(default)]
+    iat: Option<u64>,
+    #[serde(default)]
+    nbf: Option<u64>,
+    #[serde(default)]
+    exp: Option<u64>,
+    #[serde(default)]
+    iss: Option<S>,
+    #[serde(default)]
+    jti: Option<S>,
+    #[serde(rename = "NS", default)]
+    ns: Option<S>,
+    #[serde(rename = "ns", default)]
+    ns_lower: Option<S>,
+    #[serde(rename = "DB", default)]
+    db: Option<S>,
+    #[serde(rename = "db", default)]
+    db_lower: Option<S>,
+    #[serde(rename = "AC", default, deserialize_with = "present")]
+    ac: Option<T>,
+    #[serde(rename = "ac", default, deserialize_with = "present")]
+    ac_lower: Option<T>,
+    #[serde(rename = "ID", default)]
+    id: Option<S>,
+    #[serde(rename = "id", default)]
+    id_lower: Option<S>,
+    #[serde(rename = "RL", default)]
+    rl: Option<Vec<String>>,
+    #[serde(default)]
+    aud: Option<Audience>,
+}
+
+/// [`CasedClaims`] read only from a map, as the derived claims structs were. A derived
+/// `Deserialize` also accepts an array of the fields in order, which would let a payload such
+/// as `[1]` through to a missing claim error.
+///
+/// `absent_ac` is `AC` as a derived `Deserialize` reads it when the payload has neither
+/// casing, such as `None` for an `Option`, and `None` for a `T` that cannot be missing.
+pub(crate) struct ClaimsObject<S, T> {
+    claims: CasedClaims<S, T>,
+    absent_ac: Option<T>,
+}
+
+/// The JSON names of the fields of [`CasedClaims`].
+const CASED_CLAIMS: &[&str] = &[
+    "iat", "nbf", "exp", "iss", "jti", "NS", "ns", "DB", "db", "AC", "ac", "ID", "id", "RL", "aud",
+];
+
+impl<'de, S, T> Deserialize<'de> for ClaimsObject<S, T>
+where
+    S: Deserialize<'de>,
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "SurrealJWTClaims",
+            CASED_CLAIMS,
+            ClaimsObjectVisitor(PhantomData),
+        )
+    }
+}
+
+struct ClaimsObjectVisitor<S, T>(PhantomData<fn() -> (S, T)>);
+
+impl<'de, S, T> Visitor<'de> for ClaimsObjectVisitor<S, T>
+where
+    S: Deserialize<'de>,
+    T: Deserialize<'de>,
+{
+    type Value = ClaimsObject<S, T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an object of claims")
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let claims = CasedClaims::deserialize(MapAccessDeserializer::new(map))?;
+        let absent_ac = match (&claims.ac, &claims.ac_lower) {
+            (None, None) => T::deserialize(AbsentField::<A::Error>::new("AC")).ok(),
+            _ => None,
+        };
+        Ok(ClaimsObject { claims, absent_ac })
+    }
+}
+
+/// A deserializer for a field the payload lacks, which `Option` reads as `None` and any other
+/// type fails on with serde's missing field error, as in a derived `Deserialize`.
+struct AbsentField<E> {
+    name: &'static str,
+    error: PhantomData<E>,
+}
+
+impl<E> AbsentField<E> {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<'de, E: de::Error> Deserializer<'de> for AbsentField<E> {
+    type Error = E;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        Err(E::missing_field(self.name))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_none()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+/// Reads a claim that is present as `Some`, even if its value is `null`, so that an `AC` of
+/// `null` is not taken for a missing one.
+fn present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// A claim required by [`SurrealJWTClaims`] that the payload lacks, reported in the words of
+/// serde's own missing field error. [`decode_claims`](crate::jwt::decode_claims) turns it into
+/// [`JwtError::MissingClaim`](crate::JwtError).
+#[derive(Debug)]
+pub(crate) struct MissingField(pub(crate) &'static str);
+
+impl fmt::Display for MissingField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing field `{}`", self.0)
+    }
+}
+
+fn require<V>(value: Option<V>, claim: &'static str) -> Result<V, MissingField> {
+    value.ok_or(MissingField(claim))
+}
+
+impl<T> From<ClaimsObject<String, T>> for SurrealJWTClaimsPartial<T> {
+    fn from(object: ClaimsObject<String, T>) -> Self {
+        let claims = object.claims;
+        Self {
+            iat: claims.iat,
+            nbf: claims.nbf,
+            exp: claims.exp,
+            iss: claims.iss,
+            jti: claims.jti,
+            ns: claims.ns.or(claims.ns_lower),
+            db: claims.db.or(claims.db_lower),
+            ac: claims.ac.or(claims.ac_lower),
+            id: claims.id.or(claims.id_lower),
+            rl: claims.rl,
+            aud: claims.aud,
+        }
+    }
+}
+
+impl<T> TryFrom<ClaimsObject<String, T>> for SurrealJWTClaims<T> {
+    type Error = MissingField;
+
+    fn try_from(mut object: ClaimsObject<String, T>) -> Result<Self, Self::Error> {
+        let absent_ac = object.absent_ac.take();
+        let mut claims = SurrealJWTClaimsPartial::from(object);
+        claims.ac = claims.ac.or(absent_ac);
+        claims
+            .try_into_full()
+            .map_err(|missing| MissingField(missing.claims()[0]))
+    }
+}
+
+impl<'a, T> TryFrom<ClaimsObject<Borrowed<'a>, T>> for SurrealJWTClaimsRef<'a, T> {
+    type Error = MissingField;
+
+    fn try_from(object: ClaimsObject<Borrowed<'a>, T>) -> Result<Self, Self::Error> {
+        let claims = object.claims;
+        Ok(Self {
+            iat: require(claims.iat, "iat")?,
+            nbf: require(claims.nbf, "nbf")?,
+            exp: require(claims.exp, "exp")?,
+            iss: require(claims.iss, "iss")?.0,
+            jti: require(claims.jti, "jti")?.0,
+            ns: require(claims.ns.or(claims.ns_lower), "NS")?.0,
+            db: require(claims.db.or(claims.db_lower), "DB")?.0,
+            ac: require(claims.ac.or(claims.ac_lower).or(object.absent_ac), "AC")?,
+            id: require(claims.id.or(claims.id_lower), "ID")?.0,
+            rl: claims.rl,
+            aud: claims.aud,
+        })
+    }
+}
+
+/// A string claim borrowed from the payload where it is not escaped.
+pub(crate) struct Borrowed<'a>(Cow<'a, str>);
+
+impl<'de: 'a, 'a> Deserialize<'de> for Borrowed<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(BorrowedVisitor)
+    }
+}
+
+struct BorrowedVisitor;
+
+impl<'de> Visitor<'de> for BorrowedVisitor {
+    type Value = Borrowed<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Borrowed(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Borrowed(Cow::Owned(v.to_owned())))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Borrowed(Cow::Owned(v)))
+    }
+}
+
+/// A `surrealdb::RecordId` in the table named by `T` that serializes as its naked key.
+///
+/// Serialization emits only the key portion, exactly like `record_id_naked::serialize`, and
+/// deserialization reads a bare key (for example `"abc123"` or `42`) and reattaches
+/// `T::NAME` as the table, like `record_id_naked::deserialize_for`. Use it as a field type when
+/// the naked form has to round-trip:
+///
+/// ```
+/// use atopio_extra::{Table, types::Naked};
+///
+/// struct User;
+///
+/// impl Table for User {
+///     const NAME: &'static str = "user";
+/// }
+///
+/// let id: Naked<User> = serde_json::from_str("\"abc123\"").unwrap();
+/// assert_eq!(id.id().to_string(), "user:abc123");
+/// ```
+pub struct Naked<T> {
+    id: RecordId,
+    table: PhantomData<fn() -> T>,
+}
+
+impl<T: Table> Naked<T> {
+    /// Creates an id in table `T` with the given key.
+    pub fn new(key: impl Into<RecordIdKey>) -> Self {
+        Self {
+            id: RecordId::from_table_key(T::NAME, key),
+            table: PhantomData,
+        }
+    }
+}
+
+impl<T> Naked<T> {
+    /// Returns the wrapped record id.
+    pub fn id(&self) -> &RecordId {
+        &self.id
+    }
+
+    /// Consumes the wrapper and returns the record id.
+    pub fn into_inner(self) -> RecordId {
+        self.id
+    }
+}
+
+impl<T> Clone for Naked<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            table: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Naked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Naked").field(&self.id).finish()
+    }
+}
+
+impl<T> PartialEq for Naked<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for Naked<T> {}
+
+impl<T> From<Naked<T>> for RecordId {
+    fn from(value: Naked<T>) -> Self {
+        value.id
+    }
+}
+
+impl<T> Serialize for Naked<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        record_id_naked::serialize(&self.id, serializer)
+    }
+}
+
+impl<'de, T: Table> Deserialize<'de> for Naked<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self {
+            id: record_id_naked::deserialize_for::<T, D>(deserializer)?,
+            table: PhantomData,
+        })
+    }
+}
+
+/// A `surrealdb::RecordId` that serializes in the full (table:key) form.
+///
+/// The wrapper implements `Hash` and `Eq`, so it can be used as a set element or map key, and
+/// its serde implementation matches `record_id_full`. Pair a `HashSet<FullId>` field with
+/// `record_id_full::set` to get the elements sorted on output.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FullId(pub RecordId);
+
+impl From<RecordId> for FullId {
+    fn from(value: RecordId) -> Self {
+        Self(value)
+    }
+}
+
+impl From<FullId> for RecordId {
+    fn from(value: FullId) -> Self {
+        value.0
+    }
+}
+
+impl AsRef<RecordId> for FullId {
+    fn as_ref(&self) -> &RecordId {
+        &self.0
+    }
+}
+
+impl Serialize for FullId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        record_id_full::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FullId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        record_id_full::deserialize(deserializer).map(Self)
+    }
+}
+
+/// A `surrealdb::RecordId` with a total order, for use as a `BTreeMap`/`BTreeSet` key.
+///
+/// Ids are compared with [`collate`](crate::collate()), in the order SurrealDB sorts them:
+/// by table name first and then by key. Numeric keys compare numerically, so `user:2` sorts
+/// before `user:10`, and string keys compare lexicographically. Serialization matches
+/// `record_id_full`, which also makes the wrapper usable as a JSON map key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OrdId(pub RecordId);
+
+impl Ord for OrdId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        crate::collate(&self.0, &other.0)
+    }
+}
+
+impl PartialOrd for OrdId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<RecordId> for OrdId {
+    fn from(value: RecordId) -> Self {
+        Self(value)
+    }
+}
+
+impl From<OrdId> for RecordId {
+    fn from(value: OrdId) -> Self {
+        value.0
+    }
+}
+
+impl AsRef<RecordId> for OrdId {
+    fn as_ref(&self) -> &RecordId {
+        &self.0
+    }
+}
+
+impl Serialize for OrdId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        record_id_full::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for OrdId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        record_id_full::deserialize(deserializer).map(Self)
+    }
+}
+
+/// A list of record ids to bind as a query parameter, as in `WHERE id IN $ids`.
+///
+/// The ids reach the server as record ids, so they compare equal to the `id` field. Binding
+/// their text instead, for example after reading them through a full-form helper into
+/// strings, matches nothing, since the string `"user:abc"` is not the record `user:abc`.
+///
+/// ```no_run
+/// # async fn run(db: surrealdb::Surreal<surrealdb::engine::any::Any>) -> surrealdb::Result<()> {
+/// use atopio_extra::types::IdsParam;
+///
+/// let ids: Vec<surrealdb::RecordId> = vec!["user:abc".parse()?, ("user", 42).into()];
+/// let mut response = db
+///     .query("SELECT * FROM user WHERE id IN $ids")
+///     .bind(("ids", IdsParam(ids)))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Serialization matches [`record_id_full::vec`]: the binary and in-process protocols, which
+/// are not human readable, get native record ids, and human readable formats such as JSON get
+/// the full (table:key) strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IdsParam(pub Vec<RecordId>);
+
+impl From<Vec<RecordId>> for IdsParam {
+    fn from(value: Vec<RecordId>) -> Self {
+        Self(value)
+    }
+}
+
+impl FromIterator<RecordId> for IdsParam {
+    fn from_iter<I: IntoIterator<Item = RecordId>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Serialize for IdsParam {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        record_id_full::vec::serialize(&self.0, serializer)
+    }
 }
@@ -0,0 +1,114 @@
+//! TypeScript export support for the record id wrapper types, through `ts-rs` and `specta`.
+//!
+//! Every wrapper that serializes as a record id string exports as `string`. A
+//! [`RecordLink<T>`](crate::link::RecordLink) exports as `string | T`, matching its untagged
+//! serialization.
+//!
+//! specta's derive asks every generic argument of a field type for its own type, so a field of
+//! type `TypedId<User>` also needs `User: specta::Type`, usually by deriving it on the marker.
+
+#[cfg(feature = "ts-rs")]
+mod ts_rs_impl {
+    use crate::link::RecordLink;
+    use crate::types::{FullId, Naked, OrdId};
+    use crate::{RecordIdRange, TypedId};
+    use ts_rs::{Config, Dummy, TS, TypeVisitor};
+
+    macro_rules! impl_string {
+        ($([$($param:ident)?] $ty:ty => $without_generics:ty),* $(,)?) => {$(
+            impl$(<$param>)? TS for $ty {
+                type WithoutGenerics = $without_generics;
+                type OptionInnerType = Self;
+
+                fn name(_: &Config) -> String {
+                    "string".to_owned()
+                }
+
+                fn inline(cfg: &Config) -> String {
+                    Self::name(cfg)
+                }
+            }
+        )*};
+    }
+
+    impl_string!(
+        [T] TypedId<T> => TypedId<Dummy>,
+        [T] Naked<T> => Naked<Dummy>,
+        [] FullId => Self,
+        [] OrdId => Self,
+        [] RecordIdRange => Self,
+    );
+
+    impl<T: TS> TS for RecordLink<T> {
+        type WithoutGenerics = RecordLink<Dummy>;
+        type OptionInnerType = Self;
+
+        fn name(cfg: &Config) -> String {
+            format!("string | {}", T::name(cfg))
+        }
+
+        fn inline(cfg: &Config) -> String {
+            format!("string | {}", T::inline(cfg))
+        }
+
+        fn visit_dependencies(v: &mut impl TypeVisitor)
+        where
+            Self: 'static,
+        {
+            T::visit_dependencies(v);
+        }
+
+        fn visit_generics(v: &mut impl TypeVisitor)
+        where
+            Self: 'static,
+        {
+            T::visit_generics(v);
+            v.visit::<T>();
+        }
+    }
+}
+
+#[cfg(feature = "specta")]
+mod specta_impl {
+    use crate::link::RecordLink;
+    use crate::types::{FullId, Naked, OrdId};
+    use crate::{RecordIdRange, TypedId};
+    use specta::{
+        DataType, DefOpts, EnumType, EnumVariant, ExportError, PrimitiveType, TupleType, Type,
+    };
+
+    macro_rules! impl_string {
+        ($([$($param:ident)?] $ty:ty),* $(,)?) => {$(
+            impl$(<$param>)? Type for $ty {
+                fn inline(_: DefOpts, _: &[DataType]) -> Result<DataType, ExportError> {
+                    Ok(DataType::Primitive(PrimitiveType::String))
+                }
+            }
+        )*};
+    }
+
+    impl_string!([T] TypedId<T>, [T] Naked<T>, [] FullId, [] OrdId, [] RecordIdRange);
+
+    fn variant(ty: DataType) -> EnumVariant {
+        EnumVariant::Unnamed(TupleType {
+            fields: vec![ty],
+            generics: vec![],
+        })
+    }
+
+    impl<T: Type> Type for RecordLink<T> {
+        fn inline(opts: DefOpts, generics: &[DataType]) -> Result<DataType, ExportError> {
+            let record = match generics.first() {
+                Some(record) => record.clone(),
+                None => T::reference(opts, generics)?,
+            };
+            Ok(DataType::Enum(EnumType::Untagged {
+                variants: vec![
+                    variant(DataType::Primitive(PrimitiveType::String)),
+                    variant(record),
+                ],
+                generics: vec![],
+            }))
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Time-ordered uuid keys, so a record's id can be known before it is created.
+//!
+//! Enabled by the `uuid` feature. The ids have real uuid keys, written `user:u'0190...'`,
+//! rather than strings holding uuid text. Version 7 uuids start with a millisecond timestamp,
+//! so ids made later sort after ids made earlier:
+//!
+//! ```
+//! let first = atopio_extra::new_uuid_v7_id("event");
+//! let second = atopio_extra::new_uuid_v7_id("event");
+//! assert!(first.key() < second.key());
+//! ```
+
+use crate::{Table, TypedId};
+use std::time::{Duration, SystemTime};
+use surrealdb::{RecordId, RecordIdKey};
+
+/// Creates an id in `table` with a new version 7 uuid key.
+///
+/// Keys made by one process are strictly increasing, even within the same millisecond.
+pub fn new_uuid_v7_id(table: &str) -> RecordId {
+    RecordId::from_table_key(table, new_key())
+}
+
+/// Returns the time embedded in the uuid key of `id`.
+///
+/// This is meant for debugging, such as seeing when an id from [`new_uuid_v7_id`] was made.
+/// Returns `None` if the key is not a uuid or is a uuid version with no timestamp, such as a
+/// random version 4 uuid.
+pub fn key_uuid_timestamp(id: &RecordId) -> Option<SystemTime> {
+    let surrealdb::sql::Id::Uuid(uuid) = id.key().into_inner_ref() else {
+        return None;
+    };
+    let (secs, nanos) = uuid.0.get_timestamp()?.to_unix();
+    SystemTime::UNIX_EPOCH.checked_add(Duration::new(secs, nanos))
+}
+
+impl<T: Table> TypedId<T> {
+    /// Creates an id in table `T` with a new version 7 uuid key.
+    ///
+    /// See [`new_uuid_v7_id`].
+    pub fn new_uuid_v7() -> Self {
+        Self::new(new_key())
+    }
+}
+
+fn new_key() -> RecordIdKey {
+    RecordIdKey::from(uuid::Uuid::now_v7())
+}
@@ -0,0 +1,334 @@
+//! Checking the time, issuer and tenant claims of a token.
+//!
+//! [`Validation`] runs the checks every consumer of the decoded claims otherwise writes by
+//! hand, and [`Expectations`] checks that a token is for this service's namespace and
+//! database. It does not verify the signature, so it only tells whether a token would still be
+//! accepted, not whether it can be trusted.
+
+use crate::RecordIdRedacted;
+use crate::parse::validate_record_id_str;
+use crate::types::{SurrealJWTClaims, SurrealJWTClaimsPartial, since_epoch};
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+/// The checks to run on a token's claims.
+///
+/// Build it up from [`new`](Self::new) and call [`validate`](Self::validate) with the time to
+/// check against:
+///
+/// ```
+/// use atopio_extra::Validation;
+/// use atopio_extra::types::SurrealJWTClaims;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let claims: SurrealJWTClaims<String> = serde_json::from_str(
+///     r#"{"iat":100,"nbf":100,"exp":200,"iss":"SurrealDB","jti":"j","NS":"app","DB":"main","AC":"user","ID":"user:1"}"#,
+/// ).unwrap();
+/// let validation = Validation::new()
+///     .leeway(Duration::from_secs(30))
+///     .require_exp()
+///     .expected_iss("SurrealDB");
+///
+/// assert!(validation.validate(&claims, UNIX_EPOCH + Duration::from_secs(229)).is_ok());
+/// assert!(validation.validate(&claims, UNIX_EPOCH + Duration::from_secs(230)).is_err());
+/// ```
+///
+/// The leeway allows for clock skew between the issuer and this host in both directions: a
+/// token is accepted up to `leeway` after its `exp` and from `leeway` before its `nbf`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validation {
+    leeway: Duration,
+    require_exp: bool,
+    iss: Option<String>,
+}
+
+impl Validation {
+    /// Starts with no leeway, `exp` and `nbf` checked only if present, and any issuer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the clock skew allowed on `exp` and `nbf`.
+    pub fn leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
+    /// Fails tokens without an `exp`. [`SurrealJWTClaims`] always has one, so this only
+    /// matters for [`validate_partial`](Self::validate_partial).
+    pub fn require_exp(mut self) -> Self {
+        self.require_exp = true;
+        self
+    }
+
+    /// Fails tokens whose `iss` is not exactly `iss`, or that have none.
+    pub fn expected_iss(mut self, iss: impl Into<String>) -> Self {
+        self.iss = Some(iss.into());
+        self
+    }
+
+    /// Runs every check on `claims` at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] listing every check that failed.
+    pub fn validate<T>(
+        &self,
+        claims: &SurrealJWTClaims<T>,
+        now: SystemTime,
+    ) -> Result<(), ValidationError> {
+        self.check(Some(claims.exp), Some(claims.nbf), Some(&claims.iss), now)
+    }
+
+    /// Runs every check on claims that may lack some of them, at `now`. An absent `nbf` passes,
+    /// as does an absent `exp` unless [`require_exp`](Self::require_exp) was set.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] listing every check that failed.
+    pub fn validate_partial<T>(
+        &self,
+        claims: &SurrealJWTClaimsPartial<T>,
+        now: SystemTime,
+    ) -> Result<(), ValidationError> {
+        self.check(claims.exp, claims.nbf, claims.iss.as_deref(), now)
+    }
+
+    fn check(
+        &self,
+        exp: Option<u64>,
+        nbf: Option<u64>,
+        iss: Option<&str>,
+        now: SystemTime,
+    ) -> Result<(), ValidationError> {
+        let now = since_epoch(now);
+        let mut failures = Vec::new();
+        match exp {
+            Some(exp) if now >= Duration::from_secs(exp).saturating_add(self.leeway) => {
+                failures.push(ValidationFailure::Expired { exp });
+            }
+            None if self.require_exp => failures.push(ValidationFailure::MissingExp),
+            _ => {}
+        }
+        match nbf {
+            Some(nbf) if now.saturating_add(self.leeway) < Duration::from_secs(nbf) => {
+                failures.push(ValidationFailure::NotYetValid { nbf });
+            }
+            _ => {}
+        }
+        match &self.iss {
+            Some(expected) if iss != Some(expected.as_str()) => {
+                failures.push(ValidationFailure::InvalidIssuer(iss.map(str::to_owned)));
+            }
+            _ => {}
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError(failures))
+        }
+    }
+}
+
+/// A check of [`Validation`] that a token failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationFailure {
+    /// The token expired more than the leeway ago.
+    Expired {
+        /// The token's `exp`.
+        exp: u64,
+    },
+    /// The token becomes valid more than the leeway from now.
+    NotYetValid {
+        /// The token's `nbf`.
+        nbf: u64,
+    },
+    /// The token has no `exp`, and one is required.
+    MissingExp,
+    /// The token's `iss` is not the expected one. Holds the `iss` found, if any.
+    InvalidIssuer(Option<String>),
+}
+
+impl fmt::Display for ValidationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Expired { exp } => write!(f, "the token expired at {exp}"),
+            Self::NotYetValid { nbf } => write!(f, "the token is not valid before {nbf}"),
+            Self::MissingExp => f.write_str("the token has no `exp` claim"),
+            Self::InvalidIssuer(Some(iss)) => write!(f, "the token was issued by {iss:?}"),
+            Self::InvalidIssuer(None) => f.write_str("the token has no `iss` claim"),
+        }
+    }
+}
+
+/// The checks a token failed, returned by [`Validation::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError(Vec<ValidationFailure>);
+
+impl ValidationError {
+    /// Returns every check that failed, in the order `exp`, `nbf`, `iss`.
+    pub fn failures(&self) -> &[ValidationFailure] {
+        &self.0
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, failure) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{failure}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// The values a service expects in the claims of the tokens it accepts, checked by
+/// [`SurrealJWTClaims::validate_expectations`]. A field left `None` is not checked.
+///
+/// ```
+/// use atopio_extra::Expectations;
+/// use atopio_extra::types::SurrealJWTClaims;
+///
+/// let claims: SurrealJWTClaims<String> = serde_json::from_str(
+///     r#"{"iat":1,"nbf":1,"exp":2,"iss":"gateway","jti":"j","NS":"acme","DB":"main","AC":"user","ID":"user:1"}"#,
+/// ).unwrap();
+/// let expectations = Expectations {
+///     iss: Some("gateway".into()),
+///     ns: Some("acme".into()),
+///     id_table: Some("user".into()),
+///     ..Default::default()
+/// };
+/// assert!(claims.validate_expectations(&expectations).is_ok());
+///
+/// let other_tenant = Expectations { ns: Some("globex".into()), ..Default::default() };
+/// let err = claims.validate_expectations(&other_tenant).unwrap_err();
+/// assert_eq!(err.to_string(), r#"the `NS` claim is "acme", expected "globex""#);
+/// assert_eq!(err.redacted().to_string(), r#"the `NS` claim is <redacted>, expected "globex""#);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expectations {
+    /// The expected `iss`.
+    pub iss: Option<String>,
+    /// The expected `NS`.
+    pub ns: Option<String>,
+    /// The expected `DB`.
+    pub db: Option<String>,
+    /// The table the `ID` must be a record id in.
+    pub id_table: Option<String>,
+}
+
+impl Expectations {
+    /// Checks the claims in the order `iss`, `NS`, `DB`, `ID`, returning the first mismatch.
+    pub(crate) fn check(
+        &self,
+        iss: &str,
+        ns: &str,
+        db: &str,
+        id: &str,
+    ) -> Result<(), ExpectationError> {
+        for (claim, expected, actual) in [
+            ("iss", &self.iss, iss),
+            ("NS", &self.ns, ns),
+            ("DB", &self.db, db),
+        ] {
+            match expected {
+                Some(expected) if expected != actual => {
+                    return Err(ExpectationError {
+                        claim,
+                        expected: expected.clone(),
+                        actual: actual.to_owned(),
+                    });
+                }
+                _ => {}
+            }
+        }
+        match &self.id_table {
+            Some(table) if !validate_record_id_str(id).is_ok_and(|id| id.table() == table) => {
+                Err(ExpectationError {
+                    claim: "ID",
+                    expected: table.clone(),
+                    actual: id.to_owned(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A claim that did not hold the value of [`Expectations`].
+///
+/// Its `Display` shows the value the token held. Log [`redacted`](Self::redacted) instead where
+/// that value must not appear, such as the `ID` of a user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectationError {
+    claim: &'static str,
+    expected: String,
+    actual: String,
+}
+
+impl ExpectationError {
+    /// Returns the JSON name of the claim, such as `iss` or `NS`.
+    pub fn claim(&self) -> &'static str {
+        self.claim
+    }
+
+    /// Returns the expected value, or for `ID` the expected table.
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// Returns the value the token held.
+    pub fn actual(&self) -> &str {
+        &self.actual
+    }
+
+    /// Returns the error with the value the token held hidden. An `ID` that is a record id
+    /// keeps its table, as in [`RecordIdRedacted`].
+    pub fn redacted(&self) -> ExpectationErrorRedacted<'_> {
+        ExpectationErrorRedacted(self)
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, actual: &dyn fmt::Display) -> fmt::Result {
+        if self.claim == "ID" {
+            write!(
+                f,
+                "the `ID` claim is {actual}, expected a record id in table {:?}",
+                self.expected
+            )
+        } else {
+            write!(
+                f,
+                "the `{}` claim is {actual}, expected {:?}",
+                self.claim, self.expected
+            )
+        }
+    }
+}
+
+impl fmt::Display for ExpectationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, &format_args!("{:?}", self.actual))
+    }
+}
+
+impl std::error::Error for ExpectationError {}
+
+/// An [`ExpectationError`] displayed without the value the token held, returned by
+/// [`ExpectationError::redacted`].
+#[derive(Debug, Clone, Copy)]
+pub struct ExpectationErrorRedacted<'a>(&'a ExpectationError);
+
+impl fmt::Display for ExpectationErrorRedacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let error = self.0;
+        match validate_record_id_str(&error.actual) {
+            Ok(id) if error.claim == "ID" => error.write(f, &RecordIdRedacted(id.record_id())),
+            _ => error.write(f, &"<redacted>"),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Verifying tokens signed with a public key, such as RS256 or ES256.
+//!
+//! Enabled by the `verify` feature, which uses `jsonwebtoken` for the signature math. The
+//! claims are still read into [`SurrealJWTClaims`], with the same errors as
+//! [`decode_payload_insecurely`](crate::decode_payload_insecurely), so a service verifying
+//! tokens from an external identity provider needs no claims struct of its own:
+//!
+//! ```
+//! use atopio_extra::verify::Algorithm;
+//! use atopio_extra::{JwtError, VerifyingKey, verify_with_key};
+//!
+//! let key = VerifyingKey::rsa_from_pem(include_bytes!("../tests/keys/rsa_public.pem")).unwrap();
+//! // {"alg":"RS256"}, with a signature that does not match.
+//! let token = "eyJhbGciOiJSUzI1NiJ9.e30.c2ln";
+//! assert!(matches!(
+//!     verify_with_key::<String>(token, &key, Algorithm::RS256),
+//!     Err(JwtError::InvalidSignature)
+//! ));
+//! ```
+//!
+//! Tokens from an OIDC provider name their key by `kid` in a JWKS document. Fetch the document
+//! however suits the service, parse it with [`Jwks::from_slice`] and verify with
+//! [`verify_with_jwks`].
+
+use crate::clock::{Clock, SystemClock};
+use crate::jwt::{self, JwtError, JwtHeader};
+use crate::types::SurrealJWTClaims;
+use crate::validation::Validation;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use jsonwebtoken::{DecodingKey, crypto};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::SystemTime;
+
+pub use jsonwebtoken::Algorithm;
+
+/// A public key, or secret, to verify token signatures with.
+///
+/// Load it once, for example at startup, and share it between requests.
+#[derive(Clone)]
+pub struct VerifyingKey(DecodingKey);
+
+impl VerifyingKey {
+    /// Reads an RSA public key from PEM, either `BEGIN PUBLIC KEY` or `BEGIN RSA PUBLIC KEY`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JwtError::InvalidKey`] if `pem` does not hold an RSA public key.
+    pub fn rsa_from_pem(pem: &[u8]) -> Result<Self, JwtError> {
+        DecodingKey::from_rsa_pem(pem)
+            .map(Self)
+            .map_err(|e| JwtError::InvalidKey(Box::new(e)))
+    }
+
+    /// Reads an RSA public key from PKCS#1 `RSAPublicKey` DER, as written by
+    /// `openssl rsa -pubin -RSAPublicKey_out -outform DER`. The key is not checked until a
+    /// token is verified with it.
+    pub fn rsa_from_der(der: &[u8]) -> Self {
+        Self(DecodingKey::from_rsa_der(der))
+    }
+
+    /// Reads an EC public key from PEM, as `BEGIN PUBLIC KEY`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JwtError::InvalidKey`] if `pem` does not hold an EC public key.
+    pub fn ec_from_pem(pem: &[u8]) -> Result<Self, JwtError> {
+        DecodingKey::from_ec_pem(pem)
+            .map(Self)
+            .map_err(|e| JwtError::InvalidKey(Box::new(e)))
+    }
+
+    /// Reads an EC public key from DER, as the uncompressed point: `0x04` followed by the `x`
+    /// and `y` coordinates, which is the last 65 bytes of a P-256 `SubjectPublicKeyInfo`. The
+    /// key is not checked until a token is verified with it.
+    pub fn ec_from_der(der: &[u8]) -> Self {
+        Self(DecodingKey::from_ec_der(der))
+    }
+}
+
+impl From<DecodingKey> for VerifyingKey {
+    fn from(key: DecodingKey) -> Self {
+        Self(key)
+    }
+}
+
+impl fmt::Debug for VerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("VerifyingKey(..)")
+    }
+}
+
+/// Verifies a token signed with `algorithm` by the holder of `key`, and checks that it is
+/// valid now, with no leeway.
+///
+/// # Errors
+///
+/// Returns the errors of [`verify_with_key_at`].
+pub fn verify_with_key<T>(
+    token: &str,
+    key: &VerifyingKey,
+    algorithm: Algorithm,
+) -> Result<SurrealJWTClaims<T>, JwtError>
+where
+    T: DeserializeOwned,
+{
+    verify_with_key_at(token, key, algorithm, &Validation::new(), SystemClock.now())
+}
+
+/// Verifies a token signed with `algorithm` by the holder of `key`, then runs `validation` on
+/// its claims at `now`.
+///
+/// The token's header must name `algorithm`, so a token cannot choose a weaker one, and the
+/// signature is checked before the claims are decoded.
+///
+/// # Errors
+///
+/// Returns [`JwtError::UnsupportedAlgorithm`] if the header's `alg` is not `algorithm`,
+/// [`JwtError::InvalidSignature`] if the signature does not match, [`JwtError::InvalidKey`]
+/// if `key` cannot be used with `algorithm`, and [`JwtError::Validation`] if the claims fail
+/// `validation`. A token that cannot be decoded fails with the errors of
+/// [`decode_parts_insecurely`](crate::decode_parts_insecurely).
+pub fn verify_with_key_at<T>(
+    token: &str,
+    key: &VerifyingKey,
+    algorithm: Algorithm,
+    validation: &Validation,
+    now: SystemTime,
+) -> Result<SurrealJWTClaims<T>, JwtError>
+where
+    T: DeserializeOwned,
+{
+    let [header, payload, signature] = jwt::split(token)?;
+    let alg = jwt::decode_segment::<JwtHeader>(header)?.alg;
+    if alg.parse::<Algorithm>().ok() != Some(algorithm) {
+        return Err(JwtError::UnsupportedAlgorithm(alg));
+    }
+    URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(JwtError::InvalidBase64)?;
+    let signing_input = &token[..header.len() + 1 + payload.len()];
+    match crypto::verify(signature, signing_input.as_bytes(), &key.0, algorithm) {
+        Ok(true) => {}
+        Ok(false) => return Err(JwtError::InvalidSignature),
+        Err(e) => return Err(JwtError::InvalidKey(Box::new(e))),
+    }
+    let claims = jwt::decode_claims(payload)?;
+    validation
+        .validate(&claims, now)
+        .map_err(JwtError::Validation)?;
+    Ok(claims)
+}
+
+/// A JSON Web Key Set, the document an OIDC provider publishes its signing keys in.
+///
+/// ```
+/// use atopio_extra::Jwks;
+///
+/// let jwks = Jwks::from_slice(br#"{"keys":[{"kty":"EC","kid":"k1","crv":"P-256","x":"..","y":".."}]}"#).unwrap();
+/// assert_eq!(jwks.find("k1").map(|key| key.kty.as_str()), Some("EC"));
+/// assert!(jwks.find("k2").is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jwks {
+    /// The keys, in the order of the document.
+    pub keys: Vec<Jwk>,
+}
+
+impl Jwks {
+    /// Parses a JWKS document. Keys of a type this crate cannot verify with are kept, and
+    /// only fail when a token names them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JwtError::InvalidJson`] if `json` is not a JWKS document, such as one whose
+    /// keys have no `kty`.
+    pub fn from_slice(json: &[u8]) -> Result<Self, JwtError> {
+        serde_json::from_slice(json).map_err(JwtError::InvalidJson)
+    }
+
+    /// Returns the signing key with the id `kid`. Keys marked `"use": "enc"`, which are for
+    /// encryption, are skipped.
+    pub fn find(&self, kid: &str) -> Option<&Jwk> {
+        self.keys
+            .iter()
+            .find(|key| key.kid.as_deref() == Some(kid) && key.use_.as_deref() != Some("enc"))
+    }
+}
+
+/// One key of a [`Jwks`], with the parameters of RSA and EC public keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jwk {
+    /// The key type, such as `RSA` or `EC`.
+    pub kty: String,
+    /// The key id that tokens name in their header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    /// The algorithm the key is for, such as `RS256`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    /// What the key is for: `sig` or `enc`.
+    #[serde(rename = "use", default, skip_serializing_if = "Option::is_none")]
+    pub use_: Option<String>,
+    /// The modulus of an RSA key, in base64url.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    /// The exponent of an RSA key, in base64url.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+    /// The curve of an EC key, such as `P-256`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    /// The `x` coordinate of an EC key, in base64url.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    /// The `y` coordinate of an EC key, in base64url.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    /// Every other parameter, such as `x5c`.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Jwk {
+    /// Returns whether tokens signed with `algorithm` may be verified with this key: the
+    /// algorithm must suit the key type and curve, and match the key's `alg` if it has one.
+    pub fn accepts(&self, algorithm: Algorithm) -> bool {
+        let suits = match (self.kty.as_str(), self.crv.as_deref()) {
+            ("RSA", _) => matches!(
+                algorithm,
+                Algorithm::RS256
+                    | Algorithm::RS384
+                    | Algorithm::RS512
+                    | Algorithm::PS256
+                    | Algorithm::PS384
+                    | Algorithm::PS512
+            ),
+            ("EC", Some("P-256")) => algorithm == Algorithm::ES256,
+            ("EC", Some("P-384")) => algorithm == Algorithm::ES384,
+            _ => false,
+        };
+        suits
+            && self
+                .alg
+                .as_deref()
+                .is_none_or(|alg| alg.parse::<Algorithm>().ok() == Some(algorithm))
+    }
+
+    /// Builds the key to verify signatures with.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JwtError::UnsupportedKeyType`] if the key is neither `RSA` nor `EC` on
+    /// `P-256` or `P-384`, and [`JwtError::InvalidKey`] if it lacks one of its parameters or
+    /// one is not base64url.
+    pub fn verifying_key(&self) -> Result<VerifyingKey, JwtError> {
+        let key = match (self.kty.as_str(), self.crv.as_deref()) {
+            ("RSA", _) => DecodingKey::from_rsa_components(
+                self.param(&self.n, "n")?,
+                self.param(&self.e, "e")?,
+            ),
+            ("EC", Some("P-256" | "P-384")) => DecodingKey::from_ec_components(
+                self.param(&self.x, "x")?,
+                self.param(&self.y, "y")?,
+            ),
+            _ => return Err(JwtError::UnsupportedKeyType(self.kty.clone())),
+        };
+        key.map(VerifyingKey)
+            .map_err(|e| JwtError::InvalidKey(Box::new(e)))
+    }
+
+    fn param<'a>(&self, value: &'a Option<String>, name: &str) -> Result<&'a str, JwtError> {
+        value.as_deref().ok_or_else(|| {
+            JwtError::InvalidKey(format!("the {} key has no `{name}`", self.kty).into())
+        })
+    }
+}
+
+/// Verifies a token with the key of `jwks` its header names by `kid`, and checks that it is
+/// valid now, with no leeway.
+///
+/// # Errors
+///
+/// Returns the errors of [`verify_with_jwks_at`].
+pub fn verify_with_jwks<T>(token: &str, jwks: &Jwks) -> Result<SurrealJWTClaims<T>, JwtError>
+where
+    T: DeserializeOwned,
+{
+    verify_with_jwks_at(token, jwks, &Validation::new(), SystemClock.now())
+}
+
+/// Verifies a token with the key of `jwks` its header names by `kid`, then runs `validation`
+/// on its claims at `now`.
+///
+/// # Errors
+///
+/// Returns [`JwtError::UnknownKeyId`] if the header has no `kid` or `jwks` has no signing key
+/// with it, [`JwtError::UnsupportedKeyType`] if the key is not one this crate verifies with,
+/// [`JwtError::UnsupportedAlgorithm`] if the header's `alg` does not suit the key, and
+/// otherwise the errors of [`verify_with_key_at`].
+pub fn verify_with_jwks_at<T>(
+    token: &str,
+    jwks: &Jwks,
+    validation: &Validation,
+    now: SystemTime,
+) -> Result<SurrealJWTClaims<T>, JwtError>
+where
+    T: DeserializeOwned,
+{
+    let [header, _, _] = jwt::split(token)?;
+    let header = jwt::decode_segment::<JwtHeader>(header)?;
+    let jwk = header
+        .kid
+        .as_deref()
+        .and_then(|kid| jwks.find(kid))
+        .ok_or_else(|| JwtError::UnknownKeyId(header.kid.clone()))?;
+    let key = jwk.verifying_key()?;
+    let algorithm = header
+        .alg
+        .parse::<Algorithm>()
+        .ok()
+        .filter(|algorithm| jwk.accepts(*algorithm))
+        .ok_or(JwtError::UnsupportedAlgorithm(header.alg))?;
+    verify_with_key_at(token, &key, algorithm, validation, now)
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "axum")]
+
+use atopio_extra::axum::Path;
+use atopio_extra::{Table, TypedId};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode, header};
+use axum::routing::get;
+use tower::ServiceExt;
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+async fn show(Path(id): Path<TypedId<User>>) -> String {
+    id.to_string()
+}
+
+async fn call(uri: &str) -> Result<(StatusCode, String), Box<dyn std::error::Error>> {
+    let app = Router::new()
+        .route("/users/{id}", get(show))
+        .route("/pairs/{a}/{b}", get(show));
+    let response = app
+        .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+        .await?;
+    let status = response.status();
+    if status == StatusCode::BAD_REQUEST {
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE),
+            Some(&header::HeaderValue::from_static("application/json"))
+        );
+    }
+    let body = to_bytes(response.into_body(), usize::MAX).await?;
+    Ok((status, String::from_utf8(body.to_vec())?))
+}
+
+fn error_code(body: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let json: serde_json::Value = serde_json::from_str(body)?;
+    assert!(json["message"].is_string());
+    Ok(json["error"].as_str().unwrap_or_default().to_owned())
+}
+
+#[tokio::test]
+async fn test_axum_path_naked_key() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(
+        call("/users/abc123").await?,
+        (StatusCode::OK, "user:abc123".to_owned())
+    );
+    assert_eq!(
+        call("/users/42").await?,
+        (StatusCode::OK, "user:42".to_owned())
+    );
+    assert_eq!(
+        call("/users/has%20space").await?,
+        (StatusCode::OK, "user:⟨has space⟩".to_owned())
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_axum_path_full_id() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(
+        call("/users/user:abc123").await?,
+        (StatusCode::OK, "user:abc123".to_owned())
+    );
+    assert_eq!(
+        call("/users/user:%E2%9F%A8a:b%E2%9F%A9").await?,
+        (StatusCode::OK, "user:⟨a:b⟩".to_owned())
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_axum_path_malformed() -> Result<(), Box<dyn std::error::Error>> {
+    let (status, body) = call("/users/user:").await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error_code(&body)?, "malformed_id");
+    assert!(body.contains("is not a valid record id or key"));
+
+    let (status, body) = call("/users/%20").await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error_code(&body)?, "malformed_id");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_axum_path_wrong_table() -> Result<(), Box<dyn std::error::Error>> {
+    let (status, body) = call("/users/post:abc123").await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error_code(&body)?, "table_mismatch");
+    assert!(body.contains(r#"expected table \"user\", got \"post\""#));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_axum_path_wrong_route() -> Result<(), Box<dyn std::error::Error>> {
+    // A route with two parameters is a programming error, reported as axum's Path would.
+    let (status, body) = call("/pairs/a/b").await?;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(error_code(&body)?, "invalid_path");
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use atopio_extra::Table;
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Full {
+    #[serde(with = "atopio_extra::record_id_full")]
+    id: surrealdb::RecordId,
+    #[serde(with = "atopio_extra::record_id_full::option")]
+    parent: Option<surrealdb::RecordId>,
+    #[serde(with = "atopio_extra::record_id_full::vec")]
+    friends: Vec<surrealdb::RecordId>,
+    #[serde(with = "atopio_extra::record_id_full::option_vec")]
+    tags: Option<Vec<surrealdb::RecordId>>,
+    #[serde(with = "atopio_extra::record_id_full::map_key")]
+    scores: HashMap<surrealdb::RecordId, u32>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Naked {
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::deserialize_for::<User, _>"
+    )]
+    id: surrealdb::RecordId,
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::vec::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::vec::deserialize_for::<User, _>"
+    )]
+    friends: Vec<surrealdb::RecordId>,
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::map_key::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::map_key::deserialize_for::<User, _, _, _>"
+    )]
+    scores: HashMap<surrealdb::RecordId, u32>,
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::typed::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::typed::deserialize_for::<User, _>"
+    )]
+    typed: surrealdb::RecordId,
+}
+
+fn full() -> Result<Full, Box<dyn std::error::Error>> {
+    Ok(Full {
+        id: surrealdb::RecordId::from_str("user:⟨has space⟩")?,
+        parent: Some(surrealdb::RecordId::from(("user", 42))),
+        friends: vec![
+            surrealdb::RecordId::from_str("user:abc")?,
+            surrealdb::RecordId::from_str("reading:['sensor1', 1717000000]")?,
+        ],
+        tags: None,
+        scores: HashMap::from([(surrealdb::RecordId::from_str("user:abc")?, 7)]),
+    })
+}
+
+#[test]
+fn test_binary_full_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let value = full()?;
+    let bytes = postcard::to_stdvec(&value)?;
+    let back: Full = postcard::from_bytes(&bytes)?;
+    assert_eq!(back, value);
+    Ok(())
+}
+
+#[test]
+fn test_binary_full_option_vec_some() -> Result<(), Box<dyn std::error::Error>> {
+    let value = Full {
+        parent: None,
+        tags: Some(vec![surrealdb::RecordId::from_str("tag:rust")?]),
+        ..full()?
+    };
+    let bytes = postcard::to_stdvec(&value)?;
+    let back: Full = postcard::from_bytes(&bytes)?;
+    assert_eq!(back, value);
+    Ok(())
+}
+
+#[test]
+fn test_binary_full_uses_native_encoding() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:abc")?;
+    let value = full()?;
+    let bytes = postcard::to_stdvec(&Full {
+        id: id.clone(),
+        ..value
+    })?;
+    let native = postcard::to_stdvec(&id)?;
+    assert!(bytes.starts_with(&native));
+    Ok(())
+}
+
+#[test]
+fn test_binary_naked_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let value = Naked {
+        id: surrealdb::RecordId::from_str("user:⟨42⟩")?,
+        friends: vec![surrealdb::RecordId::from(("user", 7))],
+        scores: HashMap::from([(surrealdb::RecordId::from_str("user:abc")?, 1)]),
+        typed: surrealdb::RecordId::from_str("user:007")?,
+    };
+    let bytes = postcard::to_stdvec(&value)?;
+    let back: Naked = postcard::from_bytes(&bytes)?;
+    assert_eq!(back, value);
+    Ok(())
+}
+
+#[test]
+fn test_binary_naked_deserialize_for_uses_table() -> Result<(), Box<dyn std::error::Error>> {
+    let value = Naked {
+        id: surrealdb::RecordId::from_str("account:abc")?,
+        friends: vec![],
+        scores: HashMap::new(),
+        typed: surrealdb::RecordId::from(("account", 1)),
+    };
+    let bytes = postcard::to_stdvec(&value)?;
+    let back: Naked = postcard::from_bytes(&bytes)?;
+    assert_eq!(back.id, surrealdb::RecordId::from_str("user:abc")?);
+    assert_eq!(back.typed, surrealdb::RecordId::from(("user", 1)));
+    Ok(())
+}
+
+#[test]
+fn test_binary_json_output_unchanged() -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_value(full()?)?;
+    assert_eq!(json["id"], "user:⟨has space⟩");
+    assert_eq!(json["parent"], "user:42");
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+use atopio_extra::{RecordIdBuildError, RecordIdBuilder, validate_record_id_str};
+
+#[derive(Serialize, Deserialize)]
+struct ContainerFull {
+    #[serde(with = "atopio_extra::record_id_full")]
+    id: surrealdb::RecordId,
+}
+
+#[test]
+fn test_record_id_builder_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let ids = [
+        RecordIdBuilder::table("reading")
+            .push_key("sensor1")
+            .push_key(1717000000_i64)
+            .build()?,
+        RecordIdBuilder::table("reading")
+            .push_key("a, 'quoted' \"b\"")
+            .push_key(1.5)
+            .push_key(true)
+            .build()?,
+        RecordIdBuilder::table("event")
+            .field("city", "london")
+            .field("year", 2024)
+            .build_object()?,
+        RecordIdBuilder::table("event")
+            .field("name with space", "{braces}")
+            .build_object()?,
+        RecordIdBuilder::table("user").key("abc123").build()?,
+        RecordIdBuilder::table("user").key(42).build()?,
+        RecordIdBuilder::table("user")
+            .uuid(surrealdb::Uuid::nil())
+            .build()?,
+        RecordIdBuilder::table("user").ulid_now().build()?,
+    ];
+
+    for id in ids {
+        let s = serde_json::to_string(&ContainerFull { id: id.clone() })?;
+        let parsed: ContainerFull = serde_json::from_str(&s)?;
+        assert_eq!(parsed.id, id, "{s}");
+    }
+
+    let reading = RecordIdBuilder::table("reading")
+        .push_key("sensor1")
+        .push_key(1717000000_i64)
+        .build()?;
+    assert_eq!(reading.to_string(), "reading:['sensor1', 1717000000]");
+
+    let ulid = RecordIdBuilder::table("user").ulid_now().build()?;
+    assert_eq!(ulid.key().to_string().len(), 26);
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_builder_errors() {
+    assert_eq!(
+        RecordIdBuilder::table("").key("a").build(),
+        Err(RecordIdBuildError::EmptyTable)
+    );
+    assert_eq!(
+        RecordIdBuilder::table("my table").key("a").build(),
+        Err(RecordIdBuildError::InvalidTable("my table".into()))
+    );
+    // Table names that `validate_record_id_str` reads as numbers.
+    for table in ["123", "1e5"] {
+        assert_eq!(
+            RecordIdBuilder::table(table).key("a").build(),
+            Err(RecordIdBuildError::InvalidTable(table.into()))
+        );
+    }
+    let escaped = RecordIdBuilder::table("sensor-data").key("a").build();
+    assert!(escaped.is_ok_and(|id| validate_record_id_str(&id.to_string()).is_ok()));
+    assert_eq!(
+        RecordIdBuilder::table("user").build(),
+        Err(RecordIdBuildError::EmptyKey)
+    );
+    assert_eq!(
+        RecordIdBuilder::table("user").key("").build(),
+        Err(RecordIdBuildError::EmptyKey)
+    );
+    assert_eq!(
+        RecordIdBuilder::table("event").build_object(),
+        Err(RecordIdBuildError::EmptyKey)
+    );
+    assert_eq!(
+        RecordIdBuilder::table("event").field("a", 1).build(),
+        Err(RecordIdBuildError::ConflictingKey)
+    );
+    assert_eq!(
+        RecordIdBuilder::table("event")
+            .push_key(1)
+            .field("a", 1)
+            .build_object(),
+        Err(RecordIdBuildError::ConflictingKey)
+    );
+    assert_eq!(
+        RecordIdBuilder::table("user").key("a").push_key(1).build(),
+        Err(RecordIdBuildError::ConflictingKey)
+    );
+}
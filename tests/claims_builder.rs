@@ -0,0 +1,122 @@
+use atopio_extra::types::SurrealJWTClaims;
+use atopio_extra::{ClaimsBuildError, RecordIdBuilder, TokenKind};
+use std::time::{Duration, UNIX_EPOCH};
+
+#[test]
+fn test_claims_builder() -> Result<(), Box<dyn std::error::Error>> {
+    let now = UNIX_EPOCH + Duration::from_millis(1_000_900);
+    let user = RecordIdBuilder::table("user").key("ada").build()?;
+    let claims = SurrealJWTClaims::builder()
+        .issuer("https://auth.example.com")
+        .namespace("app")
+        .database("main")
+        .subject_record(&user)
+        .access("user".to_owned())
+        .jti("j1")
+        .ttl(Duration::from_secs(600))
+        .not_before_in(Duration::from_secs(5))
+        .build_at(now)?;
+    assert_eq!((claims.iat, claims.nbf, claims.exp), (1_000, 1_005, 1_600));
+    assert_eq!(claims.iss, "https://auth.example.com");
+    assert_eq!((claims.ns.as_str(), claims.db.as_str()), ("app", "main"));
+    assert_eq!(
+        (claims.ac.as_str(), claims.id.as_str()),
+        ("user", "user:ada")
+    );
+    assert_eq!(claims.kind(), TokenKind::Record);
+
+    let json = serde_json::to_value(&claims)?;
+    assert_eq!(json["ID"], "user:ada");
+    let parsed: SurrealJWTClaims<String> = serde_json::from_value(json)?;
+    assert_eq!(parsed.exp, 1_600);
+
+    let root = SurrealJWTClaims::builder()
+        .access(())
+        .jti("j2")
+        .build_at(now)?;
+    assert_eq!(root.iss, "SurrealDB");
+    assert_eq!(root.exp, 4_600);
+    assert_eq!(root.kind(), TokenKind::Root);
+
+    let database = SurrealJWTClaims::builder()
+        .namespace("app")
+        .database("main")
+        .access(())
+        .jti("j3")
+        .build()?;
+    assert_eq!(database.kind(), TokenKind::Database);
+    assert!(!database.is_expired());
+    Ok(())
+}
+
+#[test]
+fn test_claims_builder_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let now = UNIX_EPOCH + Duration::from_secs(1_000);
+    let user = RecordIdBuilder::table("user").key("ada").build()?;
+    let record = || {
+        SurrealJWTClaims::builder()
+            .namespace("app")
+            .database("main")
+            .subject_record(&user)
+            .access(())
+            .jti("j")
+    };
+    assert!(record().build_at(now).is_ok());
+
+    let no_db = SurrealJWTClaims::builder()
+        .namespace("app")
+        .subject_record(&user)
+        .access(())
+        .jti("j");
+    assert_eq!(
+        no_db.build_at(now).err(),
+        Some(ClaimsBuildError::MissingClaim("DB"))
+    );
+    let no_ns = SurrealJWTClaims::builder()
+        .database("main")
+        .access(())
+        .jti("j");
+    assert_eq!(
+        no_ns.build_at(now).err(),
+        Some(ClaimsBuildError::MissingClaim("NS"))
+    );
+    let no_ac = SurrealJWTClaims::<()>::builder().jti("j");
+    assert_eq!(
+        no_ac.build_at(now).err(),
+        Some(ClaimsBuildError::MissingClaim("AC"))
+    );
+    let no_jti = SurrealJWTClaims::builder().access(());
+    assert_eq!(
+        no_jti.build_at(now).err(),
+        Some(ClaimsBuildError::MissingClaim("jti"))
+    );
+
+    assert_eq!(
+        record().ttl(Duration::MAX).build_at(now).err(),
+        Some(ClaimsBuildError::Overflow("exp"))
+    );
+    assert_eq!(
+        record()
+            .not_before_in(Duration::from_secs(u64::MAX - 999))
+            .build_at(now)
+            .err(),
+        Some(ClaimsBuildError::Overflow("nbf"))
+    );
+    assert!(
+        record()
+            .ttl(Duration::from_secs(u64::MAX - 1_000))
+            .build_at(now)
+            .is_ok()
+    );
+    Ok(())
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_claims_builder_jti_random() -> Result<(), Box<dyn std::error::Error>> {
+    let build = || SurrealJWTClaims::builder().access(()).jti_random().build();
+    let (first, second) = (build()?, build()?);
+    assert_ne!(first.jti, second.jti);
+    assert_eq!(uuid::Uuid::parse_str(&first.jti)?.get_version_num(), 4);
+    Ok(())
+}
@@ -0,0 +1,324 @@
+#![cfg(feature = "client")]
+
+use atopio_extra::client::{
+    PageSizeError, Pager, SurrealRepo, delete_by_key, select_by_key, update_by_key_merge,
+};
+use atopio_extra::{Table, TypedId};
+use serde::{Deserialize, Serialize};
+use surrealdb::Surreal;
+use surrealdb::engine::local::{Db, Mem};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct User {
+    name: String,
+    age: u32,
+}
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+struct Users(Surreal<Db>);
+
+impl SurrealRepo<User> for Users {
+    type Connection = Db;
+
+    fn db(&self) -> &Surreal<Db> {
+        &self.0
+    }
+}
+
+#[derive(Serialize)]
+struct Rename {
+    name: &'static str,
+}
+
+async fn db() -> Result<Surreal<Db>, Box<dyn std::error::Error>> {
+    let db = Surreal::new::<Mem>(()).await?;
+    db.use_ns("test").use_db("test").await?;
+    db.query(
+        "CREATE user:abc SET name = 'Ada', age = 36; CREATE user:42 SET name = 'Grace', age = 45",
+    )
+    .await?
+    .check()?;
+    Ok(db)
+}
+
+#[tokio::test]
+async fn test_client_select_by_key() -> Result<(), Box<dyn std::error::Error>> {
+    let db = db().await?;
+
+    let user: Option<User> = select_by_key(&db, "user", "abc").await?;
+    assert_eq!(
+        user,
+        Some(User {
+            name: "Ada".to_owned(),
+            age: 36
+        })
+    );
+
+    let user: Option<User> = select_by_key(&db, "user", 42).await?;
+    assert_eq!(user.map(|u| u.name), Some("Grace".to_owned()));
+
+    let missing: Option<User> = select_by_key(&db, "user", "nobody").await?;
+    assert_eq!(missing, None);
+
+    // The string key "42" is a different record from the integer key 42.
+    let missing: Option<User> = select_by_key(&db, "user", "42").await?;
+    assert_eq!(missing, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_update_by_key_merge() -> Result<(), Box<dyn std::error::Error>> {
+    let db = db().await?;
+
+    let user: Option<User> =
+        update_by_key_merge(&db, "user", "abc", Rename { name: "Ada L." }).await?;
+    assert_eq!(
+        user,
+        Some(User {
+            name: "Ada L.".to_owned(),
+            age: 36
+        })
+    );
+
+    let missing: Option<User> =
+        update_by_key_merge(&db, "user", "nobody", Rename { name: "x" }).await?;
+    assert_eq!(missing, None);
+    let still_missing: Option<User> = select_by_key(&db, "user", "nobody").await?;
+    assert_eq!(still_missing, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_delete_by_key() -> Result<(), Box<dyn std::error::Error>> {
+    let db = db().await?;
+
+    let deleted: Option<User> = delete_by_key(&db, "user", 42).await?;
+    assert_eq!(deleted.map(|u| u.name), Some("Grace".to_owned()));
+
+    let gone: Option<User> = select_by_key(&db, "user", 42).await?;
+    assert_eq!(gone, None);
+
+    let again: Option<User> = delete_by_key(&db, "user", 42).await?;
+    assert_eq!(again, None);
+    Ok(())
+}
+
+fn user(name: &str, age: u32) -> User {
+    User {
+        name: name.to_owned(),
+        age,
+    }
+}
+
+#[tokio::test]
+async fn test_repo_get() -> Result<(), Box<dyn std::error::Error>> {
+    let users = Users(db().await?);
+
+    assert_eq!(
+        users.get(&TypedId::new("abc")).await?,
+        Some(user("Ada", 36))
+    );
+    assert_eq!(users.get(&TypedId::new(42)).await?, Some(user("Grace", 45)));
+    assert_eq!(users.get(&TypedId::new("nobody")).await?, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repo_create() -> Result<(), Box<dyn std::error::Error>> {
+    let users = Users(db().await?);
+
+    let created = users.create(&user("Linus", 28)).await?;
+    assert_eq!(created, Some(user("Linus", 28)));
+
+    let all = users.list(10, 0).await?;
+    assert_eq!(all.len(), 3);
+    assert!(all.contains(&user("Linus", 28)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repo_update() -> Result<(), Box<dyn std::error::Error>> {
+    let users = Users(db().await?);
+
+    let updated = users
+        .update(&TypedId::new("abc"), &user("Ada L.", 37))
+        .await?;
+    assert_eq!(updated, Some(user("Ada L.", 37)));
+    assert_eq!(
+        users.get(&TypedId::new("abc")).await?,
+        Some(user("Ada L.", 37))
+    );
+
+    let missing = users.update(&TypedId::new("nobody"), &user("x", 0)).await?;
+    assert_eq!(missing, None);
+    assert_eq!(users.get(&TypedId::new("nobody")).await?, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repo_delete() -> Result<(), Box<dyn std::error::Error>> {
+    let users = Users(db().await?);
+
+    assert_eq!(
+        users.delete(&TypedId::new(42)).await?,
+        Some(user("Grace", 45))
+    );
+    assert_eq!(users.get(&TypedId::new(42)).await?, None);
+    assert_eq!(users.delete(&TypedId::new(42)).await?, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repo_list() -> Result<(), Box<dyn std::error::Error>> {
+    let users = Users(db().await?);
+    users
+        .0
+        .query("CREATE post:1 SET name = 'not a user', age = 0")
+        .await?
+        .check()?;
+
+    // Integer keys sort before string keys.
+    assert_eq!(
+        users.list(10, 0).await?,
+        vec![user("Grace", 45), user("Ada", 36)]
+    );
+    assert_eq!(users.list(1, 0).await?, vec![user("Grace", 45)]);
+    assert_eq!(users.list(1, 1).await?, vec![user("Ada", 36)]);
+    assert_eq!(users.list(10, 2).await?, vec![]);
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Reading {
+    n: i64,
+}
+
+impl Table for Reading {
+    const NAME: &'static str = "reading";
+}
+
+async fn readings(count: i64) -> Result<Surreal<Db>, Box<dyn std::error::Error>> {
+    let db = Surreal::new::<Mem>(()).await?;
+    db.use_ns("test").use_db("test").await?;
+    db.query("FOR $n IN 1..=$count { CREATE type::thing('reading', $n) SET n = $n }; CREATE other:1 SET n = 0")
+        .bind(("count", count))
+        .await?
+        .check()?;
+    Ok(db)
+}
+
+async fn walk(db: &Surreal<Db>, size: u64) -> Result<Vec<Vec<i64>>, Box<dyn std::error::Error>> {
+    let mut pages = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = Pager::<Reading>::new(cursor, size)?
+            .fetch::<Reading, _>(db)
+            .await?;
+        pages.push(page.rows.iter().map(|r| r.n).collect());
+        match page.next {
+            Some(next) => cursor = Some(next),
+            None => return Ok(pages),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_pager_walks_every_row_once() -> Result<(), Box<dyn std::error::Error>> {
+    let db = readings(250).await?;
+
+    let pages = walk(&db, 100).await?;
+    assert_eq!(
+        pages.iter().map(Vec::len).collect::<Vec<_>>(),
+        vec![100, 100, 50]
+    );
+    assert_eq!(pages.concat(), (1..=250).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pager_exactly_full_last_page() -> Result<(), Box<dyn std::error::Error>> {
+    let db = readings(250).await?;
+
+    // No empty trailing page when the row count is a multiple of the page size.
+    let pages = walk(&db, 50).await?;
+    assert_eq!(pages.len(), 5);
+    assert!(pages.iter().all(|page| page.len() == 50));
+    assert_eq!(pages.concat(), (1..=250).collect::<Vec<_>>());
+
+    let pages = walk(&db, 250).await?;
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].len(), 250);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pager_next_cursor() -> Result<(), Box<dyn std::error::Error>> {
+    let db = readings(250).await?;
+
+    let page = Pager::<Reading>::new(None, 100)?
+        .fetch::<Reading, _>(&db)
+        .await?;
+    assert_eq!(page.next, Some(TypedId::new(100)));
+
+    let page = Pager::new(page.next, 100)?.fetch::<Reading, _>(&db).await?;
+    assert_eq!(page.rows.first(), Some(&Reading { n: 101 }));
+    assert_eq!(page.next, Some(TypedId::new(200)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pager_empty_page() -> Result<(), Box<dyn std::error::Error>> {
+    let db = readings(0).await?;
+    let page = Pager::<Reading>::new(None, 100)?
+        .fetch::<Reading, _>(&db)
+        .await?;
+    assert!(page.rows.is_empty());
+    assert_eq!(page.next, None);
+
+    // A cursor past the last record also gives an empty last page.
+    let db = readings(250).await?;
+    let page = Pager::new(Some(TypedId::<Reading>::new(250)), 100)?
+        .fetch::<Reading, _>(&db)
+        .await?;
+    assert!(page.rows.is_empty());
+    assert_eq!(page.next, None);
+    Ok(())
+}
+
+#[test]
+fn test_pager_rejects_zero_size() {
+    assert_eq!(Pager::<Reading>::new(None, 0).err(), Some(PageSizeError));
+    assert_eq!(PageSizeError.to_string(), "page size must be at least 1");
+    assert!(Pager::<Reading>::new(None, 1).is_ok());
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Select {
+    n: i64,
+}
+
+impl Table for Select {
+    const NAME: &'static str = "select";
+}
+
+#[tokio::test]
+async fn test_pager_keyword_table() -> Result<(), Box<dyn std::error::Error>> {
+    let db = Surreal::new::<Mem>(()).await?;
+    db.use_ns("test").use_db("test").await?;
+    db.query("FOR $n IN 1..=5 { CREATE type::thing('select', $n) SET n = $n }")
+        .await?
+        .check()?;
+
+    let pager = Pager::<Select>::new(None, 3)?;
+    assert_eq!(pager.query().0, "SELECT * FROM `select`:.. LIMIT $limit");
+    let page = pager.fetch::<Select, _>(&db).await?;
+    assert_eq!(page.rows.iter().map(|r| r.n).collect::<Vec<_>>(), [1, 2, 3]);
+
+    let page = Pager::new(page.next, 3)?.fetch::<Select, _>(&db).await?;
+    assert_eq!(page.rows.iter().map(|r| r.n).collect::<Vec<_>>(), [4, 5]);
+    assert_eq!(page.next, None);
+    Ok(())
+}
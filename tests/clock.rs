@@ -0,0 +1,53 @@
+#![cfg(feature = "test-utils")]
+
+use atopio_extra::Validation;
+use atopio_extra::clock::{Clock, FixedClock, SteppingClock, SystemClock};
+use atopio_extra::types::SurrealJWTClaims;
+use common::at;
+use std::time::Duration;
+
+mod common;
+
+fn claims() -> SurrealJWTClaims<String> {
+    common::claims(100, 200)
+}
+
+fn expired(claims: &SurrealJWTClaims<String>, clock: &impl Clock) -> bool {
+    claims.is_expired_at(clock.now())
+}
+
+#[test]
+fn test_fixed_clock() {
+    let clock = FixedClock(at(150));
+    assert_eq!(clock.now(), clock.now());
+    assert!(!expired(&claims(), &clock));
+    assert!(expired(&claims(), &FixedClock(at(200))));
+    assert!(Validation::new().validate(&claims(), clock.now()).is_ok());
+    assert!(SystemClock.now() >= at(200));
+}
+
+#[test]
+fn test_stepping_clock() {
+    let clock = SteppingClock::new(at(190), Duration::from_secs(5));
+    assert_eq!(clock.now(), at(190));
+    assert!(!expired(&claims(), &clock));
+    assert!(expired(&claims(), &clock));
+
+    clock.advance(Duration::from_secs(100));
+    assert_eq!(clock.now(), at(305));
+    assert_eq!(
+        claims().issued_ago_at(clock.now()),
+        Duration::from_secs(210)
+    );
+}
+
+#[test]
+fn test_stepping_clock_stops_at_the_latest_time() {
+    let latest = at(i64::MAX as u64);
+    let clock = SteppingClock::new(latest, Duration::from_secs(u64::MAX));
+    assert_eq!(clock.now(), latest);
+    assert_eq!(clock.now(), latest);
+
+    clock.advance(Duration::from_secs(u64::MAX));
+    assert_eq!(clock.now(), latest);
+}
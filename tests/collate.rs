@@ -0,0 +1,105 @@
+use atopio_extra::collate;
+use atopio_extra::types::OrdId;
+use std::cmp::Ordering;
+use std::str::FromStr;
+use surrealdb::RecordId;
+use surrealdb::Surreal;
+use surrealdb::engine::local::Mem;
+
+/// Ids of every key kind in two tables, in no particular order.
+const IDS: &[&str] = &[
+    "item:abc",
+    "item:10",
+    "entry:Z",
+    "item:{ a: 2 }",
+    "item:⟨10⟩",
+    "item:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'",
+    "item:-5",
+    "item:[1, 'b']",
+    "item:Z",
+    "entry:3",
+    "item:⟨9⟩",
+    "item:[1]",
+    "item:-9223372036854775808",
+    "item:a",
+    "item:{ a: 1, b: 1 }",
+    "item:[0, 'z']",
+    "item:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c0f'",
+    "item:2",
+    "item:⟨ä⟩",
+];
+
+fn parse_all() -> Result<Vec<RecordId>, Box<dyn std::error::Error>> {
+    let mut ids = Vec::new();
+    for id in IDS {
+        ids.push(RecordId::from_str(id)?);
+    }
+    Ok(ids)
+}
+
+#[test]
+fn test_collate_orders_by_table_then_key_kind() -> Result<(), Box<dyn std::error::Error>> {
+    let mut ids = parse_all()?;
+    ids.sort_by(collate);
+    let sorted: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    assert_eq!(
+        sorted,
+        [
+            "entry:3",
+            "entry:Z",
+            "item:-9223372036854775808",
+            "item:-5",
+            "item:2",
+            "item:10",
+            "item:⟨10⟩",
+            "item:⟨9⟩",
+            "item:Z",
+            "item:a",
+            "item:abc",
+            "item:⟨ä⟩",
+            "item:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c0f'",
+            "item:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'",
+            "item:[0, 'z']",
+            "item:[1]",
+            "item:[1, 'b']",
+            "item:{ a: 1, b: 1 }",
+            "item:{ a: 2 }",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_ord_id_uses_collate() -> Result<(), Box<dyn std::error::Error>> {
+    let ids = parse_all()?;
+    for a in &ids {
+        for b in &ids {
+            assert_eq!(
+                OrdId(a.clone()).cmp(&OrdId(b.clone())),
+                collate(a, b),
+                "{a} {b}"
+            );
+        }
+    }
+    assert_eq!(collate(&ids[0], &ids[0]), Ordering::Equal);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_collate_matches_order_by_id() -> Result<(), Box<dyn std::error::Error>> {
+    let db = Surreal::new::<Mem>(()).await?;
+    db.use_ns("test").use_db("test").await?;
+    for id in IDS {
+        db.query(format!("CREATE {id}")).await?.check()?;
+    }
+
+    let mut response = db
+        .query("SELECT VALUE id FROM item, entry ORDER BY id")
+        .await?;
+    let server: Vec<RecordId> = response.take(0)?;
+
+    let mut ids = parse_all()?;
+    ids.sort_by(collate);
+    assert_eq!(server, ids);
+    Ok(())
+}
@@ -0,0 +1,25 @@
+//! Fixtures shared by the integration tests that work with token claims.
+
+#![allow(dead_code)]
+
+use atopio_extra::types::SurrealJWTClaims;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Returns the time `secs` seconds after the Unix epoch.
+pub fn at(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+/// Returns the claims of a record token for `user:1` in `app`/`main`, issued and valid from
+/// `nbf` until `exp`. `exp` may be before `nbf`.
+pub fn claims(nbf: u64, exp: u64) -> SurrealJWTClaims<String> {
+    let claims = SurrealJWTClaims::builder()
+        .namespace("app")
+        .database("main")
+        .subject_record(&surrealdb::RecordId::from_table_key("user", 1))
+        .access("user".to_owned())
+        .jti("j")
+        .build_at(at(nbf))
+        .expect("every claim is given");
+    SurrealJWTClaims { exp, ..claims }
+}
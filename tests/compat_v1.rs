@@ -0,0 +1,151 @@
+#![cfg(feature = "compat-v1")]
+
+use std::str::FromStr;
+
+use atopio_extra::Table;
+use serde::{Deserialize, Serialize};
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct ModelV1 {
+    #[serde(with = "atopio_extra::compat_v1::thing_full")]
+    id: surrealdb_v1::sql::Thing,
+    #[serde(with = "atopio_extra::compat_v1::thing_full::option")]
+    parent: Option<surrealdb_v1::sql::Thing>,
+    #[serde(
+        serialize_with = "atopio_extra::compat_v1::thing_naked::serialize",
+        deserialize_with = "atopio_extra::compat_v1::thing_naked::deserialize_for::<User, _>"
+    )]
+    owner: surrealdb_v1::sql::Thing,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct ModelV2 {
+    #[serde(with = "atopio_extra::record_id_full")]
+    id: surrealdb::RecordId,
+    #[serde(with = "atopio_extra::record_id_full::option")]
+    parent: Option<surrealdb::RecordId>,
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::deserialize_for::<User, _>"
+    )]
+    owner: surrealdb::RecordId,
+}
+
+fn v1(s: &str) -> Result<surrealdb_v1::sql::Thing, Box<dyn std::error::Error>> {
+    Ok(surrealdb_v1::sql::thing(s)?)
+}
+
+fn v2(s: &str) -> Result<surrealdb::RecordId, Box<dyn std::error::Error>> {
+    Ok(surrealdb::RecordId::from_str(s)?)
+}
+
+#[test]
+fn test_compat_v1_json_is_byte_identical() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        ("user:abc", None, "user:abc"),
+        ("user:⟨has space⟩", Some("user:42"), "user:⟨42⟩"),
+        (
+            "reading:['sensor1', 1717000000]",
+            Some("post:⟨it's⟩"),
+            "user:⟨has space⟩",
+        ),
+        (
+            "event:{ city: 'london', year: 2024 }",
+            None,
+            "user:['a', 1]",
+        ),
+        ("`my table`:⟨a\"b⟩", None, "user:42"),
+    ];
+
+    for (id, parent, owner) in cases {
+        let old = ModelV1 {
+            id: v1(id)?,
+            parent: parent.map(v1).transpose()?,
+            owner: v1(owner)?,
+        };
+        let new = ModelV2 {
+            id: v2(id)?,
+            parent: parent.map(v2).transpose()?,
+            owner: v2(owner)?,
+        };
+
+        let json = serde_json::to_string(&new)?;
+        assert_eq!(serde_json::to_string(&old)?, json, "{id}");
+        assert_eq!(serde_json::from_str::<ModelV1>(&json)?, old, "{id}");
+
+        let mut old_cbor = Vec::new();
+        ciborium::into_writer(&old, &mut old_cbor)?;
+        let mut new_cbor = Vec::new();
+        ciborium::into_writer(&new, &mut new_cbor)?;
+        assert_eq!(old_cbor, new_cbor, "{id}");
+        assert_eq!(
+            ciborium::from_reader::<ModelV1, _>(old_cbor.as_slice())?,
+            old,
+            "{id}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_compat_v1_conversions() -> Result<(), Box<dyn std::error::Error>> {
+    let thing = v1("reading:['sensor1', 1.5f, { a: 1 }]")?;
+    let converted = atopio_extra::compat_v1::thing_from_v1(&thing)?;
+    assert_eq!(
+        converted,
+        surrealdb::sql::thing("reading:['sensor1', 1.5f, { a: 1 }]")?
+    );
+    assert_eq!(atopio_extra::compat_v1::thing_to_v1(&converted)?, thing);
+
+    let uuid = surrealdb::sql::thing("user:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'")?;
+    let err = atopio_extra::compat_v1::thing_to_v1(&uuid)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("cannot be converted"), "{err}");
+
+    let err = serde_json::from_str::<ModelV1>(
+        r#"{"id":"user:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'","parent":null,"owner":"abc"}"#,
+    )
+    .err()
+    .map(|e| e.to_string())
+    .unwrap_or_default();
+    assert!(err.contains("cannot be converted"), "{err}");
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NakedV1 {
+    #[serde(with = "atopio_extra::compat_v1::thing_naked")]
+    owner: surrealdb_v1::sql::Thing,
+}
+
+#[test]
+fn test_compat_v1_naked_deserialize_points_to_table_aware_forms()
+-> Result<(), Box<dyn std::error::Error>> {
+    let model = NakedV1 {
+        owner: v1("user:abc")?,
+    };
+    let json = serde_json::to_string(&model)?;
+    assert_eq!(json, r#"{"owner":"abc"}"#);
+
+    for input in [json.as_str(), r#"{"owner":"user:abc"}"#] {
+        let err = serde_json::from_str::<NakedV1>(input)
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default();
+        assert!(err.contains("deserialize_for"), "{err}");
+        assert!(err.contains("Naked<T>"), "{err}");
+    }
+
+    let bytes = postcard::to_allocvec(&model)?;
+    let back: NakedV1 = postcard::from_bytes(&bytes)?;
+    assert_eq!(back.owner, model.owner);
+    Ok(())
+}
@@ -0,0 +1,195 @@
+#![cfg(any(feature = "sha256", feature = "blake3"))]
+
+use atopio_extra::content_id::{ContentId, HashAlgorithm, MAX_LENGTH};
+use atopio_extra::{DeriveIdError, derive_id_from};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Reading {
+    value: f64,
+    sensor: &'static str,
+    tags: Vec<&'static str>,
+    meta: Meta,
+}
+
+#[derive(Serialize)]
+struct Meta {
+    z: i64,
+    a: Option<i64>,
+}
+
+// Whichever algorithm the enabled features provide; the tests below do not depend on it.
+fn algorithm() -> HashAlgorithm {
+    #[cfg(feature = "sha256")]
+    return HashAlgorithm::Sha256;
+    #[cfg(not(feature = "sha256"))]
+    return HashAlgorithm::Blake3;
+}
+
+fn reading() -> Reading {
+    Reading {
+        value: 20.5,
+        sensor: "s1",
+        tags: vec!["a", "b"],
+        meta: Meta { z: 1, a: None },
+    }
+}
+
+#[test]
+fn test_content_id_canonical_form() -> Result<(), Box<dyn std::error::Error>> {
+    let ids = ContentId::new(algorithm());
+    assert_eq!(
+        ids.canonical(&reading())?,
+        r#"{"meta":{"a":null,"z":1},"sensor":"s1","tags":["a","b"],"value":20.5}"#
+    );
+
+    // Field names are sorted by their UTF-8 bytes, and strings are written as serde_json does.
+    assert_eq!(
+        ids.canonical(&json!({"é": -3, "a": "é\n", "Z": true}))?,
+        r#"{"Z":true,"a":"é\n","é":-3}"#
+    );
+    assert_eq!(ids.canonical(&json!([1, 2.5, "x"]))?, r#"[1,2.5,"x"]"#);
+    Ok(())
+}
+
+#[test]
+fn test_content_id_ignores_field_order() -> Result<(), Box<dyn std::error::Error>> {
+    let from_struct = derive_id_from(algorithm(), "reading", &reading())?;
+    let from_json = derive_id_from(
+        algorithm(),
+        "reading",
+        &json!({"tags": ["a", "b"], "sensor": "s1", "meta": {"z": 1, "a": null}, "value": 20.5}),
+    )?;
+    assert_eq!(from_struct, from_json);
+    assert_eq!(from_struct.table(), "reading");
+
+    let other = derive_id_from(
+        algorithm(),
+        "reading",
+        &json!({"sensor": "s1", "value": 20.6}),
+    )?;
+    assert_ne!(from_struct, other);
+
+    // Array order is part of the content.
+    assert_ne!(
+        derive_id_from(algorithm(), "reading", &json!(["a", "b"]))?,
+        derive_id_from(algorithm(), "reading", &json!(["b", "a"]))?
+    );
+    Ok(())
+}
+
+#[cfg(feature = "sha256")]
+#[test]
+fn test_content_id_sha256_is_stable() -> Result<(), Box<dyn std::error::Error>> {
+    let ids = ContentId::new(HashAlgorithm::Sha256);
+    assert_eq!(
+        ids.derive("reading", &reading())?.to_string(),
+        "reading:e802cabdd263cfe458bd35449bdd09ba"
+    );
+    assert_eq!(
+        ids.clone()
+            .length(MAX_LENGTH)
+            .derive("reading", &reading())?
+            .to_string(),
+        "reading:e802cabdd263cfe458bd35449bdd09bacf6687a426dd2a7765e5059d5f4aac7d"
+    );
+    assert_eq!(
+        ids.length(8)
+            .derive("t", &json!({"é": -3, "a": "é\n", "Z": true}))?
+            .to_string(),
+        "t:3b0bf454"
+    );
+    Ok(())
+}
+
+#[cfg(feature = "blake3")]
+#[test]
+fn test_content_id_blake3_is_stable() -> Result<(), Box<dyn std::error::Error>> {
+    let ids = ContentId::new(HashAlgorithm::Blake3);
+    assert_eq!(
+        ids.derive("reading", &reading())?.to_string(),
+        "reading:24a6372b8d198d234ef12c68ea7701ed"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_content_id_exclude() -> Result<(), Box<dyn std::error::Error>> {
+    let ids = ContentId::new(algorithm())
+        .exclude("imported_at")
+        .exclude("meta.seen");
+    let first = ids.derive(
+        "row",
+        &json!({"value": 1, "imported_at": "2024-06-01", "meta": {"seen": 1, "source": "a"}}),
+    )?;
+    let second = ids.derive(
+        "row",
+        &json!({"value": 1, "imported_at": "2024-06-02", "meta": {"seen": 2, "source": "a"}}),
+    )?;
+    assert_eq!(first, second);
+    assert_eq!(
+        first,
+        ids.derive("row", &json!({"value": 1, "meta": {"source": "a"}}))?
+    );
+    assert_eq!(
+        ids.canonical(&json!({"value": 1, "imported_at": "x", "meta": {"seen": 1}}))?,
+        r#"{"meta":{},"value":1}"#
+    );
+
+    // Only excluded fields are ignored.
+    let changed = ids.derive(
+        "row",
+        &json!({"value": 1, "imported_at": "2024-06-01", "meta": {"seen": 1, "source": "b"}}),
+    )?;
+    assert_ne!(first, changed);
+
+    // Paths into missing or non-object fields are ignored.
+    let ids = ContentId::new(algorithm())
+        .exclude("missing.deep")
+        .exclude("value.inner");
+    assert_eq!(ids.canonical(&json!({"value": 1}))?, r#"{"value":1}"#);
+    Ok(())
+}
+
+#[test]
+fn test_content_id_length() -> Result<(), Box<dyn std::error::Error>> {
+    let id = ContentId::new(algorithm())
+        .length(10)
+        .derive("row", &reading())?;
+    assert_eq!(id.key().to_string().trim_matches(['⟨', '⟩']).len(), 10);
+    assert_eq!(ContentId::DEFAULT_LENGTH, 32);
+
+    let error = ContentId::new(algorithm())
+        .length(0)
+        .derive("row", &reading())
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(error.contains("between 1 and 64"));
+    assert_eq!(
+        ContentId::new(algorithm())
+            .length(MAX_LENGTH + 1)
+            .derive("row", &reading()),
+        Err(DeriveIdError::InvalidLength(MAX_LENGTH + 1))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_content_id_errors() {
+    let ids = ContentId::new(algorithm()).exclude("at");
+    assert_eq!(
+        ids.derive("row", &json!([1, 2])),
+        Err(DeriveIdError::NotAnObject)
+    );
+
+    let mut map = BTreeMap::new();
+    map.insert((1, 2), "tuple keys are not JSON");
+    let error = derive_id_from(algorithm(), "row", &map)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(error.contains("cannot be hashed"));
+}
@@ -0,0 +1,107 @@
+use std::str::FromStr;
+
+use atopio_extra::cursor::{self, CursorError};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+
+#[test]
+fn test_cursor_round_trip_all_key_kinds() -> Result<(), Box<dyn std::error::Error>> {
+    let ids = [
+        surrealdb::RecordId::from_str("user:abc")?,
+        surrealdb::RecordId::from_str("user:⟨has space⟩")?,
+        surrealdb::RecordId::from_str("user:⟨42⟩")?,
+        surrealdb::RecordId::from(("user", 42)),
+        surrealdb::RecordId::from(("user", -7)),
+        surrealdb::RecordId::from_str("user:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'")?,
+        surrealdb::RecordId::from_str("reading:['sensor1', 1717000000]")?,
+        surrealdb::RecordId::from_str("event:{ city: 'london', year: 2024 }")?,
+        surrealdb::RecordId::from_str("`my table`:⟨quote \"inside\"⟩")?,
+    ];
+    for id in ids {
+        let token = cursor::encode(&id);
+        assert!(
+            token
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'),
+            "{token}"
+        );
+        assert_eq!(cursor::decode(&token)?, id, "{token}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_cursor_is_stable() -> Result<(), Box<dyn std::error::Error>> {
+    // Cursors handed out by earlier releases must keep decoding to the same ids.
+    let cases = [
+        ("AXVzZXI6YWJjE7OCIQ", "user:abc"),
+        ("AXVzZXI6NDKAG-Np", "user:42"),
+        (
+            "AXJlYWRpbmc6WydzZW5zb3IxJywgMTcxNzAwMDAwMF3uSq49",
+            "reading:['sensor1', 1717000000]",
+        ),
+    ];
+    for (token, expected) in cases {
+        let id = surrealdb::RecordId::from_str(expected)?;
+        assert_eq!(cursor::decode(token)?, id, "{token}");
+        assert_eq!(cursor::encode(&id), token, "{expected}");
+    }
+    Ok(())
+}
+
+fn reencode(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+#[test]
+fn test_cursor_rejects_tampering() -> Result<(), Box<dyn std::error::Error>> {
+    let token = cursor::encode(&surrealdb::RecordId::from_str("user:abc")?);
+    let bytes = URL_SAFE_NO_PAD.decode(&token)?;
+
+    let mut edited = bytes.clone();
+    edited[5] = b'x';
+    let truncated = &bytes[..bytes.len() - 1];
+
+    for input in [
+        String::new(),
+        "not a cursor!".to_owned(),
+        "user:abc".to_owned(),
+        reencode(&edited),
+        reencode(truncated),
+        reencode(&bytes[..4]),
+    ] {
+        assert_eq!(
+            cursor::decode(&input),
+            Err(CursorError::Tampered),
+            "{input:?}"
+        );
+    }
+    Ok(())
+}
+
+/// Builds a cursor with a valid checksum around arbitrary content.
+fn forge(version: u8, text: &[u8]) -> String {
+    let mut bytes = vec![version];
+    bytes.extend_from_slice(text);
+    let hash = bytes.iter().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(*byte)).wrapping_mul(0x0100_0193)
+    });
+    bytes.extend_from_slice(&hash.to_be_bytes());
+    reencode(&bytes)
+}
+
+#[test]
+fn test_cursor_distinguishes_version_and_invalid_id() {
+    assert_eq!(
+        cursor::decode(&forge(2, b"user:abc")),
+        Err(CursorError::UnsupportedVersion(2))
+    );
+    assert_eq!(
+        cursor::decode(&forge(1, b"not an id")),
+        Err(CursorError::InvalidId("not an id".to_owned()))
+    );
+    let err = cursor::decode(&forge(1, &[0xff, 0xfe]))
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("invalid record id"), "{err}");
+}
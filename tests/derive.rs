@@ -0,0 +1,130 @@
+#![cfg(feature = "derive")]
+
+use atopio_extra::{SurrealTable, Table, TypedId};
+
+#[derive(SurrealTable)]
+#[surreal(table = "person")]
+struct User;
+
+#[derive(SurrealTable)]
+struct UserProfile;
+
+#[derive(SurrealTable)]
+struct HTTPRequestLog;
+
+#[derive(SurrealTable)]
+#[allow(dead_code)]
+struct Wrapper<T>(T);
+
+#[test]
+fn test_derive_table_attribute() {
+    assert_eq!(User::NAME, "person");
+    assert_eq!(User::table_name(), "person");
+    assert_eq!(TypedId::<User>::new("abc").to_string(), "person:abc");
+}
+
+#[test]
+fn test_derive_table_snake_case_default() {
+    assert_eq!(UserProfile::NAME, "user_profile");
+    assert_eq!(HTTPRequestLog::NAME, "http_request_log");
+    assert_eq!(Wrapper::<u8>::table_name(), "wrapper");
+}
+
+#[test]
+fn test_derive_table_compile_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
+
+mod model {
+    use atopio_extra::{SurrealTable, surreal_model};
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+    use std::str::FromStr;
+    use surrealdb::RecordId;
+
+    #[derive(SurrealTable)]
+    struct User;
+
+    #[surreal_model(id = "full")]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Post {
+        id: surrealdb::RecordId,
+        author: Option<RecordId>,
+        tags: Vec<RecordId>,
+        title: String,
+        #[serde(with = "atopio_extra::record_id_full::map_key")]
+        scores: HashMap<RecordId, u32>,
+        #[serde(with = "atopio_extra::record_id_object")]
+        origin: RecordId,
+    }
+
+    #[surreal_model(id = "naked", table = User)]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Member {
+        id: RecordId,
+        #[serde(default)]
+        invited_by: Option<RecordId>,
+        friends: Vec<RecordId>,
+    }
+
+    #[surreal_model(id = "object")]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Reading(RecordId, u32);
+
+    #[test]
+    fn test_surreal_model_full() -> Result<(), Box<dyn std::error::Error>> {
+        let post = Post {
+            id: RecordId::from_str("post:hello")?,
+            author: Some(RecordId::from_str("user:abc")?),
+            tags: vec![RecordId::from_str("tag:rust")?],
+            title: "Hello".to_owned(),
+            scores: HashMap::from([(RecordId::from_str("user:abc")?, 1)]),
+            origin: RecordId::from_str("user:abc")?,
+        };
+        let json = serde_json::to_value(&post)?;
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "id": "post:hello",
+                "author": "user:abc",
+                "tags": ["tag:rust"],
+                "title": "Hello",
+                "scores": {"user:abc": 1},
+                "origin": {"table": "user", "key": "abc"},
+            })
+        );
+        assert_eq!(serde_json::from_value::<Post>(json)?, post);
+        Ok(())
+    }
+
+    #[test]
+    fn test_surreal_model_naked() -> Result<(), Box<dyn std::error::Error>> {
+        let member: Member = serde_json::from_str(r#"{"id":"abc","friends":["def",42]}"#)?;
+        assert_eq!(
+            member,
+            Member {
+                id: RecordId::from_str("user:abc")?,
+                invited_by: None,
+                friends: vec![
+                    RecordId::from_str("user:def")?,
+                    RecordId::from(("user", 42))
+                ],
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&member)?,
+            r#"{"id":"abc","invited_by":null,"friends":["def","42"]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_surreal_model_object_tuple_struct() -> Result<(), Box<dyn std::error::Error>> {
+        let reading = Reading(RecordId::from_str("reading:abc")?, 3);
+        let json = serde_json::to_string(&reading)?;
+        assert_eq!(json, r#"[{"table":"reading","key":"abc"},3]"#);
+        assert_eq!(serde_json::from_str::<Reading>(&json)?, reading);
+        Ok(())
+    }
+}
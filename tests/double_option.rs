@@ -0,0 +1,84 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct PostPatch {
+    #[serde(
+        default,
+        skip_serializing_if = "atopio_extra::record_id_full::double_option::is_unset",
+        with = "atopio_extra::record_id_full::double_option"
+    )]
+    author: Option<Option<surrealdb::RecordId>>,
+    #[serde(default)]
+    title: Option<String>,
+}
+
+#[test]
+fn test_double_option_missing_field() -> Result<(), Box<dyn std::error::Error>> {
+    let patch: PostPatch = serde_json::from_str(r#"{"title":"Hello"}"#)?;
+    assert_eq!(patch.author, None);
+    assert_eq!(serde_json::to_string(&patch)?, r#"{"title":"Hello"}"#);
+    Ok(())
+}
+
+#[test]
+fn test_double_option_null() -> Result<(), Box<dyn std::error::Error>> {
+    let patch: PostPatch = serde_json::from_str(r#"{"author":null}"#)?;
+    assert_eq!(patch.author, Some(None));
+    assert_eq!(
+        serde_json::to_string(&patch)?,
+        r#"{"author":null,"title":null}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_double_option_set() -> Result<(), Box<dyn std::error::Error>> {
+    let patch: PostPatch = serde_json::from_str(r#"{"author":"user:⟨has space⟩"}"#)?;
+    assert_eq!(
+        patch.author,
+        Some(Some(surrealdb::RecordId::from_str("user:⟨has space⟩")?))
+    );
+    assert_eq!(
+        serde_json::to_string(&patch)?,
+        r#"{"author":"user:⟨has space⟩","title":null}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_double_option_invalid() {
+    let err = serde_json::from_str::<PostPatch>(r#"{"author":"nope"}"#)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("invalid record id \"nope\""), "{err}");
+
+    let err = serde_json::from_str::<PostPatch>(r#"{"author":42}"#)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("invalid type"), "{err}");
+}
+
+#[test]
+fn test_double_option_binary_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    for author in [
+        None,
+        Some(None),
+        Some(Some(surrealdb::RecordId::from_str("user:abc")?)),
+    ] {
+        let patch = PostPatch {
+            author,
+            title: None,
+        };
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&patch, &mut bytes)?;
+        assert_eq!(
+            ciborium::from_reader::<PostPatch, _>(bytes.as_slice())?,
+            patch
+        );
+    }
+    Ok(())
+}
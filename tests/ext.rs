@@ -0,0 +1,323 @@
+use std::str::FromStr;
+
+use atopio_extra::{FullIdRef, KeyKind, RecordIdExt, RecordIdRedacted};
+
+#[test]
+fn test_record_id_ext_simple_key() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:abc123")?;
+
+    assert_eq!(id.table_str(), "user");
+    assert_eq!(id.key_string(), "abc123");
+    assert!(id.is_in_table("user"));
+    assert!(!id.is_in_table("post"));
+    assert!(!id.is_in_table("User"));
+
+    let moved = id.with_table("post");
+    assert_eq!(moved, surrealdb::RecordId::from_str("post:abc123")?);
+
+    let (table, key) = id.into_parts();
+    assert_eq!(table, "user");
+    assert_eq!(key, surrealdb::RecordIdKey::from("abc123"));
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_ext_complex_keys() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        ("user:⟨has space⟩", "has space"),
+        ("user:⟨42⟩", "42"),
+        ("user:⟨a:b⟩", "a:b"),
+        ("user:⟨🦀⟩", "🦀"),
+        ("user:42", "42"),
+        ("reading:['sensor1', 1717000000]", "['sensor1', 1717000000]"),
+        ("event:{ city: 'london' }", "{ city: 'london' }"),
+    ];
+
+    for (raw, key) in cases {
+        let id = surrealdb::RecordId::from_str(raw)?;
+        assert_eq!(id.key_string(), key, "{raw}");
+
+        let moved = id.with_table("archive");
+        assert_eq!(moved.table(), "archive");
+        assert_eq!(moved.key(), id.key());
+
+        let (table, parts_key) = id.clone().into_parts();
+        assert_eq!(surrealdb::RecordId::from_table_key(table, parts_key), id);
+    }
+
+    let escaped = surrealdb::RecordId::from_str("user:⟨has space⟩")?;
+    assert_ne!(escaped.key().to_string(), escaped.key_string());
+
+    Ok(())
+}
+
+#[derive(serde::Serialize)]
+struct ContainerNaked {
+    #[serde(with = "atopio_extra::record_id_naked")]
+    id: surrealdb::RecordId,
+}
+
+#[derive(serde::Serialize)]
+struct ContainerNakedRef<'a> {
+    id: atopio_extra::NakedKey<'a>,
+}
+
+#[test]
+fn test_record_id_ext_naked_matches_naked_serialize() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        "user:abc123",
+        "user:⟨has space⟩",
+        "user:⟨42⟩",
+        "user:⟨a:b⟩",
+        "user:42",
+        "reading:['sensor1', 1717000000]",
+        "event:{ city: 'london' }",
+        "user:u'018f3c1e-0000-7000-8000-000000000000'",
+    ];
+
+    for raw in cases {
+        let id = surrealdb::RecordId::from_str(raw)?;
+        let json = serde_json::to_value(ContainerNaked { id: id.clone() })?;
+        assert_eq!(json["id"], id.naked().to_string(), "{raw}");
+        assert_eq!(serde_json::to_value(id.naked())?, json["id"], "{raw}");
+
+        let mut module = Vec::new();
+        ciborium::into_writer(&ContainerNaked { id: id.clone() }, &mut module)?;
+        let mut wrapped = Vec::new();
+        ciborium::into_writer(&ContainerNakedRef { id: id.naked() }, &mut wrapped)?;
+        assert_eq!(wrapped, module, "{raw}");
+    }
+
+    let id = surrealdb::RecordId::from_str("user:⟨has space⟩")?;
+    assert_eq!(format!("/users/{}", id.naked()), "/users/has space");
+
+    Ok(())
+}
+
+#[derive(serde::Serialize)]
+struct ContainerFull {
+    #[serde(with = "atopio_extra::record_id_full")]
+    id: surrealdb::RecordId,
+}
+
+#[test]
+fn test_record_id_ext_full_matches_full_serialize() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        "user:abc123",
+        "user:⟨has space⟩",
+        "user:42",
+        "reading:['sensor1', 1717000000]",
+        "event:{ city: 'london' }",
+    ];
+
+    for raw in cases {
+        let id = surrealdb::RecordId::from_str(raw)?;
+        let json = serde_json::to_value(ContainerFull { id: id.clone() })?;
+        assert_eq!(serde_json::to_value(id.full())?, json["id"], "{raw}");
+        assert_eq!(serde_json::to_value(FullIdRef(&id))?, json["id"], "{raw}");
+        assert_eq!(FullIdRef(&id).to_string(), id.to_string(), "{raw}");
+
+        let mut native = Vec::new();
+        ciborium::into_writer(&id, &mut native)?;
+        let mut wrapped = Vec::new();
+        ciborium::into_writer(&id.full(), &mut wrapped)?;
+        assert_eq!(wrapped, native, "{raw}");
+    }
+
+    let id = surrealdb::RecordId::from_str("user:⟨has space⟩")?;
+    let body = serde_json::json!({ "id": id.full(), "key": id.naked() });
+    assert_eq!(
+        body,
+        serde_json::json!({ "id": "user:⟨has space⟩", "key": "has space" })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_record_id_ext_key_as() -> Result<(), Box<dyn std::error::Error>> {
+    let number = surrealdb::RecordId::from_str("user:42")?;
+    assert_eq!(number.key_as_i64(), Ok(42));
+
+    let uuid = surrealdb::Uuid::parse_str("0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10")?;
+    let id = surrealdb::RecordId::from(("user", uuid));
+    assert_eq!(id.key_as_uuid(), Ok(uuid));
+
+    let text = surrealdb::RecordId::from_str("user:⟨has space⟩")?;
+    assert_eq!(text.key_as_str(), Ok("has space"));
+    Ok(())
+}
+
+#[test]
+fn test_record_id_ext_key_as_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        ("user:⟨42⟩", KeyKind::String),
+        (
+            "user:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'",
+            KeyKind::Uuid,
+        ),
+        ("reading:['sensor1', 1]", KeyKind::Array),
+        ("event:{ city: 'london' }", KeyKind::Object),
+        ("user:rand()", KeyKind::Generated),
+    ];
+    for (input, found) in cases {
+        let id = surrealdb::RecordId::from_str(input)?;
+        let err = id.key_as_i64().err().ok_or(input)?;
+        assert_eq!(err.expected(), KeyKind::Integer, "{input}");
+        assert_eq!(err.found(), found, "{input}");
+    }
+
+    let id = surrealdb::RecordId::from_str("user:⟨0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10⟩")?;
+    let err = id
+        .key_as_uuid()
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert_eq!(err, "expected a uuid record id key, found a string key");
+
+    let id = surrealdb::RecordId::from_str("user:42")?;
+    let err = id
+        .key_as_str()
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert_eq!(err, "expected a string record id key, found an integer key");
+
+    let id = surrealdb::RecordId::from_str("event:{ city: 'london' }")?;
+    let err = id
+        .key_as_i64()
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert_eq!(
+        err,
+        "expected an integer record id key, found an object key"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_record_id_ext_same_record() -> Result<(), Box<dyn std::error::Error>> {
+    let parse = |s: &str| surrealdb::RecordId::from_str(s).map_err(Box::new);
+    let cases = [
+        (parse("user:abc")?, parse("user:⟨abc⟩")?, true),
+        (parse("user:abc")?, parse("user:`abc`")?, true),
+        (
+            parse("user:⟨abc⟩")?,
+            surrealdb::RecordId::from(("user", "abc")),
+            true,
+        ),
+        (
+            parse("user:⟨has space⟩")?,
+            surrealdb::RecordId::from(("user", "has space")),
+            true,
+        ),
+        (parse("`user`:abc")?, parse("user:abc")?, true),
+        (
+            parse("user:42")?,
+            surrealdb::RecordId::from(("user", 42)),
+            true,
+        ),
+        (parse("user:42")?, parse("user:⟨42⟩")?, false),
+        (
+            parse("user:42")?,
+            surrealdb::RecordId::from(("user", "42")),
+            false,
+        ),
+        (parse("user:abc")?, parse("User:abc")?, false),
+        (parse("user:abc")?, parse("post:abc")?, false),
+        (parse("user:abc")?, parse("user:⟨abc ⟩")?, false),
+        (
+            parse("event:{ city: 'london', year: 2024 }")?,
+            parse("event:{ year: 2024, city: 'london' }")?,
+            true,
+        ),
+        (
+            parse("reading:['a', 1]")?,
+            parse("reading:[\"a\", 1]")?,
+            true,
+        ),
+        (
+            parse("reading:['a', 1]")?,
+            parse("reading:[1, 'a']")?,
+            false,
+        ),
+        (
+            parse("user:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'")?,
+            parse("user:⟨0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10⟩")?,
+            false,
+        ),
+    ];
+
+    for (a, b, same) in cases {
+        assert_eq!(a.same_record(&b), same, "{a} vs {b}");
+        assert_eq!(b.same_record(&a), same, "{b} vs {a}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_record_id_ext_redacted() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:⟨ada@example.com⟩")?;
+    assert_eq!(id.redacted().to_string(), "user:<redacted:1143>");
+    assert_eq!(format!("{:?}", id.redacted()), "user:<redacted:1143>");
+    assert_eq!(RecordIdRedacted(&id).suffix(), "1143");
+
+    // Stable for equal keys, whatever the table, and different for the string and integer 42.
+    let other = surrealdb::RecordId::from_str("admin:⟨ada@example.com⟩")?;
+    assert_eq!(other.redacted().to_string(), "admin:<redacted:1143>");
+    let number = surrealdb::RecordId::from_str("user:42")?;
+    let string = surrealdb::RecordId::from_str("user:⟨42⟩")?;
+    assert_eq!(number.redacted().to_string(), "user:<redacted:0260>");
+    assert_eq!(string.redacted().to_string(), "user:<redacted:87ca>");
+
+    let line = format!("{:?}", Some(id.redacted()));
+    assert!(!line.contains("ada"), "{line}");
+    Ok(())
+}
+
+#[test]
+fn test_record_id_redacted_serialize() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(serde::Serialize)]
+    struct AuditEvent {
+        #[serde(serialize_with = "atopio_extra::record_id_redacted::serialize")]
+        actor: surrealdb::RecordId,
+        #[serde(serialize_with = "atopio_extra::record_id_redacted::option::serialize")]
+        target: Option<surrealdb::RecordId>,
+        #[serde(serialize_with = "atopio_extra::record_id_redacted::vec::serialize")]
+        seen: Vec<surrealdb::RecordId>,
+    }
+
+    let event = AuditEvent {
+        actor: surrealdb::RecordId::from_str("user:abc")?,
+        target: None,
+        seen: vec![surrealdb::RecordId::from_str("user:42")?],
+    };
+    assert_eq!(
+        serde_json::to_string(&event)?,
+        r#"{"actor":"user:<redacted:f34c>","target":null,"seen":["user:<redacted:0260>"]}"#
+    );
+
+    let event = AuditEvent {
+        target: Some(surrealdb::RecordId::from_str("user:abc")?),
+        ..event
+    };
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&event, &mut bytes)?;
+    let value: serde_json::Value = ciborium::from_reader(bytes.as_slice())?;
+    assert_eq!(value["target"], "user:<redacted:f34c>");
+
+    // Formats that tag `Some` read the redacted form back as an optional string.
+    #[derive(serde::Deserialize)]
+    struct Redacted {
+        actor: String,
+        target: Option<String>,
+        seen: Vec<String>,
+    }
+    let bytes = postcard::to_allocvec(&event)?;
+    let redacted: Redacted = postcard::from_bytes(&bytes)?;
+    assert_eq!(redacted.actor, "user:<redacted:f34c>");
+    assert_eq!(redacted.target.as_deref(), Some("user:<redacted:f34c>"));
+    assert_eq!(redacted.seen, ["user:<redacted:0260>"]);
+    Ok(())
+}
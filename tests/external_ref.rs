@@ -0,0 +1,92 @@
+use atopio_extra::ExternalRef;
+use atopio_extra::external_ref::RefTables;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use surrealdb::RecordId;
+
+struct Sources;
+
+impl RefTables for Sources {
+    const TABLES: &'static [&'static str] = &["article", "post"];
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Document {
+    #[serde(deserialize_with = "atopio_extra::external_ref::deserialize_in::<Sources, _>")]
+    source: ExternalRef,
+}
+
+#[test]
+fn test_external_ref_allowed_tables() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        (r#"{"source":"article:123"}"#, Some("article:123")),
+        (r#"{"source":"post:⟨a b⟩"}"#, Some("post:⟨a b⟩")),
+        (r#"{"source":"partner:feed-42"}"#, None),
+        (r#"{"source":"isbn:9780131103627"}"#, None),
+        (r#"{"source":"https://partner.example/items/42"}"#, None),
+        (r#"{"source":"plain-slug"}"#, None),
+        (r#"{"source":""}"#, None),
+    ];
+    for (json, record) in cases {
+        let doc: Document = serde_json::from_str(json)?;
+        match record {
+            Some(text) => {
+                assert_eq!(doc.source, ExternalRef::Record(RecordId::from_str(text)?));
+                assert!(doc.source.as_external().is_none());
+            }
+            None => {
+                assert!(doc.source.as_record().is_none(), "{json}");
+                assert!(!doc.source.is_record_in("article"));
+            }
+        }
+        assert_eq!(serde_json::to_string(&doc)?, json);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_external_ref_default_allows_every_table() -> Result<(), Box<dyn std::error::Error>> {
+    let reference: ExternalRef = serde_json::from_str(r#""partner:feed""#)?;
+    assert!(reference.is_record_in("partner"));
+    assert!(!reference.is_record_in("article"));
+    assert_eq!(
+        reference.as_record(),
+        Some(&RecordId::from_str("partner:feed")?)
+    );
+
+    let reference: ExternalRef = serde_json::from_str(r#""https://partner.example/x""#)?;
+    assert_eq!(reference.as_external(), Some("https://partner.example/x"));
+    assert_eq!(reference.to_string(), "https://partner.example/x");
+
+    assert_eq!(
+        ExternalRef::parse_in("article:1", &[]),
+        ExternalRef::External("article:1".to_owned())
+    );
+    assert_eq!(
+        ExternalRef::from(RecordId::from_str("article:1")?).to_string(),
+        "article:1"
+    );
+
+    let error = serde_json::from_str::<ExternalRef>("42")
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(error.contains("invalid type"), "{error}");
+    Ok(())
+}
+
+#[test]
+fn test_external_ref_binary_formats() -> Result<(), Box<dyn std::error::Error>> {
+    for source in [
+        ExternalRef::Record(RecordId::from_str("article:123")?),
+        ExternalRef::External("partner:feed-42".to_owned()),
+    ] {
+        let doc = Document { source };
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&doc, &mut bytes)?;
+        assert_eq!(ciborium::from_reader::<Document, _>(bytes.as_slice())?, doc);
+        let bytes = postcard::to_allocvec(&doc)?;
+        assert_eq!(postcard::from_bytes::<Document>(&bytes)?, doc);
+    }
+    Ok(())
+}
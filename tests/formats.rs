@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use atopio_extra::Table;
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Full {
+    #[serde(with = "atopio_extra::record_id_full")]
+    id: surrealdb::RecordId,
+    #[serde(with = "atopio_extra::record_id_full::option")]
+    parent: Option<surrealdb::RecordId>,
+    #[serde(with = "atopio_extra::record_id_full::option")]
+    missing: Option<surrealdb::RecordId>,
+    #[serde(with = "atopio_extra::record_id_full::vec")]
+    friends: Vec<surrealdb::RecordId>,
+    #[serde(with = "atopio_extra::record_id_full::option_vec")]
+    tags: Option<Vec<surrealdb::RecordId>>,
+    #[serde(with = "atopio_extra::record_id_full::map_key")]
+    scores: HashMap<surrealdb::RecordId, u32>,
+    #[serde(with = "atopio_extra::record_id_full::any")]
+    any: surrealdb::RecordId,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Naked {
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::deserialize_for::<User, _>"
+    )]
+    id: surrealdb::RecordId,
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::deserialize_for::<User, _>"
+    )]
+    number: surrealdb::RecordId,
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::option::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::option::deserialize_for::<User, _>"
+    )]
+    parent: Option<surrealdb::RecordId>,
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::vec::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::vec::deserialize_for::<User, _>"
+    )]
+    friends: Vec<surrealdb::RecordId>,
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::map_key::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::map_key::deserialize_for::<User, _, _, _>"
+    )]
+    scores: HashMap<surrealdb::RecordId, u32>,
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::typed::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::typed::deserialize_for::<User, _>"
+    )]
+    typed: surrealdb::RecordId,
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::value::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::value::deserialize_for::<User, _>"
+    )]
+    value: surrealdb::RecordId,
+}
+
+fn full() -> Result<Full, Box<dyn std::error::Error>> {
+    Ok(Full {
+        id: surrealdb::RecordId::from_str("user:⟨has space⟩")?,
+        parent: Some(surrealdb::RecordId::from(("user", 42))),
+        missing: None,
+        friends: vec![
+            surrealdb::RecordId::from_str("user:abc")?,
+            surrealdb::RecordId::from_str("reading:['sensor1', 1717000000]")?,
+        ],
+        tags: Some(vec![surrealdb::RecordId::from_str("tag:rust")?]),
+        scores: HashMap::from([
+            (surrealdb::RecordId::from_str("user:abc")?, 7),
+            (surrealdb::RecordId::from(("user", 1)), 3),
+        ]),
+        any: surrealdb::RecordId::from_str("event:{ city: 'london', year: 2024 }")?,
+    })
+}
+
+fn naked() -> Result<Naked, Box<dyn std::error::Error>> {
+    Ok(Naked {
+        id: surrealdb::RecordId::from_str("user:⟨has space⟩")?,
+        number: surrealdb::RecordId::from(("user", 42)),
+        parent: Some(surrealdb::RecordId::from_str("user:abc")?),
+        friends: vec![
+            surrealdb::RecordId::from_str("user:abc")?,
+            surrealdb::RecordId::from(("user", 7)),
+        ],
+        scores: HashMap::from([(surrealdb::RecordId::from_str("user:⟨42⟩")?, 1)]),
+        typed: surrealdb::RecordId::from_str("user:007")?,
+        value: surrealdb::RecordId::from_str("user:['sensor1', 1717000000]")?,
+    })
+}
+
+fn json<T: Serialize + DeserializeOwned>(value: &T) -> Result<T, Box<dyn std::error::Error>> {
+    Ok(serde_json::from_slice(&serde_json::to_vec(value)?)?)
+}
+
+fn cbor<T: Serialize + DeserializeOwned>(value: &T) -> Result<T, Box<dyn std::error::Error>> {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(value, &mut bytes)?;
+    Ok(ciborium::from_reader(bytes.as_slice())?)
+}
+
+fn msgpack<T: Serialize + DeserializeOwned>(value: &T) -> Result<T, Box<dyn std::error::Error>> {
+    Ok(rmp_serde::from_slice(&rmp_serde::to_vec_named(value)?)?)
+}
+
+/// MessagePack configured as human readable, so the string forms are written instead.
+fn msgpack_readable<T: Serialize + DeserializeOwned>(
+    value: &T,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let mut bytes = Vec::new();
+    value.serialize(&mut rmp_serde::Serializer::new(&mut bytes).with_human_readable())?;
+    let mut deserializer = rmp_serde::Deserializer::new(bytes.as_slice()).with_human_readable();
+    Ok(T::deserialize(&mut deserializer)?)
+}
+
+type RoundTrip<T> = fn(&T) -> Result<T, Box<dyn std::error::Error>>;
+
+fn formats<T: Serialize + DeserializeOwned>() -> [(&'static str, RoundTrip<T>); 4] {
+    [
+        ("json", json::<T>),
+        ("cbor", cbor::<T>),
+        ("msgpack", msgpack::<T>),
+        ("msgpack (human readable)", msgpack_readable::<T>),
+    ]
+}
+
+fn assert_round_trips<T>(value: &T) -> Result<(), Box<dyn std::error::Error>>
+where
+    T: Serialize + DeserializeOwned + Debug + PartialEq,
+{
+    for (name, round_trip) in formats::<T>() {
+        let back = round_trip(value).map_err(|e| format!("{name}: {e}"))?;
+        assert_eq!(&back, value, "{name}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_formats_full_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    assert_round_trips(&full()?)
+}
+
+#[test]
+fn test_formats_full_empty_collections_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    assert_round_trips(&Full {
+        parent: None,
+        friends: vec![],
+        tags: None,
+        scores: HashMap::new(),
+        ..full()?
+    })
+}
+
+#[test]
+fn test_formats_naked_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    assert_round_trips(&naked()?)
+}
+
+#[test]
+fn test_formats_naked_empty_collections_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    assert_round_trips(&Naked {
+        parent: None,
+        friends: vec![],
+        scores: HashMap::new(),
+        ..naked()?
+    })
+}
+
+#[derive(Serialize)]
+struct FullNewtype(#[serde(with = "atopio_extra::record_id_full")] surrealdb::RecordId);
+
+#[test]
+fn test_formats_cbor_writes_native_encoding() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:abc")?;
+    let mut native = Vec::new();
+    ciborium::into_writer(&id, &mut native)?;
+    let mut helper = Vec::new();
+    ciborium::into_writer(&FullNewtype(id), &mut helper)?;
+    assert_eq!(helper, native);
+    Ok(())
+}
+
+#[test]
+fn test_formats_msgpack_readable_writes_strings() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:abc")?;
+    let mut bytes = Vec::new();
+    atopio_extra::record_id_full::serialize(
+        &id,
+        &mut rmp_serde::Serializer::new(&mut bytes).with_human_readable(),
+    )?;
+    assert_eq!(rmp_serde::from_slice::<String>(&bytes)?, "user:abc");
+    Ok(())
+}
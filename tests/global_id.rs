@@ -0,0 +1,119 @@
+use atopio_extra::global_id::{self, GlobalIdError, from_global_id, to_global_id};
+use atopio_extra::{Table, TypedId};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use surrealdb::RecordId;
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Node {
+    #[serde(with = "global_id")]
+    id: RecordId,
+    #[serde(with = "global_id::typed")]
+    owner: TypedId<User>,
+}
+
+#[test]
+fn test_global_id_is_unpadded_base64url() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        ("user:abc", "dXNlcjphYmM"),
+        ("user:ab", "dXNlcjphYg"),
+        ("user:a", "dXNlcjph"),
+        ("user:⟨has spaces⟩", "dXNlcjrin6hoYXMgc3BhY2Vz4p-p"),
+        ("reading:['s1', 1]", "cmVhZGluZzpbJ3MxJywgMV0"),
+    ];
+    for (text, expected) in cases {
+        let id = RecordId::from_str(text)?;
+        let encoded = to_global_id(&id);
+        assert_eq!(encoded, expected);
+        assert!(!encoded.contains('='));
+        assert_eq!(from_global_id(&encoded)?, id);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_global_id_rejects_padding_and_tampering() {
+    assert_eq!(
+        from_global_id("dXNlcjphYmM="),
+        Err(GlobalIdError::InvalidEncoding("dXNlcjphYmM=".to_owned()))
+    );
+    assert!(matches!(
+        from_global_id("dXNlcjphYg=="),
+        Err(GlobalIdError::InvalidEncoding(_))
+    ));
+    // Standard base64 characters are not URL-safe.
+    assert!(matches!(
+        from_global_id("dXNlcjrin6hoYXMgc3BhY2Vz4p+p"),
+        Err(GlobalIdError::InvalidEncoding(_))
+    ));
+    assert!(matches!(
+        from_global_id("not base64!"),
+        Err(GlobalIdError::InvalidEncoding(_))
+    ));
+    // Invalid UTF-8.
+    assert!(matches!(
+        from_global_id("_w"),
+        Err(GlobalIdError::InvalidEncoding(_))
+    ));
+    // Valid base64 whose text is not a record id: "user".
+    assert_eq!(
+        from_global_id("dXNlcg"),
+        Err(GlobalIdError::InvalidId("user".to_owned()))
+    );
+    // A truncated id decodes to different, still valid text, so it is the caller's job to
+    // check the record exists.
+    assert_eq!(
+        from_global_id("dXNlcjph").ok(),
+        Some(RecordId::from(("user", "a")))
+    );
+
+    let message = from_global_id("dXNlcg")
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(message.contains("invalid record id"));
+}
+
+#[test]
+fn test_global_id_serde() -> Result<(), Box<dyn std::error::Error>> {
+    let node = Node {
+        id: RecordId::from_str("post:abc")?,
+        owner: TypedId::new("abc"),
+    };
+    let json = serde_json::to_string(&node)?;
+    assert_eq!(json, r#"{"id":"cG9zdDphYmM","owner":"dXNlcjphYmM"}"#);
+    assert_eq!(serde_json::from_str::<Node>(&json)?, node);
+
+    // Global ids stay strings in formats that are not human readable.
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&node, &mut cbor)?;
+    let value: ciborium::Value = ciborium::from_reader(cbor.as_slice())?;
+    assert!(
+        value
+            .as_map()
+            .is_some_and(|fields| fields.iter().all(|(_, v)| v.is_text()))
+    );
+    assert_eq!(ciborium::from_reader::<Node, _>(cbor.as_slice())?, node);
+    Ok(())
+}
+
+#[test]
+fn test_global_id_typed_rejects_other_tables() {
+    let error = serde_json::from_str::<Node>(r#"{"id":"cG9zdDphYmM","owner":"cG9zdDphYmM"}"#)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(error.contains(r#"expected table "user", got "post""#));
+
+    let error = serde_json::from_str::<Node>(r#"{"id":"dXNlcjphYmM=","owner":"dXNlcjphYmM"}"#)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(error.contains("is not a valid global id"));
+}
@@ -0,0 +1,133 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use atopio_extra::graph::{Adjacency, Edge, GraphRow, build_adjacency};
+use atopio_extra::types::FullId;
+use surrealdb::Surreal;
+use surrealdb::engine::local::Mem;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct LikeProps {
+    weight: u8,
+}
+
+#[test]
+fn test_edge_deserialize_select_results() -> Result<(), Box<dyn std::error::Error>> {
+    let json = "[{\"id\":\"likes:1\",\"in\":\"user:a\",\"out\":\"post:b\",\"weight\":3},\
+                {\"id\":\"likes:2\",\"in\":\"user:c\",\"out\":\"post:b\",\"weight\":1}]";
+    let edges: Vec<Edge<LikeProps>> = serde_json::from_str(json)?;
+
+    assert_eq!(edges.len(), 2);
+    assert_eq!(edges[0].id, Some(surrealdb::RecordId::from_str("likes:1")?));
+    assert_eq!(edges[0].r#in, surrealdb::RecordId::from_str("user:a")?);
+    assert_eq!(edges[0].out, surrealdb::RecordId::from_str("post:b")?);
+    assert_eq!(edges[1].props, LikeProps { weight: 1 });
+
+    let s = serde_json::to_string(&edges[0])?;
+    assert_eq!(
+        s,
+        "{\"id\":\"likes:1\",\"in\":\"user:a\",\"out\":\"post:b\",\"weight\":3}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_edge_between() -> Result<(), Box<dyn std::error::Error>> {
+    let edge = Edge::between(
+        surrealdb::RecordId::from_str("user:a")?,
+        surrealdb::RecordId::from_str("post:b")?,
+        LikeProps { weight: 5 },
+    );
+
+    let s = serde_json::to_string(&edge)?;
+    assert_eq!(s, "{\"in\":\"user:a\",\"out\":\"post:b\",\"weight\":5}");
+
+    Ok(())
+}
+
+#[test]
+fn test_edge_without_props() -> Result<(), Box<dyn std::error::Error>> {
+    let edge: Edge = serde_json::from_str(
+        "{\"id\":\"follows:x\",\"in\":\"user:a\",\"out\":\"user:b\",\"since\":\"ignored\"}",
+    )?;
+    assert_eq!(edge.out, surrealdb::RecordId::from_str("user:b")?);
+
+    let s = serde_json::to_string(&edge)?;
+    assert_eq!(
+        s,
+        "{\"id\":\"follows:x\",\"in\":\"user:a\",\"out\":\"user:b\"}"
+    );
+
+    Ok(())
+}
+
+fn full(text: &str) -> Result<FullId, Box<dyn std::error::Error>> {
+    Ok(FullId(surrealdb::RecordId::from_str(text)?))
+}
+
+#[test]
+fn test_build_adjacency() -> Result<(), Box<dyn std::error::Error>> {
+    let rows: Vec<GraphRow> = serde_json::from_str(
+        r#"[
+            {"id":"user:a","neighbors":["user:b","user:a","user:c","user:b"]},
+            {"id":"user:b","neighbors":[]},
+            {"id":"user:c"},
+            {"id":"user:a","neighbors":["user:d"]}
+        ]"#,
+    )?;
+    let (a, b, c, d) = (
+        full("user:a")?,
+        full("user:b")?,
+        full("user:c")?,
+        full("user:d")?,
+    );
+
+    let graph = build_adjacency(rows.clone());
+    assert_eq!(graph.len(), 3);
+    assert_eq!(
+        graph[&a],
+        [b.clone(), a.clone(), c.clone(), b.clone(), d.clone()]
+    );
+    assert!(graph[&b].is_empty());
+    assert!(graph[&c].is_empty());
+
+    let graph = Adjacency::new().dedup(true).build(rows.clone());
+    assert_eq!(graph[&a], [b.clone(), a.clone(), c.clone(), d.clone()]);
+    let graph = Adjacency::new().drop_self_loops(true).build(rows.clone());
+    assert_eq!(graph[&a], [b.clone(), c.clone(), b.clone(), d.clone()]);
+    let graph = Adjacency::new()
+        .dedup(true)
+        .drop_self_loops(true)
+        .build(rows);
+    assert_eq!(graph[&a], [b, c, d]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_graph_row_traversal_query() -> Result<(), Box<dyn std::error::Error>> {
+    let db = Surreal::new::<Mem>(()).await?;
+    db.use_ns("test").use_db("test").await?;
+    db.query(
+        "CREATE user:a, user:b, user:c;
+         RELATE user:a->follows->user:b;
+         RELATE user:a->follows->user:c;
+         RELATE user:b->follows->user:b;",
+    )
+    .await?
+    .check()?;
+
+    let mut response = db
+        .query("SELECT id, ->follows->user AS neighbors FROM user")
+        .await?;
+    let rows: Vec<GraphRow> = response.take(0)?;
+    let graph = Adjacency::new().drop_self_loops(true).build(rows);
+
+    let mut a = graph[&full("user:a")?].clone();
+    a.sort_by_key(|id| id.0.to_string());
+    assert_eq!(a, [full("user:b")?, full("user:c")?]);
+    assert!(graph[&full("user:b")?].is_empty());
+    assert!(graph[&full("user:c")?].is_empty());
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use serde_json::json;
+
+use atopio_extra::normalize_ids;
+
+#[test]
+fn test_normalize_ids_array_of_objects() {
+    let mut rows = json!([
+        {
+            "id": {"tb": "user", "id": {"String": "abc"}},
+            "name": "Ada",
+            "best_friend": {"tb": "user", "id": {"Number": 42}},
+            "address": {"city": "London", "id": 7},
+        },
+        {"id": {"tb": "user", "id": "has space"}, "tags": [{"tb": "tag", "id": "rust"}]},
+        {"id": "user:def"},
+        "not an object",
+    ]);
+
+    assert_eq!(normalize_ids(&mut rows), 3);
+    assert_eq!(
+        rows,
+        json!([
+            {
+                "id": "user:abc",
+                "name": "Ada",
+                "best_friend": "user:42",
+                "address": {"city": "London", "id": 7},
+            },
+            {"id": "user:⟨has space⟩", "tags": [{"tb": "tag", "id": "rust"}]},
+            {"id": "user:def"},
+            "not an object",
+        ])
+    );
+}
+
+#[test]
+fn test_normalize_ids_single_object() {
+    let mut row = json!({
+        "id": {"tb": "reading", "id": {"Array": [{"Strand": "sensor1"}, {"Number": {"Int": 5}}]}}
+    });
+    assert_eq!(normalize_ids(&mut row), 1);
+    assert_eq!(row["id"], "reading:['sensor1', 5]");
+
+    let mut row = json!({"id": {"tb": "user", "id": 1}, "tb": "user"});
+    assert_eq!(normalize_ids(&mut row), 1);
+    assert_eq!(row["id"], "user:1");
+}
+
+#[test]
+fn test_normalize_ids_leaves_invalid_shapes() {
+    let mut rows = json!([
+        {"id": {"tb": "user", "id": null}},
+        {"id": {"tb": 1, "id": "abc"}},
+        {"id": {"tb": "user", "id": "abc", "extra": true}},
+        {"id": null},
+    ]);
+    let before = rows.clone();
+
+    assert_eq!(normalize_ids(&mut rows), 0);
+    assert_eq!(rows, before);
+
+    let mut scalar = json!("user:abc");
+    assert_eq!(normalize_ids(&mut scalar), 0);
+}
+
+#[test]
+fn test_stringify_record_ids_nested() {
+    let mut value = json!({
+        "result": [
+            {
+                "id": {"tb": "post", "id": {"String": "hello"}},
+                "author": {
+                    "id": {"tb": "user", "id": {"String": "has space"}},
+                    "friends": [{"tb": "user", "id": {"Number": 42}}],
+                },
+                "->likes": [{"in": {"tb": "user", "id": "abc"}, "out": {"tb": "post", "id": "hello"}}],
+            },
+        ],
+        "bad": {"tb": "user", "id": null},
+        "plain": {"tb": "not", "id": "a thing", "extra": 1},
+    });
+
+    let stats = atopio_extra::stringify_record_ids(&mut value, atopio_extra::IdMode::Full);
+    assert_eq!(stats.converted, 5);
+    assert_eq!(stats.skipped, 1);
+    assert_eq!(
+        value,
+        json!({
+            "result": [
+                {
+                    "id": "post:hello",
+                    "author": {"id": "user:⟨has space⟩", "friends": ["user:42"]},
+                    "->likes": [{"in": "user:abc", "out": "post:hello"}],
+                },
+            ],
+            "bad": {"tb": "user", "id": null},
+            "plain": {"tb": "not", "id": "a thing", "extra": 1},
+        })
+    );
+}
+
+#[test]
+fn test_stringify_record_ids_naked() {
+    let mut value = json!([
+        {"tb": "user", "id": {"String": "has space"}},
+        {"tb": "user", "id": {"String": "42"}},
+        {"tb": "user", "id": {"Number": 42}},
+    ]);
+
+    let stats = atopio_extra::stringify_record_ids(&mut value, atopio_extra::IdMode::Naked);
+    assert_eq!(stats.converted, 3);
+    assert_eq!(value, json!(["has space", "⟨42⟩", "42"]));
+}
+
+#[test]
+fn test_stringify_record_ids_depth_limit() {
+    use atopio_extra::json::stringify_record_ids_to_depth;
+
+    let mut value =
+        json!({"a": {"b": {"tb": "user", "id": "abc"}}, "c": {"tb": "user", "id": "def"}});
+    let stats = stringify_record_ids_to_depth(&mut value, atopio_extra::IdMode::Full, 1);
+    assert_eq!(stats.converted, 1);
+    assert_eq!(stats.skipped, 1);
+    assert_eq!(
+        value,
+        json!({"a": {"b": {"tb": "user", "id": "abc"}}, "c": "user:def"})
+    );
+
+    let mut value = json!({"tb": "user", "id": "abc"});
+    let stats = stringify_record_ids_to_depth(&mut value, atopio_extra::IdMode::Full, 0);
+    assert_eq!(stats.converted, 1);
+    assert_eq!(value, "user:abc");
+
+    let mut deep = json!({"tb": "user", "id": "abc"});
+    for _ in 0..200 {
+        deep = json!([deep]);
+    }
+    let stats = atopio_extra::stringify_record_ids(&mut deep, atopio_extra::IdMode::Full);
+    assert_eq!(stats.converted, 0);
+    assert_eq!(stats.skipped, 1);
+}
@@ -0,0 +1,54 @@
+//! Counts the allocations made by one decode, in a test binary of its own so that no other
+//! test allocates at the same time.
+
+use atopio_extra::{decode_payload_borrowed, decode_payload_insecurely};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde_json::json;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+#[test]
+fn test_decode_payload_borrowed_does_not_allocate() {
+    let payload = json!({
+        "iat": 1, "nbf": 1, "exp": 2, "iss": "SurrealDB", "jti": "0c6f3e1a",
+        "NS": "test", "DB": "test", "AC": "users", "ID": "user:abc",
+    });
+    let token = format!("e30.{}.c2ln", URL_SAFE_NO_PAD.encode(payload.to_string()));
+    let mut scratch = Vec::with_capacity(token.len());
+
+    let borrowed = allocations(|| {
+        let claims = decode_payload_borrowed::<&str>(&token, &mut scratch).unwrap();
+        assert_eq!(claims.id, "user:abc");
+    });
+    let owned = allocations(|| {
+        let claims = decode_payload_insecurely::<String>(&token).unwrap();
+        assert_eq!(claims.id, "user:abc");
+    });
+    assert_eq!(borrowed, 0);
+    assert!(owned >= 6, "{owned}");
+}
@@ -0,0 +1,100 @@
+use atopio_extra::KeyValue;
+use atopio_extra::key_value::RecordIdParts;
+use serde_json::json;
+use std::str::FromStr;
+use surrealdb::sql::Id;
+use surrealdb::{RecordId, RecordIdKey};
+
+#[test]
+fn test_record_id_parts_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        ("user:abc", json!({"kind": "string", "value": "abc"})),
+        ("user:⟨42⟩", json!({"kind": "string", "value": "42"})),
+        ("user:-7", json!({"kind": "int", "value": -7})),
+        (
+            "user:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'",
+            json!({"kind": "uuid", "value": "0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10"}),
+        ),
+        (
+            "reading:['s1', 1717000000, 1.5]",
+            json!({"kind": "array", "value": ["s1", 1717000000, 1.5]}),
+        ),
+        (
+            "event:{ city: 'london', tags: ['a'] }",
+            json!({"kind": "object", "value": {"city": "london", "tags": ["a"]}}),
+        ),
+    ];
+    for (text, key) in cases {
+        let id = RecordId::from_str(text)?;
+        let parts = RecordIdParts::try_from(&id)?;
+        assert_eq!(parts.table, id.table());
+
+        let value = serde_json::to_value(&parts)?;
+        assert_eq!(value, json!({"table": id.table(), "key": key}), "{text}");
+        let back: RecordIdParts = serde_json::from_value(value)?;
+        assert_eq!(RecordId::try_from(back)?, id, "{text}");
+
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&parts, &mut bytes)?;
+        let back: RecordIdParts = ciborium::from_reader(bytes.as_slice())?;
+        assert_eq!(RecordId::try_from(back)?, id, "{text}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_record_id_parts_rejects_keys_without_a_key_value() -> Result<(), Box<dyn std::error::Error>>
+{
+    for text in [
+        "reading:[d'2024-06-01T10:00:00Z', 1]",
+        "reading:[3dec]",
+        "event:{ at: 1d }",
+        "event:{ missing: NONE }",
+    ] {
+        let id = RecordId::from_str(text)?;
+        let error = RecordIdParts::try_from(&id)
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default();
+        assert!(error.contains("JSON cannot represent"), "{text}: {error}");
+    }
+
+    // A generated key, which only exists before SurrealDB picks the id.
+    let key = RecordIdKey::from_inner(serde_json::from_value::<Id>(json!({"Generate": "Rand"}))?);
+    let error = KeyValue::try_from(&key)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(error.contains("is not a string"), "{error}");
+    Ok(())
+}
+
+#[test]
+fn test_record_id_parts_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let parts = RecordIdParts {
+        table: "reading".to_owned(),
+        key: KeyValue::Array(vec![json!("a\0b")]),
+    };
+    let error = RecordId::try_from(parts)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(error.contains("NUL"), "{error}");
+
+    let error = serde_json::from_str::<RecordIdParts>(
+        r#"{"table":"user","key":{"kind":"float","value":1.5}}"#,
+    )
+    .err()
+    .map(|e| e.to_string())
+    .unwrap_or_default();
+    assert!(error.contains("unknown variant `float`"), "{error}");
+
+    let error = serde_json::from_str::<RecordIdParts>(
+        r#"{"table":"user","key":{"kind":"int","value":"42"}}"#,
+    )
+    .err()
+    .map(|e| e.to_string())
+    .unwrap_or_default();
+    assert!(error.contains("invalid type"), "{error}");
+    Ok(())
+}
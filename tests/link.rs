@@ -0,0 +1,176 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use atopio_extra::link::{HasId, LinkMany, LinkOne, RecordLink};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct User {
+    #[serde(with = "atopio_extra::record_id_full")]
+    id: surrealdb::RecordId,
+    name: String,
+}
+
+impl HasId for User {
+    fn id(&self) -> &surrealdb::RecordId {
+        &self.id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Post {
+    title: String,
+    author: RecordLink<User>,
+}
+
+#[test]
+fn test_record_link_unfetched() -> Result<(), Box<dyn std::error::Error>> {
+    let post: Post = serde_json::from_str("{\"title\":\"hi\",\"author\":\"user:abc\"}")?;
+    let id = surrealdb::RecordId::from_str("user:abc")?;
+
+    assert_eq!(post.author, RecordLink::Id(id.clone()));
+    assert_eq!(post.author.id(), &id);
+    assert!(post.author.record().is_none());
+
+    let s = serde_json::to_string(&post)?;
+    assert_eq!(s, "{\"title\":\"hi\",\"author\":\"user:abc\"}");
+
+    Ok(())
+}
+
+#[test]
+fn test_record_link_fetched() -> Result<(), Box<dyn std::error::Error>> {
+    let json = "{\"title\":\"hi\",\"author\":{\"id\":\"user:abc\",\"name\":\"Ada\"}}";
+    let post: Post = serde_json::from_str(json)?;
+    let id = surrealdb::RecordId::from_str("user:abc")?;
+
+    assert!(post.author.is_fetched());
+    assert_eq!(post.author.id(), &id);
+    assert_eq!(post.author.record().map(|u| u.name.as_str()), Some("Ada"));
+
+    let s = serde_json::to_string(&post)?;
+    assert_eq!(s, json);
+
+    Ok(())
+}
+
+#[test]
+fn test_record_link_native_id() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:abc")?;
+    let native = serde_json::to_value(&id)?;
+
+    let link: RecordLink<User> = serde_json::from_value(native)?;
+    assert_eq!(link, RecordLink::Id(id));
+
+    Ok(())
+}
+
+#[test]
+fn test_record_link_rejects_other_shapes() {
+    assert!(serde_json::from_str::<RecordLink<User>>("42").is_err());
+    assert!(serde_json::from_str::<RecordLink<User>>("\"not an id\"").is_err());
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Article {
+    author: LinkOne<User>,
+    readers: LinkMany<User>,
+}
+
+fn user(key: &str, name: &str) -> Result<User, Box<dyn std::error::Error>> {
+    Ok(User {
+        id: surrealdb::RecordId::from_str(&format!("user:{key}"))?,
+        name: name.to_owned(),
+    })
+}
+
+#[test]
+fn test_link_one_writes_ids() -> Result<(), Box<dyn std::error::Error>> {
+    let unfetched: Article =
+        serde_json::from_str(r#"{"author":"user:abc","readers":["user:a","user:b"]}"#)?;
+    assert!(!unfetched.author.is_fetched());
+    assert_eq!(
+        unfetched.author.id(),
+        &surrealdb::RecordId::from_str("user:abc")?
+    );
+    assert!(unfetched.author.record().is_none());
+
+    let fetched: Article = serde_json::from_str(
+        r#"{"author":{"id":"user:abc","name":"Ada"},"readers":[{"id":"user:a","name":"A"}]}"#,
+    )?;
+    assert!(fetched.author.is_fetched());
+    assert_eq!(
+        fetched.author.id(),
+        &surrealdb::RecordId::from_str("user:abc")?
+    );
+    assert_eq!(fetched.author.record(), Some(&user("abc", "Ada")?));
+
+    // Whatever was read, only ids are written back.
+    assert_eq!(
+        serde_json::to_string(&fetched)?,
+        r#"{"author":"user:abc","readers":["user:a"]}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&unfetched)?,
+        r#"{"author":"user:abc","readers":["user:a","user:b"]}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_link_one_set_id() -> Result<(), Box<dyn std::error::Error>> {
+    let mut link = LinkOne::from_record(user("abc", "Ada")?);
+    assert!(link.is_fetched());
+
+    link.set_id(surrealdb::RecordId::from_str("user:def")?);
+    assert!(!link.is_fetched());
+    assert_eq!(serde_json::to_string(&link)?, r#""user:def""#);
+    assert_eq!(
+        LinkOne::<User>::from(surrealdb::RecordId::from_str("user:x")?).into_record(),
+        None
+    );
+    Ok(())
+}
+
+#[test]
+fn test_link_many_per_element() -> Result<(), Box<dyn std::error::Error>> {
+    let links: LinkMany<User> =
+        serde_json::from_str(r#"[{"id":"user:a","name":"A"},{"id":"user:b","name":"B"}]"#)?;
+    assert_eq!(links.len(), 2);
+    assert_eq!(
+        links.records(),
+        Some([user("a", "A")?, user("b", "B")?].as_slice())
+    );
+
+    // Mixed arrays keep every id but no records.
+    let mixed: LinkMany<User> = serde_json::from_str(r#"["user:a",{"id":"user:b","name":"B"}]"#)?;
+    let a = surrealdb::RecordId::from_str("user:a")?;
+    let b = surrealdb::RecordId::from_str("user:b")?;
+    assert_eq!(mixed.ids(), vec![&a, &b]);
+    assert_eq!(mixed.records(), None);
+
+    let empty: LinkMany<User> = serde_json::from_str("[]")?;
+    assert!(empty.is_empty());
+    assert_eq!(empty.records(), Some([].as_slice()));
+
+    assert!(serde_json::from_str::<LinkMany<User>>(r#"["user:a", 42]"#).is_err());
+    assert!(serde_json::from_str::<LinkMany<User>>(r#""user:a""#).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_link_many_set_ids() -> Result<(), Box<dyn std::error::Error>> {
+    let mut links = LinkMany::from_records(vec![user("a", "A")?]);
+    assert!(links.records().is_some());
+
+    let c = surrealdb::RecordId::from_str("user:c")?;
+    links.set_ids([c.clone()]);
+    assert_eq!(links.ids(), vec![&c]);
+    assert_eq!(links.records(), None);
+    assert_eq!(serde_json::to_string(&links)?, r#"["user:c"]"#);
+
+    let collected: LinkMany<User> = vec![c.clone()].into_iter().collect();
+    assert_eq!(collected, links);
+    assert!(LinkMany::<User>::default().is_empty());
+    Ok(())
+}
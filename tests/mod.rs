@@ -1,10 +1,11 @@
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-use atopio_extra::{decode_payload_insecurely, types};
+use atopio_extra::{JwtError, Table, TableAliasMap, decode_payload_insecurely, types};
 
 #[derive(Serialize, Deserialize)]
 struct ContainerFull {
@@ -32,6 +33,76 @@ fn test_record_id_full_serialize_deserialize() -> Result<(), Box<dyn std::error:
     Ok(())
 }
 
+#[test]
+fn test_record_id_full_serialize_matches_to_string() -> Result<(), Box<dyn std::error::Error>> {
+    for raw in [
+        "user:abc123",
+        "user:42",
+        "user:⟨has space⟩",
+        "user:u'018f3c1e-0000-7000-8000-000000000000'",
+        "reading:['sensor1', 1717000000]",
+        "event:{ city: 'london', year: 2024 }",
+    ] {
+        let id = surrealdb::RecordId::from_str(raw)?;
+        let expected = serde_json::to_string(&id.to_string())?;
+
+        let s = serde_json::to_string(&ContainerFull { id: id.clone() })?;
+        assert_eq!(s, format!("{{\"id\":{expected}}}"));
+
+        let s = serde_json::to_string(&ContainerFullOpt { id: Some(id) })?;
+        assert_eq!(s, format!("{{\"id\":{expected}}}"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_full_escapes_nan_key() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_table_key("user", "NaN");
+    assert_eq!(id.to_string(), "user:NaN");
+
+    let s = serde_json::to_string(&ContainerFull { id: id.clone() })?;
+    assert_eq!(s, r#"{"id":"user:⟨NaN⟩"}"#);
+    let parsed: ContainerFull = serde_json::from_str(&s)?;
+    assert_eq!(parsed.id, id);
+
+    let s = serde_json::to_string(&ContainerFullOpt { id: Some(id) })?;
+    assert_eq!(s, r#"{"id":"user:⟨NaN⟩"}"#);
+    Ok(())
+}
+
+#[test]
+fn test_record_id_full_borrowed_and_owned_input() -> Result<(), Box<dyn std::error::Error>> {
+    let json = "{\"id\":\"user:abc\"}";
+    let escaped = "{\"id\":\"user:\\u27e8has space\\u27e9\"}";
+
+    for input in [json, escaped] {
+        let borrowed: ContainerFull = serde_json::from_str(input)?;
+        let owned: ContainerFull = serde_json::from_reader(input.as_bytes())?;
+        let value: ContainerFull = serde_json::from_value(serde_json::from_str(input)?)?;
+        assert_eq!(borrowed.id, owned.id);
+        assert_eq!(borrowed.id, value.id);
+    }
+
+    for (input, expected) in [
+        (
+            "{\"id\":\"user:abc\"}",
+            Some(surrealdb::RecordId::from_str("user:abc")?),
+        ),
+        ("{\"id\":null}", None),
+    ] {
+        let borrowed: ContainerFullOpt = serde_json::from_str(input)?;
+        let owned: ContainerFullOpt = serde_json::from_reader(input.as_bytes())?;
+        assert_eq!(borrowed.id, expected);
+        assert_eq!(owned.id, expected);
+    }
+
+    let vec: ContainerFullVec = serde_json::from_reader("{\"ids\":[\"user:a\"]}".as_bytes())?;
+    assert_eq!(vec.ids, vec![surrealdb::RecordId::from_str("user:a")?]);
+
+    Ok(())
+}
+
 #[test]
 fn test_record_id_naked_serialize() -> Result<(), Box<dyn std::error::Error>> {
     let id = surrealdb::RecordId::from_str("user:xyz789")?;
@@ -114,6 +185,499 @@ fn test_record_id_naked_opt_none_serialize() -> Result<(), Box<dyn std::error::E
     Ok(())
 }
 
+#[derive(Serialize, Deserialize)]
+struct ContainerFullOption {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "atopio_extra::record_id_full::option"
+    )]
+    id: Option<surrealdb::RecordId>,
+}
+
+#[test]
+fn test_record_id_full_option_module() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:opt456")?;
+    let c = ContainerFullOption {
+        id: Some(id.clone()),
+    };
+
+    let s = serde_json::to_string(&c)?;
+    assert_eq!(s, format!("{{\"id\":\"{id}\"}}"));
+
+    let parsed: ContainerFullOption = serde_json::from_str(&s)?;
+    assert_eq!(parsed.id, Some(id));
+
+    let s = serde_json::to_string(&ContainerFullOption { id: None })?;
+    assert_eq!(s, "{}");
+
+    let parsed: ContainerFullOption = serde_json::from_str("{}")?;
+    assert!(parsed.id.is_none());
+
+    let parsed: ContainerFullOption = serde_json::from_str("{\"id\":null}")?;
+    assert!(parsed.id.is_none());
+
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerFullVec {
+    #[serde(with = "atopio_extra::record_id_full::vec")]
+    ids: Vec<surrealdb::RecordId>,
+}
+
+#[test]
+fn test_record_id_full_vec() -> Result<(), Box<dyn std::error::Error>> {
+    let ids = vec![
+        surrealdb::RecordId::from_str("user:a")?,
+        surrealdb::RecordId::from_str("user:b")?,
+    ];
+    let c = ContainerFullVec { ids: ids.clone() };
+
+    let s = serde_json::to_string(&c)?;
+    assert_eq!(s, "{\"ids\":[\"user:a\",\"user:b\"]}");
+
+    let parsed: ContainerFullVec = serde_json::from_str(&s)?;
+    assert_eq!(parsed.ids, ids);
+
+    let parsed: ContainerFullVec = serde_json::from_str("{\"ids\":[]}")?;
+    assert!(parsed.ids.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_full_vec_errors() {
+    let err = serde_json::from_str::<ContainerFullVec>("{\"ids\":[\"user:a\",\"user:\"]}")
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("index 1"), "{err}");
+
+    assert!(serde_json::from_str::<ContainerFullVec>("{\"ids\":\"user:a\"}").is_err());
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerFullOptionVec {
+    #[serde(default, with = "atopio_extra::record_id_full::option_vec")]
+    ids: Option<Vec<surrealdb::RecordId>>,
+}
+
+#[test]
+fn test_record_id_full_option_vec() -> Result<(), Box<dyn std::error::Error>> {
+    let ids = vec![surrealdb::RecordId::from_str("user:a")?];
+
+    let s = serde_json::to_string(&ContainerFullOptionVec {
+        ids: Some(ids.clone()),
+    })?;
+    assert_eq!(s, "{\"ids\":[\"user:a\"]}");
+
+    let parsed: ContainerFullOptionVec = serde_json::from_str(&s)?;
+    assert_eq!(parsed.ids, Some(ids));
+
+    let s = serde_json::to_string(&ContainerFullOptionVec { ids: None })?;
+    assert_eq!(s, "{\"ids\":null}");
+
+    let parsed: ContainerFullOptionVec = serde_json::from_str(&s)?;
+    assert_eq!(parsed.ids, None);
+
+    let parsed: ContainerFullOptionVec = serde_json::from_str("{}")?;
+    assert_eq!(parsed.ids, None);
+
+    let parsed: ContainerFullOptionVec = serde_json::from_str("{\"ids\":[]}")?;
+    assert_eq!(parsed.ids, Some(vec![]));
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_full_option_vec_errors() {
+    let err = serde_json::from_str::<ContainerFullOptionVec>("{\"ids\":\"user:a\"}")
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("expected an array"), "{err}");
+
+    let err = serde_json::from_str::<ContainerFullOptionVec>("{\"ids\":[\"user:a\",\"nope\"]}")
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("index 1"), "{err}");
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerFullMap {
+    #[serde(with = "atopio_extra::record_id_full::map_key")]
+    counts: HashMap<surrealdb::RecordId, u32>,
+}
+
+#[test]
+fn test_record_id_full_map_key() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:abc")?;
+    let c = ContainerFullMap {
+        counts: HashMap::from([(id.clone(), 3)]),
+    };
+
+    let s = serde_json::to_string(&c)?;
+    assert_eq!(s, "{\"counts\":{\"user:abc\":3}}");
+
+    let parsed: ContainerFullMap =
+        serde_json::from_str("{\"counts\":{\"user:abc\":3,\"user:42\":5}}")?;
+    assert_eq!(parsed.counts.len(), 2);
+    assert_eq!(parsed.counts[&id], 3);
+    assert_eq!(parsed.counts[&surrealdb::RecordId::from(("user", 42))], 5);
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_full_map_key_errors() {
+    let err =
+        serde_json::from_str::<ContainerFullMap>("{\"counts\":{\"user:abc\":1,\"user:⟨abc⟩\":2}}")
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default();
+    assert!(err.contains("duplicate record id key"), "{err}");
+
+    assert!(serde_json::from_str::<ContainerFullMap>("{\"counts\":{\"abc\":1}}").is_err());
+}
+
+#[test]
+fn test_record_id_full_array_keys() -> Result<(), Box<dyn std::error::Error>> {
+    let strings = [
+        "sensor1",
+        "a,b",
+        "it's",
+        "say \"hi\"",
+        "both ' and \"",
+        "back\\slash",
+        "[bracket], ⟨angle⟩ and `tick`",
+        "line\nbreak\ttab",
+        "emoji 🦀",
+        "",
+    ];
+
+    for s in strings {
+        let key = surrealdb::RecordIdKey::from(vec![
+            surrealdb::Value::from_inner(surrealdb::sql::Value::from(s)),
+            surrealdb::Value::from_inner(surrealdb::sql::Value::from(1717000000)),
+        ]);
+        let id = surrealdb::RecordId::from_table_key("reading", key);
+
+        let json = serde_json::to_string(&ContainerFull { id: id.clone() })?;
+        let parsed: ContainerFull = serde_json::from_str(&json)?;
+        assert_eq!(parsed.id, id, "{json}");
+    }
+
+    for raw in [
+        "reading:['sensor1', 1717000000]",
+        "reading:[['nested', 1], [2, [3, ['deep']]]]",
+        "reading:[1.5, -2, true, NONE, NULL]",
+        "reading:[d'2024-01-01T00:00:00Z', u'018f3c1e-0000-7000-8000-000000000000']",
+        "reading:[{ a: 1 }, user:abc, 10ms]",
+    ] {
+        let id = surrealdb::RecordId::from_str(raw)?;
+        let json = serde_json::to_string(&ContainerFull { id: id.clone() })?;
+        let parsed: ContainerFull = serde_json::from_str(&json)?;
+        assert_eq!(parsed.id, id, "{raw}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_full_object_keys() -> Result<(), Box<dyn std::error::Error>> {
+    for raw in [
+        "event:{ city: 'london', year: 2024 }",
+        "event:{ city: 'london', venue: { name: 'hall', floor: 2, tags: ['a', 'b'] } }",
+        "event:{ 'a b': { nested: [1, '}'] }, 'x{': '{', quote: \"it's\" }",
+        "event:{ at: d'2024-01-01T00:00:00Z', until: d'2024-01-02T12:30:00.5Z' }",
+        "event:{ owner: user:abc, id: u'018f3c1e-0000-7000-8000-000000000000' }",
+        "event:{}",
+    ] {
+        let id = surrealdb::RecordId::from_str(raw)?;
+        let json = serde_json::to_string(&ContainerFull { id: id.clone() })?;
+        let parsed: ContainerFull = serde_json::from_str(&json)?;
+        assert_eq!(parsed.id, id, "{raw}");
+    }
+
+    let a = surrealdb::RecordId::from_str("event:{ year: 2024, city: 'london' }")?;
+    let b = surrealdb::RecordId::from_str("event:{ city: 'london', year: 2024 }")?;
+    assert_eq!(a, b);
+
+    let json = serde_json::to_string(&ContainerFull { id: a })?;
+    assert_eq!(json, "{\"id\":\"event:{ city: 'london', year: 2024 }\"}");
+
+    Ok(())
+}
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerFullLenientFor {
+    #[serde(
+        serialize_with = "atopio_extra::record_id_full::serialize",
+        deserialize_with = "atopio_extra::record_id_full::lenient_for::<User, _>"
+    )]
+    id: surrealdb::RecordId,
+}
+
+#[test]
+fn test_record_id_full_lenient_for() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        ("user:abc123", surrealdb::RecordId::from(("user", "abc123"))),
+        ("abc123", surrealdb::RecordId::from(("user", "abc123"))),
+        ("42", surrealdb::RecordId::from(("user", 42))),
+        (
+            "⟨has space⟩",
+            surrealdb::RecordId::from(("user", "has space")),
+        ),
+        (
+            "has space",
+            surrealdb::RecordId::from(("user", "has space")),
+        ),
+        ("post:abc123", surrealdb::RecordId::from(("post", "abc123"))),
+    ];
+
+    for (input, expected) in cases {
+        let parsed: ContainerFullLenientFor =
+            serde_json::from_str(&format!("{{\"id\":\"{input}\"}}"))?;
+        assert_eq!(parsed.id, expected, "{input}");
+    }
+
+    for json in ["{\"id\":\"\"}", "{\"id\":\"a:b:c\"}", "{\"id\":42}"] {
+        assert!(
+            serde_json::from_str::<ContainerFullLenientFor>(json).is_err(),
+            "{json}"
+        );
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerFullVecLenientFor {
+    #[serde(
+        serialize_with = "atopio_extra::record_id_full::vec::serialize",
+        deserialize_with = "atopio_extra::record_id_full::vec_lenient_for::<User, _>"
+    )]
+    ids: Vec<surrealdb::RecordId>,
+}
+
+#[test]
+fn test_record_id_full_vec_lenient_for() -> Result<(), Box<dyn std::error::Error>> {
+    let parsed: ContainerFullVecLenientFor =
+        serde_json::from_str(r#"{"ids":["user:abc","def","42","post:x","⟨has space⟩"]}"#)?;
+    assert_eq!(
+        parsed.ids,
+        vec![
+            surrealdb::RecordId::from(("user", "abc")),
+            surrealdb::RecordId::from(("user", "def")),
+            surrealdb::RecordId::from(("user", 42)),
+            surrealdb::RecordId::from(("post", "x")),
+            surrealdb::RecordId::from(("user", "has space")),
+        ]
+    );
+    assert_eq!(
+        serde_json::to_string(&parsed)?,
+        r#"{"ids":["user:abc","user:def","user:42","post:x","user:⟨has space⟩"]}"#
+    );
+
+    let empty: ContainerFullVecLenientFor = serde_json::from_str(r#"{"ids":[]}"#)?;
+    assert!(empty.ids.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_record_id_full_vec_lenient_for_collects_errors() {
+    let err = serde_json::from_str::<ContainerFullVecLenientFor>(
+        r#"{"ids":["user:abc","","def","a:b:c"]}"#,
+    )
+    .err()
+    .map(|e| e.to_string())
+    .unwrap_or_default();
+    assert!(
+        err.contains(
+            "invalid record id \"\" (expected \"table:key\") at index 1; \
+             invalid record id \"a:b:c\" (expected \"table:key\") at index 3"
+        ),
+        "{err}"
+    );
+
+    let err = serde_json::from_str::<ContainerFullVecLenientFor>(r#"{"ids":["abc",1]}"#)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("invalid type"), "{err}");
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerFullLenient {
+    #[serde(with = "atopio_extra::record_id_full::lenient")]
+    id: surrealdb::RecordId,
+}
+
+#[test]
+fn test_record_id_full_lenient_raw_response() -> Result<(), Box<dyn std::error::Error>> {
+    // Ids as returned by SurrealDB 2.4's raw JSON interfaces, alongside the plain forms.
+    let cases = [
+        (r#"{"tb":"user","id":{"String":"abc123"}}"#, "user:abc123"),
+        (r#"{"tb":"user","id":{"Number":42}}"#, "user:42"),
+        (
+            r#"{"tb":"user","id":{"Uuid":"018f3c1e-0000-7000-8000-000000000000"}}"#,
+            "user:u'018f3c1e-0000-7000-8000-000000000000'",
+        ),
+        (
+            r#"{"tb":"reading","id":{"Array":[{"Strand":"sensor1"},{"Number":{"Int":17}},{"Number":{"Float":1.5}}]}}"#,
+            "reading:['sensor1', 17, 1.5]",
+        ),
+        (
+            r#"{"tb":"event","id":{"Object":{"city":{"Strand":"london"},"year":{"Number":{"Int":2024}}}}}"#,
+            "event:{ city: 'london', year: 2024 }",
+        ),
+        (r#"{"tb":"user","id":"abc123"}"#, "user:abc123"),
+        (r#"{"tb":"user","id":42}"#, "user:42"),
+        (
+            r#"{"tb":"reading","id":{"Array":["sensor1",17]}}"#,
+            "reading:['sensor1', 17]",
+        ),
+        (r#""user:abc123""#, "user:abc123"),
+    ];
+
+    for (json, expected) in cases {
+        let parsed: ContainerFullLenient = serde_json::from_str(&format!("{{\"id\":{json}}}"))?;
+        assert_eq!(
+            parsed.id,
+            surrealdb::RecordId::from_str(expected)?,
+            "{json}"
+        );
+    }
+
+    let s = serde_json::to_string(&ContainerFullLenient {
+        id: surrealdb::RecordId::from_str("user:abc123")?,
+    })?;
+    assert_eq!(s, "{\"id\":\"user:abc123\"}");
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_full_lenient_errors() {
+    for json in [
+        r#"{"id":{"tb":"user"}}"#,
+        r#"{"id":{"id":"abc"}}"#,
+        r#"{"id":{"tb":"user","id":"abc","extra":1}}"#,
+        r#"{"id":{"tb":"user","id":{"Number":"42"}}}"#,
+        r#"{"id":{"tb":"user","id":{"Uuid":"nope"}}}"#,
+        r#"{"id":{"tb":"user","id":null}}"#,
+        r#"{"id":42}"#,
+        r#"{"id":"nope"}"#,
+    ] {
+        assert!(
+            serde_json::from_str::<ContainerFullLenient>(json).is_err(),
+            "{json}"
+        );
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerFullAny {
+    #[serde(with = "atopio_extra::record_id_full::any")]
+    id: surrealdb::RecordId,
+}
+
+#[test]
+fn test_record_id_full_any() -> Result<(), Box<dyn std::error::Error>> {
+    let expected = surrealdb::RecordId::from(("user", "abc"));
+
+    for json in [
+        r#""user:abc""#,
+        r#"{"tb":"user","id":"abc"}"#,
+        r#"{"tb":"user","id":{"String":"abc"}}"#,
+    ] {
+        let parsed: ContainerFullAny = serde_json::from_str(&format!("{{\"id\":{json}}}"))?;
+        assert_eq!(parsed.id, expected, "{json}");
+    }
+
+    let native = serde_json::to_string(&expected)?;
+    let parsed: ContainerFullAny = serde_json::from_str(&format!("{{\"id\":{native}}}"))?;
+    assert_eq!(parsed.id, expected);
+
+    let bytes = serde::de::value::BytesDeserializer::<serde::de::value::Error>::new(b"user:abc");
+    assert_eq!(
+        atopio_extra::record_id_full::any::deserialize(bytes)?,
+        expected
+    );
+
+    let s = serde_json::to_string(&ContainerFullAny { id: expected })?;
+    assert_eq!(s, "{\"id\":\"user:abc\"}");
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_full_any_errors() {
+    let err = |json: &str| {
+        serde_json::from_str::<ContainerFullAny>(json)
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default()
+    };
+
+    let e = err("{\"id\":42}");
+    assert!(e.contains("full (table:key) string"), "{e}");
+    assert!(e.contains("{\"tb\", \"id\"} object"), "{e}");
+    assert!(e.contains("UTF-8 bytes"), "{e}");
+
+    let e = err("{\"id\":\"nope\"}");
+    assert!(
+        e.contains("invalid record id \"nope\" (expected \"table:key\")"),
+        "{e}"
+    );
+
+    let e = err("{\"id\":{\"tb\":\"user\"}}");
+    assert!(
+        e.contains("invalid record id object: missing field `id`"),
+        "{e}"
+    );
+}
+
+#[test]
+fn test_record_id_full_error_names_input() {
+    let err = |result: Result<(), serde_json::Error>| {
+        result.err().map(|e| e.to_string()).unwrap_or_default()
+    };
+
+    let e = err(serde_json::from_str::<ContainerFull>("{\"id\":\"user123\"}").map(|_| ()));
+    assert!(
+        e.contains("invalid record id \"user123\" (expected \"table:key\")"),
+        "{e}"
+    );
+
+    let e = err(serde_json::from_str::<ContainerFullOption>("{\"id\":\"user123\"}").map(|_| ()));
+    assert!(e.contains("invalid record id \"user123\""), "{e}");
+
+    let e = err(
+        serde_json::from_str::<ContainerFullVec>("{\"ids\":[\"user:a\",\"user123\"]}").map(|_| ()),
+    );
+    assert!(e.contains("invalid record id \"user123\""), "{e}");
+    assert!(e.contains("index 1"), "{e}");
+
+    let e =
+        err(serde_json::from_str::<ContainerFullMap>("{\"counts\":{\"user123\":1}}").map(|_| ()));
+    assert!(e.contains("invalid record id \"user123\""), "{e}");
+
+    let long = "x".repeat(500);
+    let e =
+        err(serde_json::from_str::<ContainerFull>(&format!("{{\"id\":\"{long}\"}}")).map(|_| ()));
+    assert!(e.contains(&format!("\"{}\"…", "x".repeat(64))), "{e}");
+    assert!(!e.contains(&long), "{e}");
+}
+
 #[test]
 fn test_decode_payload_insecurely_success() -> Result<(), Box<dyn std::error::Error>> {
     let claims = types::SurrealJWTClaims {
@@ -126,6 +690,8 @@ fn test_decode_payload_insecurely_success() -> Result<(), Box<dyn std::error::Er
         db: "db".into(),
         ac: json!({ "role": "admin" }),
         id: "subject".into(),
+        rl: None,
+        aud: None,
     };
 
     let payload_bytes = serde_json::to_vec(&claims)?;
@@ -147,9 +713,230 @@ fn test_decode_payload_insecurely_success() -> Result<(), Box<dyn std::error::Er
 fn test_decode_payload_insecurely_errors() {
     // Missing payload
     let res = decode_payload_insecurely::<serde_json::Value>("no-dots");
-    assert!(res.is_err());
+    assert!(matches!(res, Err(JwtError::MalformedToken { parts: 1 })));
 
     // Invalid base64 in payload
     let res = decode_payload_insecurely::<serde_json::Value>("a.invalid!!.c");
-    assert!(res.is_err());
+    assert!(matches!(res, Err(JwtError::InvalidBase64(_))));
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct ContainerFullOptionEmptyAsNone {
+    #[serde(default, with = "atopio_extra::record_id_full::option_empty_as_none")]
+    id: Option<surrealdb::RecordId>,
+}
+
+#[test]
+fn test_record_id_full_option_empty_as_none() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        (r#"{"id":""}"#, None),
+        (r#"{"id":"   "}"#, None),
+        (r#"{"id":null}"#, None),
+        (r#"{}"#, None),
+        (
+            r#"{"id":"user:abc"}"#,
+            Some(surrealdb::RecordId::from_str("user:abc")?),
+        ),
+        (
+            r#"{"id":"user:⟨ padded ⟩"}"#,
+            Some(surrealdb::RecordId::from(("user", " padded "))),
+        ),
+    ];
+    for (json, expected) in cases {
+        let parsed: ContainerFullOptionEmptyAsNone = serde_json::from_str(json)?;
+        assert_eq!(parsed.id, expected, "{json}");
+    }
+
+    let empty = ContainerFullOptionEmptyAsNone { id: None };
+    assert_eq!(serde_json::to_string(&empty)?, r#"{"id":null}"#);
+    let set = ContainerFullOptionEmptyAsNone {
+        id: Some(surrealdb::RecordId::from_str("user:abc")?),
+    };
+    assert_eq!(serde_json::to_string(&set)?, r#"{"id":"user:abc"}"#);
+
+    let err = serde_json::from_str::<ContainerFullOptionEmptyAsNone>(r#"{"id":"nope"}"#)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("invalid record id \"nope\""), "{err}");
+
+    let strict = serde_json::from_str::<ContainerFullOption>(r#"{"id":""}"#);
+    assert!(strict.is_err());
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct ContainerFullOptionSurrealNone {
+    #[serde(default, with = "atopio_extra::record_id_full::option_surreal_none")]
+    id: Option<surrealdb::RecordId>,
+}
+
+#[test]
+fn test_record_id_full_option_surreal_none() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        (r#"{"id":"NONE"}"#, None),
+        (r#"{"id":"none"}"#, None),
+        (r#"{"id":"Null"}"#, None),
+        (r#"{"id":" NULL "}"#, None),
+        (r#"{"id":null}"#, None),
+        (r#"{}"#, None),
+        (
+            r#"{"id":"none:abc"}"#,
+            Some(surrealdb::RecordId::from(("none", "abc"))),
+        ),
+        (
+            r#"{"id":"user:NONE"}"#,
+            Some(surrealdb::RecordId::from(("user", "NONE"))),
+        ),
+    ];
+    for (json, expected) in cases {
+        let parsed: ContainerFullOptionSurrealNone = serde_json::from_str(json)?;
+        assert_eq!(parsed.id, expected, "{json}");
+    }
+
+    let empty = ContainerFullOptionSurrealNone { id: None };
+    assert_eq!(serde_json::to_string(&empty)?, r#"{"id":null}"#);
+
+    for json in [r#"{"id":""}"#, r#"{"id":"NONE!"}"#, r#"{"id":"nil"}"#] {
+        assert!(
+            serde_json::from_str::<ContainerFullOptionSurrealNone>(json).is_err(),
+            "{json}"
+        );
+    }
+    assert!(serde_json::from_str::<ContainerFullOption>(r#"{"id":"NONE"}"#).is_err());
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ExpectUser {
+    #[serde(
+        serialize_with = "atopio_extra::record_id_full::serialize",
+        deserialize_with = "atopio_extra::record_id_full::expect::<User, _>"
+    )]
+    user: surrealdb::RecordId,
+}
+
+#[test]
+fn test_record_id_full_expect() -> Result<(), Box<dyn std::error::Error>> {
+    let ok: ExpectUser = serde_json::from_str(r#"{"user":"user:abc"}"#)?;
+    assert_eq!(ok.user, surrealdb::RecordId::from_str("user:abc")?);
+    assert_eq!(serde_json::to_string(&ok)?, r#"{"user":"user:abc"}"#);
+
+    let error = serde_json::from_str::<ExpectUser>(r#"{"user":"post:abc"}"#)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(
+        error.contains(r#"expected table "user", got "post""#),
+        "{error}"
+    );
+
+    // The table is compared exactly.
+    assert!(serde_json::from_str::<ExpectUser>(r#"{"user":"User:abc"}"#).is_err());
+
+    // Malformed ids fail as they do with `deserialize`; bare keys are not accepted.
+    let error = serde_json::from_str::<ExpectUser>(r#"{"user":"abc"}"#)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(error.contains("invalid record id"), "{error}");
+
+    // Formats that are not human readable are checked too.
+    let mut cbor = Vec::new();
+    ciborium::into_writer(
+        &ExpectUser {
+            user: surrealdb::RecordId::from_str("post:abc")?,
+        },
+        &mut cbor,
+    )?;
+    assert!(ciborium::from_reader::<ExpectUser, _>(cbor.as_slice()).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_record_id_full_with_aliases() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Invoice {
+        #[serde(with = "atopio_extra::record_id_full::with_aliases")]
+        owner: surrealdb::RecordId,
+    }
+
+    let aliases = TableAliasMap::new().alias("accounts", "organisation");
+    let read = |json: &str| aliases.scope(|| serde_json::from_str::<Invoice>(json));
+
+    let invoice = read(r#"{"owner":"accounts:xyz"}"#)?;
+    assert_eq!(
+        serde_json::to_string(&invoice)?,
+        r#"{"owner":"organisation:xyz"}"#
+    );
+    let invoice = read(r#"{"owner":"accounts:⟨a b⟩"}"#)?;
+    assert_eq!(
+        invoice.owner,
+        surrealdb::RecordId::from_table_key("organisation", "a b")
+    );
+    let invoice = read(r#"{"owner":"accounts:42"}"#)?;
+    assert_eq!(
+        invoice.owner,
+        surrealdb::RecordId::from_table_key("organisation", 42)
+    );
+
+    // Unknown tables and the new name itself pass through untouched.
+    for json in [r#"{"owner":"user:xyz"}"#, r#"{"owner":"organisation:xyz"}"#] {
+        assert_eq!(serde_json::to_string(&read(json)?)?, json);
+    }
+
+    // Outside a scope nothing is rewritten, and the previous scope is restored after nesting.
+    let invoice: Invoice = serde_json::from_str(r#"{"owner":"accounts:xyz"}"#)?;
+    assert_eq!(invoice.owner.to_string(), "accounts:xyz");
+    let other = TableAliasMap::new().alias("accounts", "team");
+    let (inner, outer) = aliases.scope(|| {
+        let inner = other.scope(|| serde_json::from_str::<Invoice>(r#"{"owner":"accounts:1"}"#));
+        let outer = serde_json::from_str::<Invoice>(r#"{"owner":"accounts:1"}"#);
+        (inner, outer)
+    });
+    assert_eq!(inner?.owner.to_string(), "team:1");
+    assert_eq!(outer?.owner.to_string(), "organisation:1");
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(
+        &Invoice {
+            owner: "accounts:xyz".parse()?,
+        },
+        &mut bytes,
+    )?;
+    let invoice = aliases.scope(|| ciborium::from_reader::<Invoice, _>(bytes.as_slice()))?;
+    assert_eq!(invoice.owner.to_string(), "organisation:xyz");
+
+    let error = read(r#"{"owner":"accounts"}"#)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(error.contains("invalid record id"), "{error}");
+    Ok(())
+}
+
+#[test]
+fn test_table_alias_map_seed() -> Result<(), Box<dyn std::error::Error>> {
+    use serde::de::DeserializeSeed;
+
+    let aliases: TableAliasMap = [("accounts", "organisation"), ("people", "user")]
+        .into_iter()
+        .collect();
+    assert_eq!(aliases.resolve("people"), "user");
+    assert_eq!(aliases.resolve("post"), "post");
+
+    let cases = [
+        (r#""accounts:xyz""#, "organisation:xyz"),
+        (r#""people:['a', 1]""#, "user:['a', 1]"),
+        (r#""post:xyz""#, "post:xyz"),
+    ];
+    for (json, expected) in cases {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let id = aliases.seed().deserialize(&mut deserializer)?;
+        assert_eq!(id.to_string(), expected);
+    }
+
+    let mut deserializer = serde_json::Deserializer::from_str("42");
+    assert!(aliases.seed().deserialize(&mut deserializer).is_err());
+    Ok(())
 }
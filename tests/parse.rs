@@ -0,0 +1,113 @@
+use std::str::FromStr;
+
+use atopio_extra::{RecordIdParseError, parse_record_id_lenient, validate_record_id_str};
+
+#[test]
+fn test_validate_record_id_str_valid() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        ("user:abc123", "user", "abc123"),
+        ("user:42", "user", "42"),
+        ("  user:abc  ", "user", "abc"),
+        ("user:⟨has space⟩", "user", "⟨has space⟩"),
+        ("user:`has space`", "user", "`has space`"),
+        ("user:⟨a\\⟩b⟩", "user", "⟨a\\⟩b⟩"),
+        ("⟨my table⟩:abc", "my table", "abc"),
+        ("reading:['a', 1]", "reading", "['a', 1]"),
+        (
+            "user:u'018f3c1e-0000-7000-8000-000000000000'",
+            "user",
+            "u'018f3c1e-0000-7000-8000-000000000000'",
+        ),
+    ];
+
+    for (input, table, key) in cases {
+        let parts = validate_record_id_str(input)?;
+        assert_eq!(parts.table(), table, "{input}");
+        assert_eq!(parts.key_text(), key, "{input}");
+        assert_eq!(parts.record_id(), &surrealdb::RecordId::from_str(input)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_record_id_str_errors() {
+    use RecordIdParseError::*;
+
+    let cases = [
+        ("", MissingTable),
+        ("abc", MissingTable),
+        (":abc", MissingTable),
+        ("user:", EmptyKey),
+        ("user:   ", EmptyKey),
+        ("us-er:abc", InvalidTableIdent("us-er".into())),
+        ("123:abc", InvalidTableIdent("123".into())),
+        ("user:⟨unclosed", UnbalancedEscape),
+        ("user:`unclosed", UnbalancedEscape),
+        ("⟨unclosed:abc", UnbalancedEscape),
+        ("user:abc def", TrailingInput("def".into())),
+        ("user:⟨a⟩b", TrailingInput("b".into())),
+        ("user:1.5", TrailingInput(".5".into())),
+        ("user:abc-def", TrailingInput("-def".into())),
+    ];
+
+    for (input, expected) in cases {
+        assert_eq!(validate_record_id_str(input), Err(expected), "{input:?}");
+    }
+
+    for input in ["user:[1, 2", "user:éa", "user:rand()"] {
+        let result = validate_record_id_str(input);
+        assert!(
+            matches!(result, Err(InvalidKey(_))),
+            "{input:?}: {result:?}"
+        );
+    }
+
+    let err = validate_record_id_str("user:⟨unclosed")
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("unclosed"), "{err}");
+}
+
+#[test]
+fn test_parse_record_id_lenient_normalizes() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        ("user:abc", "user:abc"),
+        ("  user:abc\n", "user:abc"),
+        ("\"user:abc\"", "user:abc"),
+        ("'user:abc'", "user:abc"),
+        (" \" user:abc \" ", "user:abc"),
+        ("user:⟨has space⟩", "user:⟨has space⟩"),
+        ("user:`has space`", "user:⟨has space⟩"),
+        ("'user:`has space`'", "user:⟨has space⟩"),
+        ("user:42", "user:42"),
+    ];
+
+    for (input, expected) in cases {
+        let id = parse_record_id_lenient(input)?;
+        assert_eq!(id, surrealdb::RecordId::from_str(expected)?, "{input:?}");
+        assert_eq!(id.to_string(), expected, "{input:?}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parse_record_id_lenient_errors() {
+    use RecordIdParseError::*;
+
+    let cases = [
+        ("", MissingTable),
+        ("\"\"", MissingTable),
+        ("user:", EmptyKey),
+        ("user:abc def", TrailingInput("def".into())),
+    ];
+
+    for (input, expected) in cases {
+        assert_eq!(parse_record_id_lenient(input), Err(expected), "{input:?}");
+    }
+
+    for input in ["\"user:abc'", "''user:abc''", "`user:abc`"] {
+        assert!(parse_record_id_lenient(input).is_err(), "{input:?}");
+    }
+}
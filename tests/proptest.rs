@@ -0,0 +1,82 @@
+#![cfg(feature = "proptest")]
+
+use atopio_extra::proptest::{arb_record_id, arb_record_id_with_table, arb_surreal_claims};
+use atopio_extra::{Table, decode_payload_insecurely};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Full {
+    #[serde(with = "atopio_extra::record_id_full")]
+    id: RecordId,
+    #[serde(with = "atopio_extra::record_id_full::vec")]
+    ids: Vec<RecordId>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Naked {
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::deserialize_for::<User, _>"
+    )]
+    id: RecordId,
+}
+
+/// Builds an unsigned token around `payload`, the way SurrealDB lays it out.
+fn encode(payload: &impl Serialize) -> String {
+    let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"HS512","typ":"JWT"}"#);
+    let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(payload).unwrap());
+    format!("{header}.{payload}.c2lnbmF0dXJl")
+}
+
+proptest! {
+    #[test]
+    fn test_record_id_full_round_trips(
+        id in arb_record_id(),
+        ids in prop::collection::vec(arb_record_id(), 0..4),
+    ) {
+        let value = Full { id, ids };
+
+        let json = serde_json::to_string(&value).unwrap();
+        let back: Full = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(&back, &value, "{}", json);
+
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&value, &mut bytes).unwrap();
+        let back: Full = ciborium::from_reader(bytes.as_slice()).unwrap();
+        prop_assert_eq!(back, value);
+    }
+
+    #[test]
+    fn test_record_id_naked_round_trips(id in arb_record_id_with_table("user")) {
+        let value = Naked { id };
+
+        let json = serde_json::to_string(&value).unwrap();
+        let back: Naked = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(&back, &value, "{}", json);
+
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&value, &mut bytes).unwrap();
+        let back: Naked = ciborium::from_reader(bytes.as_slice()).unwrap();
+        prop_assert_eq!(back, value);
+    }
+
+    #[test]
+    fn test_claims_round_trip(claims in arb_surreal_claims::<(String, Vec<u32>)>()) {
+        let token = encode(&claims);
+        let back = decode_payload_insecurely::<(String, Vec<u32>)>(&token).unwrap();
+        prop_assert_eq!(
+            serde_json::to_value(&back).unwrap(),
+            serde_json::to_value(&claims).unwrap()
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use std::ops::Bound;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use atopio_extra::RecordIdRange;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ContainerRange {
+    range: RecordIdRange,
+}
+
+#[test]
+fn test_record_id_range_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let bounds = |key: i64| {
+        [
+            Bound::Unbounded,
+            Bound::Included(key.into()),
+            Bound::Excluded(key.into()),
+        ]
+    };
+
+    for start in bounds(1) {
+        for end in bounds(100) {
+            let range = RecordIdRange::new("user", start.clone(), end);
+            let s = serde_json::to_string(&ContainerRange {
+                range: range.clone(),
+            })?;
+
+            let parsed: ContainerRange = serde_json::from_str(&s)?;
+            assert_eq!(parsed.range, range, "{s}");
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_range_text() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        ("user:1..100", "user:1..100"),
+        ("user:100..", "user:100.."),
+        ("user:..100", "user:..100"),
+        ("user:1..=100", "user:1..=100"),
+        ("user:1>..100", "user:1>..100"),
+        ("user:..", "user:.."),
+        (
+            "temperature:⟨2024-01-01⟩..⟨2024-02-01⟩",
+            "temperature:⟨2024-01-01⟩..⟨2024-02-01⟩",
+        ),
+        ("reading:['a', 1]..['a', 5]", "reading:['a', 1]..['a', 5]"),
+    ];
+
+    for (input, text) in cases {
+        let range = RecordIdRange::from_str(input)?;
+        assert_eq!(range.to_string(), text);
+    }
+
+    let range = RecordIdRange::from_str("user:1..=100")?;
+    assert_eq!(range.table(), "user");
+    assert_eq!(range.start(), &Bound::Included(1.into()));
+    assert_eq!(range.end(), &Bound::Included(100.into()));
+
+    let range = RecordIdRange::from_str("temperature:⟨2024-01-01⟩..")?;
+    assert_eq!(range.start(), &Bound::Included("2024-01-01".into()));
+    assert_eq!(range.end(), &Bound::Unbounded);
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_range_errors() {
+    for input in ["user:1", "user", "1..100", "", "user:1..100; DELETE user"] {
+        assert!(RecordIdRange::from_str(input).is_err(), "{input}");
+    }
+
+    let err = serde_json::from_str::<ContainerRange>("{\"range\":\"user:1\"}")
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("invalid record id range"), "{err}");
+}
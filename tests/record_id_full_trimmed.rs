@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Base {
+    #[serde(with = "atopio_extra::record_id_full")]
+    id: surrealdb::RecordId,
+    #[serde(with = "atopio_extra::record_id_full::option")]
+    parent: Option<surrealdb::RecordId>,
+    #[serde(with = "atopio_extra::record_id_full::vec")]
+    friends: Vec<surrealdb::RecordId>,
+    #[serde(with = "atopio_extra::record_id_full::option_vec")]
+    tags: Option<Vec<surrealdb::RecordId>>,
+    #[serde(with = "atopio_extra::record_id_full::map_key")]
+    scores: HashMap<surrealdb::RecordId, u32>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Trimmed {
+    #[serde(with = "atopio_extra::record_id_full::trimmed")]
+    id: surrealdb::RecordId,
+    #[serde(with = "atopio_extra::record_id_full::trimmed::option")]
+    parent: Option<surrealdb::RecordId>,
+    #[serde(with = "atopio_extra::record_id_full::trimmed::vec")]
+    friends: Vec<surrealdb::RecordId>,
+    #[serde(with = "atopio_extra::record_id_full::trimmed::option_vec")]
+    tags: Option<Vec<surrealdb::RecordId>>,
+    #[serde(with = "atopio_extra::record_id_full::trimmed::map_key")]
+    scores: HashMap<surrealdb::RecordId, u32>,
+}
+
+#[test]
+fn test_record_id_full_trimmed_accepts_padding() -> Result<(), Box<dyn std::error::Error>> {
+    let json = r#"{
+        "id": "user:abc ",
+        "parent": "\t user:42\r\n",
+        "friends": [" user:a", "user:b  "],
+        "tags": [" tag:rust "],
+        "scores": {" user:abc ": 7}
+    }"#;
+    let row: Trimmed = serde_json::from_str(json)?;
+    assert_eq!(row.id, surrealdb::RecordId::from_str("user:abc")?);
+    assert_eq!(row.parent, Some(surrealdb::RecordId::from(("user", 42))));
+    assert_eq!(
+        row.friends,
+        vec![
+            surrealdb::RecordId::from_str("user:a")?,
+            surrealdb::RecordId::from_str("user:b")?,
+        ]
+    );
+    assert_eq!(
+        row.tags,
+        Some(vec![surrealdb::RecordId::from_str("tag:rust")?])
+    );
+    assert_eq!(
+        row.scores,
+        HashMap::from([(surrealdb::RecordId::from_str("user:abc")?, 7)])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_full_trimmed_keeps_escaped_whitespace() -> Result<(), Box<dyn std::error::Error>>
+{
+    let row: Trimmed = serde_json::from_str(
+        r#"{"id":"  user:⟨ padded ⟩ ","parent":null,"friends":[" user:` x `"],"tags":null,"scores":{}}"#,
+    )?;
+    assert_eq!(row.id, surrealdb::RecordId::from(("user", " padded ")));
+    assert_eq!(
+        row.friends,
+        vec![surrealdb::RecordId::from(("user", " x "))]
+    );
+    assert_eq!(row.parent, None);
+    assert_eq!(row.tags, None);
+    Ok(())
+}
+
+#[test]
+fn test_record_id_full_trimmed_errors() {
+    let cases = [
+        (
+            r#"{"id":"   ","parent":null,"friends":[],"tags":null,"scores":{}}"#,
+            "invalid record id \"\"",
+        ),
+        (
+            r#"{"id":"user:a","parent":null,"friends":["user:b"," nope "],"tags":null,"scores":{}}"#,
+            "invalid record id \"nope\" (expected \"table:key\") at index 1",
+        ),
+        (
+            r#"{"id":"user:a","parent":null,"friends":[],"tags":null,"scores":{"user:a":1," user:a":2}}"#,
+            "duplicate record id key",
+        ),
+    ];
+    for (json, expected) in cases {
+        let err = serde_json::from_str::<Trimmed>(json)
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default();
+        assert!(err.contains(expected), "{json}: {err}");
+    }
+}
+
+/// Record ids already in their canonical form, built from every combination of a few tables
+/// and keys of each kind.
+fn clean_ids() -> Vec<surrealdb::RecordId> {
+    let tables = ["user", "post_2024", "`my table`", "⟨odd-table⟩"];
+    let keys = [
+        "abc",
+        "42",
+        "-7",
+        "⟨has space⟩",
+        "⟨ padded ⟩",
+        "`tick`",
+        "u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'",
+        "['sensor1', 1717000000]",
+        "{ city: 'london', year: 2024 }",
+        "⟨with \"quote\"⟩",
+    ];
+    let mut ids = Vec::new();
+    for table in tables {
+        for key in keys {
+            let text = format!("{table}:{key}");
+            let id = surrealdb::RecordId::from_str(&text)
+                .unwrap_or_else(|_| panic!("{text} should be a record id"));
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+#[test]
+fn test_record_id_full_trimmed_matches_base_on_clean_input()
+-> Result<(), Box<dyn std::error::Error>> {
+    let ids = clean_ids();
+    for (i, id) in ids.iter().enumerate() {
+        let base = Base {
+            id: id.clone(),
+            parent: (i % 2 == 0).then(|| id.clone()),
+            friends: ids[..i % 5].to_vec(),
+            tags: (i % 3 == 0).then(|| ids[i..].iter().take(3).cloned().collect()),
+            scores: HashMap::from([(id.clone(), i as u32)]),
+        };
+        let trimmed = Trimmed {
+            id: base.id.clone(),
+            parent: base.parent.clone(),
+            friends: base.friends.clone(),
+            tags: base.tags.clone(),
+            scores: base.scores.clone(),
+        };
+
+        let json = serde_json::to_string(&base)?;
+        assert_eq!(serde_json::to_string(&trimmed)?, json, "{id}");
+        assert_eq!(serde_json::from_str::<Trimmed>(&json)?, trimmed, "{id}");
+        assert_eq!(serde_json::from_str::<Base>(&json)?, base, "{id}");
+    }
+
+    for input in [
+        "",
+        "user",
+        ":abc",
+        "user:",
+        "user:abc def",
+        "user:⟨unclosed",
+    ] {
+        let json =
+            format!(r#"{{"id":{input:?},"parent":null,"friends":[],"tags":null,"scores":{{}}}}"#);
+        assert!(serde_json::from_str::<Base>(&json).is_err(), "{input:?}");
+        assert!(serde_json::from_str::<Trimmed>(&json).is_err(), "{input:?}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_record_id_full_trimmed_binary_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let row = Trimmed {
+        id: surrealdb::RecordId::from_str("user:abc")?,
+        parent: Some(surrealdb::RecordId::from(("user", 42))),
+        friends: vec![surrealdb::RecordId::from_str("user:⟨has space⟩")?],
+        tags: None,
+        scores: HashMap::from([(surrealdb::RecordId::from_str("user:abc")?, 7)]),
+    };
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&row, &mut bytes)?;
+    assert_eq!(ciborium::from_reader::<Trimmed, _>(bytes.as_slice())?, row);
+    Ok(())
+}
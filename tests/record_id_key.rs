@@ -0,0 +1,77 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ContainerKey {
+    #[serde(with = "atopio_extra::record_id_key")]
+    key: surrealdb::RecordIdKey,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ContainerKeyMany {
+    #[serde(default, with = "atopio_extra::record_id_key::option")]
+    parent: Option<surrealdb::RecordIdKey>,
+    #[serde(with = "atopio_extra::record_id_key::vec")]
+    tags: Vec<surrealdb::RecordIdKey>,
+}
+
+#[test]
+fn test_record_id_key_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        ("t:abc123", "\"abc123\""),
+        ("t:⟨has space⟩", "\"has space\""),
+        ("t:⟨007⟩", "\"007\""),
+        ("t:42", "42"),
+        ("t:-1", "-1"),
+        (
+            "t:u'018f3c1e-0000-7000-8000-000000000000'",
+            "\"018f3c1e-0000-7000-8000-000000000000\"",
+        ),
+        ("t:['sensor1', 1717000000]", "[\"sensor1\",1717000000]"),
+        ("t:{ city: 'london' }", "{\"city\":\"london\"}"),
+    ];
+
+    for (raw, json) in cases {
+        let key = surrealdb::RecordId::from_str(raw)?.key().clone();
+        let s = serde_json::to_string(&ContainerKey { key: key.clone() })?;
+        assert_eq!(s, format!("{{\"key\":{json}}}"));
+
+        let parsed: ContainerKey = serde_json::from_str(&s)?;
+        assert_eq!(parsed.key, key, "{raw}");
+    }
+
+    for json in ["{\"key\":null}", "{\"key\":true}", "{\"key\":1.5}"] {
+        assert!(
+            serde_json::from_str::<ContainerKey>(json).is_err(),
+            "{json}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_key_option_and_vec() -> Result<(), Box<dyn std::error::Error>> {
+    let c = ContainerKeyMany {
+        parent: Some(surrealdb::RecordIdKey::from(7)),
+        tags: vec![
+            surrealdb::RecordIdKey::from("a"),
+            surrealdb::RecordIdKey::from(1),
+        ],
+    };
+
+    let s = serde_json::to_string(&c)?;
+    assert_eq!(s, "{\"parent\":7,\"tags\":[\"a\",1]}");
+
+    let parsed: ContainerKeyMany = serde_json::from_str(&s)?;
+    assert_eq!(parsed.parent, c.parent);
+    assert_eq!(parsed.tags, c.tags);
+
+    let parsed: ContainerKeyMany = serde_json::from_str("{\"tags\":[]}")?;
+    assert_eq!(parsed.parent, None);
+
+    assert!(serde_json::from_str::<ContainerKeyMany>("{\"tags\":[null]}").is_err());
+
+    Ok(())
+}
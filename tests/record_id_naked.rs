@@ -0,0 +1,687 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use atopio_extra::{Table, types::Naked};
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerNaked {
+    #[serde(with = "atopio_extra::record_id_naked")]
+    id: surrealdb::RecordId,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerNakedFor {
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::deserialize_for::<User, _>"
+    )]
+    id: surrealdb::RecordId,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerNakedOption {
+    #[serde(default, with = "atopio_extra::record_id_naked::option")]
+    id: Option<surrealdb::RecordId>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerNakedOptionFor {
+    #[serde(
+        default,
+        serialize_with = "atopio_extra::record_id_naked::option::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::option::deserialize_for::<User, _>"
+    )]
+    id: Option<surrealdb::RecordId>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerNakedVec {
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::vec::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::vec::deserialize_for::<User, _>"
+    )]
+    ids: Vec<surrealdb::RecordId>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerNakedOptionVec {
+    #[serde(
+        default,
+        serialize_with = "atopio_extra::record_id_naked::option_vec::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::option_vec::deserialize_for::<User, _>"
+    )]
+    ids: Option<Vec<surrealdb::RecordId>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerNakedMap {
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::map_key::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::map_key::deserialize_for::<User, _, _, _>"
+    )]
+    counts: HashMap<surrealdb::RecordId, u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerNakedWrapper {
+    id: Naked<User>,
+}
+
+#[test]
+fn test_record_id_naked_with_points_to_table_aware_forms() -> Result<(), Box<dyn std::error::Error>>
+{
+    let id = surrealdb::RecordId::from_str("user:abc123")?;
+    let s = serde_json::to_string(&ContainerNaked { id: id.clone() })?;
+    assert_eq!(s, "{\"id\":\"abc123\"}");
+
+    for json in [s.as_str(), "{\"id\":\"user:abc123\"}", "{\"id\":42}"] {
+        let err = serde_json::from_str::<ContainerNaked>(json)
+            .err()
+            .ok_or("read a naked key without its table")?;
+        let message = err.to_string();
+        assert!(
+            message.contains("deserialize_for") && message.contains("Naked<T>"),
+            "{message}"
+        );
+    }
+    let err = serde_json::from_str::<ContainerNakedOption>("{\"id\":\"abc123\"}").err();
+    assert!(err.is_some_and(|e| e.to_string().contains("Naked<T>")));
+
+    // Formats that are not human readable carry the whole id, so they round-trip.
+    let bytes = postcard::to_allocvec(&ContainerNaked { id: id.clone() })?;
+    assert_eq!(postcard::from_bytes::<ContainerNaked>(&bytes)?.id, id);
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_naked_deserialize_for_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    for raw in [
+        "user:abc123",
+        "user:42",
+        "user:⟨42⟩",
+        "user:⟨has space⟩",
+        "user:['a', 1]",
+    ] {
+        let id = surrealdb::RecordId::from_str(raw)?;
+        let s = serde_json::to_string(&ContainerNakedFor { id: id.clone() })?;
+
+        let parsed: ContainerNakedFor = serde_json::from_str(&s)?;
+        assert_eq!(parsed.id, id);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_naked_deserialize_for_numeric_keys() -> Result<(), Box<dyn std::error::Error>> {
+    let parsed: ContainerNakedFor = serde_json::from_str("{\"id\":42}")?;
+    assert_eq!(parsed.id, surrealdb::RecordId::from(("user", 42)));
+
+    let parsed: ContainerNakedFor = serde_json::from_str("{\"id\":\"42\"}")?;
+    assert_eq!(parsed.id, surrealdb::RecordId::from(("user", 42)));
+
+    assert!(serde_json::from_str::<ContainerNakedFor>("{\"id\":18446744073709551615}").is_err());
+    assert!(serde_json::from_str::<ContainerNakedFor>("{\"id\":1.5}").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_naked_deserialize_for_colon_key() -> Result<(), Box<dyn std::error::Error>> {
+    let parsed: ContainerNakedFor = serde_json::from_str("{\"id\":\"post:abc\"}")?;
+    assert_eq!(parsed.id.table(), "user");
+    assert_eq!(parsed.id, surrealdb::RecordId::from(("user", "post:abc")));
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_naked_deserialize_for_errors() {
+    assert!(serde_json::from_str::<ContainerNakedFor>("{\"id\":\"\"}").is_err());
+    assert!(serde_json::from_str::<ContainerNakedFor>("{\"id\":null}").is_err());
+    assert!(serde_json::from_str::<ContainerNakedFor>("{\"id\":[\"abc\"]}").is_err());
+}
+
+#[test]
+fn test_naked_wrapper_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let container = ContainerNakedWrapper {
+        id: Naked::new("abc123"),
+    };
+
+    let s = serde_json::to_string(&container)?;
+    assert_eq!(s, "{\"id\":\"abc123\"}");
+
+    let parsed: ContainerNakedWrapper = serde_json::from_str(&s)?;
+    assert_eq!(parsed.id, container.id);
+    assert_eq!(
+        surrealdb::RecordId::from(parsed.id),
+        surrealdb::RecordId::from_str("user:abc123")?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_naked_option_module() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:naked2")?;
+
+    let s = serde_json::to_string(&ContainerNakedOption {
+        id: Some(id.clone()),
+    })?;
+    assert_eq!(s, "{\"id\":\"naked2\"}");
+
+    let s = serde_json::to_string(&ContainerNakedOption { id: None })?;
+    assert_eq!(s, "{\"id\":null}");
+
+    let parsed: ContainerNakedOption = serde_json::from_str("{\"id\":null}")?;
+    assert!(parsed.id.is_none());
+    let bytes = postcard::to_allocvec(&ContainerNakedOption {
+        id: Some(id.clone()),
+    })?;
+    let parsed: ContainerNakedOption = postcard::from_bytes(&bytes)?;
+    assert_eq!(parsed.id, Some(id));
+
+    let parsed: ContainerNakedOption = serde_json::from_str("{}")?;
+    assert!(parsed.id.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_naked_option_for() -> Result<(), Box<dyn std::error::Error>> {
+    let parsed: ContainerNakedOptionFor = serde_json::from_str("{}")?;
+    assert_eq!(parsed.id, None);
+
+    let parsed: ContainerNakedOptionFor = serde_json::from_str("{\"id\":null}")?;
+    assert_eq!(parsed.id, None);
+
+    let parsed: ContainerNakedOptionFor = serde_json::from_str("{\"id\":\"abc123\"}")?;
+    assert_eq!(
+        parsed.id,
+        Some(surrealdb::RecordId::from_str("user:abc123")?)
+    );
+
+    let parsed: ContainerNakedOptionFor = serde_json::from_str("{\"id\":42}")?;
+    assert_eq!(parsed.id, Some(surrealdb::RecordId::from(("user", 42))));
+
+    let err = serde_json::from_str::<ContainerNakedOptionFor>("{\"id\":\"\"}")
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("non-empty"), "{err}");
+    assert!(serde_json::from_str::<ContainerNakedOptionFor>("{\"id\":true}").is_err());
+
+    let id = surrealdb::RecordId::from_str("user:⟨has space⟩")?;
+    let s = serde_json::to_string(&ContainerNakedOptionFor {
+        id: Some(id.clone()),
+    })?;
+    assert_eq!(s, "{\"id\":\"has space\"}");
+    let parsed: ContainerNakedOptionFor = serde_json::from_str(&s)?;
+    assert_eq!(parsed.id, Some(id));
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_naked_vec_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let ids = vec![
+        surrealdb::RecordId::from_str("user:abc")?,
+        surrealdb::RecordId::from_str("user:42")?,
+        surrealdb::RecordId::from_str("user:def")?,
+    ];
+
+    let s = serde_json::to_string(&ContainerNakedVec { ids: ids.clone() })?;
+    assert_eq!(s, "{\"ids\":[\"abc\",\"42\",\"def\"]}");
+
+    let parsed: ContainerNakedVec = serde_json::from_str(&s)?;
+    assert_eq!(parsed.ids, ids);
+
+    let parsed: ContainerNakedVec = serde_json::from_str("{\"ids\":[\"abc\",42,\"def\"]}")?;
+    assert_eq!(parsed.ids, ids);
+
+    let parsed: ContainerNakedVec = serde_json::from_str("{\"ids\":[]}")?;
+    assert!(parsed.ids.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_naked_vec_errors() {
+    let err = serde_json::from_str::<ContainerNakedVec>("{\"ids\":[\"abc\",1.5]}")
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("index 1"), "{err}");
+
+    assert!(serde_json::from_str::<ContainerNakedVec>("{\"ids\":\"abc\"}").is_err());
+}
+
+#[test]
+fn test_record_id_naked_option_vec() -> Result<(), Box<dyn std::error::Error>> {
+    let ids = vec![
+        surrealdb::RecordId::from_str("user:abc")?,
+        surrealdb::RecordId::from_str("user:7")?,
+    ];
+
+    let s = serde_json::to_string(&ContainerNakedOptionVec {
+        ids: Some(ids.clone()),
+    })?;
+    assert_eq!(s, "{\"ids\":[\"abc\",\"7\"]}");
+
+    let parsed: ContainerNakedOptionVec = serde_json::from_str(&s)?;
+    assert_eq!(parsed.ids, Some(ids));
+
+    let parsed: ContainerNakedOptionVec = serde_json::from_str("{\"ids\":null}")?;
+    assert_eq!(parsed.ids, None);
+
+    let parsed: ContainerNakedOptionVec = serde_json::from_str("{}")?;
+    assert_eq!(parsed.ids, None);
+
+    let parsed: ContainerNakedOptionVec = serde_json::from_str("{\"ids\":[]}")?;
+    assert_eq!(parsed.ids, Some(vec![]));
+
+    assert!(serde_json::from_str::<ContainerNakedOptionVec>("{\"ids\":\"abc\"}").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_naked_map_key_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let c = ContainerNakedMap {
+        counts: HashMap::from([(surrealdb::RecordId::from_str("user:abc123")?, 1)]),
+    };
+    let s = serde_json::to_string(&c)?;
+    assert_eq!(s, "{\"counts\":{\"abc123\":1}}");
+
+    let parsed: ContainerNakedMap =
+        serde_json::from_str("{\"counts\":{\"abc123\":1,\"42\":2,\"⟨42⟩\":3}}")?;
+    assert_eq!(parsed.counts.len(), 3);
+    assert_eq!(
+        parsed.counts[&surrealdb::RecordId::from(("user", "abc123"))],
+        1
+    );
+    assert_eq!(parsed.counts[&surrealdb::RecordId::from(("user", 42))], 2);
+    assert_eq!(parsed.counts[&surrealdb::RecordId::from(("user", "42"))], 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_naked_map_key_collision() -> Result<(), Box<dyn std::error::Error>> {
+    let c = ContainerNakedMap {
+        counts: HashMap::from([
+            (surrealdb::RecordId::from_str("user:abc")?, 1),
+            (surrealdb::RecordId::from_str("post:abc")?, 2),
+        ]),
+    };
+    let err = serde_json::to_string(&c)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("both serialize to the naked key"), "{err}");
+
+    let c = ContainerNakedMap {
+        counts: HashMap::from([
+            (surrealdb::RecordId::from_str("user:1")?, 1),
+            (surrealdb::RecordId::from_str("post:2")?, 2),
+        ]),
+    };
+    assert!(serde_json::to_string(&c).is_ok());
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ContainerNakedTyped {
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::typed::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::typed::deserialize_for::<User, _>"
+    )]
+    id: surrealdb::RecordId,
+}
+
+#[test]
+fn test_record_id_naked_typed_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        (surrealdb::RecordId::from(("user", 42)), "42"),
+        (surrealdb::RecordId::from(("user", 0)), "0"),
+        (
+            surrealdb::RecordId::from(("user", i64::MAX)),
+            "9223372036854775807",
+        ),
+        (
+            surrealdb::RecordId::from(("user", i64::MIN)),
+            "-9223372036854775808",
+        ),
+        (surrealdb::RecordId::from(("user", "abc123")), "\"abc123\""),
+        (surrealdb::RecordId::from(("user", "007")), "\"007\""),
+        (surrealdb::RecordId::from(("user", "42")), "\"42\""),
+        (
+            surrealdb::RecordId::from_str("user:u'018f3c1e-0000-7000-8000-000000000000'")?,
+            "\"u'018f3c1e-0000-7000-8000-000000000000'\"",
+        ),
+    ];
+
+    for (id, json) in cases {
+        let s = serde_json::to_string(&ContainerNakedTyped { id: id.clone() })?;
+        assert_eq!(s, format!("{{\"id\":{json}}}"));
+
+        let parsed: ContainerNakedTyped = serde_json::from_str(&s)?;
+        assert_eq!(parsed.id, id, "{json}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_naked_typed_errors() {
+    for json in [
+        "{\"id\":9223372036854775808}",
+        "{\"id\":1.5}",
+        "{\"id\":\"\"}",
+        "{\"id\":null}",
+        "{\"id\":true}",
+    ] {
+        assert!(
+            serde_json::from_str::<ContainerNakedTyped>(json).is_err(),
+            "{json}"
+        );
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ContainerNakedValue {
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::value::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::value::deserialize_for::<User, _>"
+    )]
+    id: surrealdb::RecordId,
+}
+
+#[test]
+fn test_record_id_naked_value_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        ("user:abc123", "\"abc123\""),
+        ("user:⟨has space⟩", "\"has space\""),
+        ("user:⟨42⟩", "\"42\""),
+        ("user:42", "42"),
+        (
+            "user:u'018f3c1e-0000-7000-8000-000000000000'",
+            "\"018f3c1e-0000-7000-8000-000000000000\"",
+        ),
+        ("user:['sensor1', 1717000000]", "[\"sensor1\",1717000000]"),
+        (
+            "user:{ city: 'london', tags: ['a', 'b'] }",
+            "{\"city\":\"london\",\"tags\":[\"a\",\"b\"]}",
+        ),
+    ];
+
+    for (raw, json) in cases {
+        let id = surrealdb::RecordId::from_str(raw)?;
+        let s = serde_json::to_string(&ContainerNakedValue { id: id.clone() })?;
+        assert_eq!(s, format!("{{\"id\":{json}}}"));
+
+        let parsed: ContainerNakedValue = serde_json::from_str(&s)?;
+        assert_eq!(parsed.id, id, "{raw}");
+    }
+
+    let parsed: ContainerNakedValue =
+        serde_json::from_str("{\"id\":\"018F3C1E-0000-7000-8000-000000000000\"}")?;
+    assert_eq!(
+        parsed.id,
+        surrealdb::RecordId::from(("user", "018F3C1E-0000-7000-8000-000000000000"))
+    );
+
+    for json in [
+        "{\"id\":null}",
+        "{\"id\":false}",
+        "{\"id\":1.5}",
+        "{\"id\":[\"nul\\u0000\"]}",
+    ] {
+        assert!(
+            serde_json::from_str::<ContainerNakedValue>(json).is_err(),
+            "{json}"
+        );
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ContainerNakedUuid {
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::uuid::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::uuid::deserialize_for::<User, _>"
+    )]
+    id: surrealdb::RecordId,
+}
+
+#[test]
+fn test_record_id_naked_uuid() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:u'018f3c1e-0000-7000-8000-00000000abcd'")?;
+
+    let s = serde_json::to_string(&ContainerNakedUuid { id: id.clone() })?;
+    assert_eq!(s, "{\"id\":\"018f3c1e-0000-7000-8000-00000000abcd\"}");
+
+    for json in [
+        "\"018f3c1e-0000-7000-8000-00000000abcd\"",
+        "\"018F3C1E-0000-7000-8000-00000000ABCD\"",
+        "\"{018f3c1e-0000-7000-8000-00000000abcd}\"",
+        "\"018f3c1e00007000800000000000abcd\"",
+        "\"urn:uuid:018f3c1e-0000-7000-8000-00000000abcd\"",
+    ] {
+        let parsed: ContainerNakedUuid = serde_json::from_str(&format!("{{\"id\":{json}}}"))?;
+        assert_eq!(parsed.id, id, "{json}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_naked_uuid_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let err = serde_json::to_string(&ContainerNakedUuid {
+        id: surrealdb::RecordId::from_str("user:abc")?,
+    })
+    .err()
+    .map(|e| e.to_string())
+    .unwrap_or_default();
+    assert!(err.contains("does not have a uuid key"), "{err}");
+
+    for json in ["{\"id\":\"abc\"}", "{\"id\":42}", "{\"id\":null}"] {
+        assert!(
+            serde_json::from_str::<ContainerNakedUuid>(json).is_err(),
+            "{json}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_naked_raw_string_keys() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        ("has space", "has space"),
+        ("a:b", "a:b"),
+        ("user:abc", "user:abc"),
+        ("emoji 🦀", "emoji 🦀"),
+        ("🦀", "🦀"),
+        ("abc123", "abc123"),
+        ("42", "⟨42⟩"),
+        ("rand()", "rand()"),
+    ];
+
+    for (raw, text) in cases {
+        let id = surrealdb::RecordId::from(("user", raw));
+        let s = serde_json::to_string(&ContainerNakedFor { id: id.clone() })?;
+        assert_eq!(s, format!("{{\"id\":\"{text}\"}}"));
+
+        let parsed: ContainerNakedFor = serde_json::from_str(&s)?;
+        assert_eq!(parsed.id, id, "{raw}");
+    }
+
+    let parsed: ContainerNakedFor = serde_json::from_str("{\"id\":\"⟨has space⟩\"}")?;
+    assert_eq!(parsed.id, surrealdb::RecordId::from(("user", "has space")));
+
+    let parsed: ContainerNakedFor = serde_json::from_str("{\"id\":\"`has space`\"}")?;
+    assert_eq!(parsed.id, surrealdb::RecordId::from(("user", "has space")));
+
+    Ok(())
+}
+
+struct Customer;
+
+impl Table for Customer {
+    const NAME: &'static str = "customer";
+}
+
+struct Order;
+
+impl Table for Order {
+    const NAME: &'static str = "order";
+    const KEY_KIND: atopio_extra::KeyKind = atopio_extra::KeyKind::Integer;
+}
+
+struct Device;
+
+impl Table for Device {
+    const NAME: &'static str = "device";
+    const KEY_KIND: atopio_extra::KeyKind = atopio_extra::KeyKind::Uuid;
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FlexibleCustomer {
+    #[serde(
+        serialize_with = "atopio_extra::record_id_naked::flexible::serialize",
+        deserialize_with = "atopio_extra::record_id_naked::flexible::deserialize_for::<Customer, _>"
+    )]
+    id: surrealdb::RecordId,
+}
+
+#[derive(Debug, Deserialize)]
+struct FlexibleOrder {
+    #[serde(
+        deserialize_with = "atopio_extra::record_id_naked::flexible::deserialize_for::<Order, _>"
+    )]
+    id: surrealdb::RecordId,
+}
+
+#[derive(Debug, Deserialize)]
+struct FlexibleDevice {
+    #[serde(
+        deserialize_with = "atopio_extra::record_id_naked::flexible::deserialize_for::<Device, _>"
+    )]
+    id: surrealdb::RecordId,
+}
+
+#[test]
+fn test_flexible_string_table() -> Result<(), Box<dyn std::error::Error>> {
+    let from_number: FlexibleCustomer = serde_json::from_str(r#"{"id":12345}"#)?;
+    let from_string: FlexibleCustomer = serde_json::from_str(r#"{"id":"12345"}"#)?;
+    assert_eq!(
+        from_number.id,
+        surrealdb::RecordId::from(("customer", "12345"))
+    );
+    assert_eq!(from_string.id, from_number.id);
+
+    let max: FlexibleCustomer = serde_json::from_str(&format!(r#"{{"id":{}}}"#, i64::MAX))?;
+    assert_eq!(
+        max.id,
+        surrealdb::RecordId::from(("customer", i64::MAX.to_string()))
+    );
+    let min: FlexibleCustomer = serde_json::from_str(&format!(r#"{{"id":{}}}"#, i64::MIN))?;
+    assert_eq!(
+        min.id,
+        surrealdb::RecordId::from(("customer", i64::MIN.to_string()))
+    );
+
+    // Strings are kept exactly, leading zeros included.
+    let zeros: FlexibleCustomer = serde_json::from_str(r#"{"id":"00123"}"#)?;
+    assert_eq!(zeros.id, surrealdb::RecordId::from(("customer", "00123")));
+    let text: FlexibleCustomer = serde_json::from_str(r#"{"id":"abc"}"#)?;
+    assert_eq!(text.id, surrealdb::RecordId::from(("customer", "abc")));
+
+    assert_eq!(serde_json::to_string(&from_number)?, r#"{"id":"12345"}"#);
+    Ok(())
+}
+
+#[test]
+fn test_flexible_integer_table() -> Result<(), Box<dyn std::error::Error>> {
+    let from_number: FlexibleOrder = serde_json::from_str(r#"{"id":12345}"#)?;
+    let from_string: FlexibleOrder = serde_json::from_str(r#"{"id":"12345"}"#)?;
+    assert_eq!(from_number.id, surrealdb::RecordId::from(("order", 12345)));
+    assert_eq!(from_string.id, from_number.id);
+
+    let max: FlexibleOrder = serde_json::from_str(&format!(r#"{{"id":"{}"}}"#, i64::MAX))?;
+    assert_eq!(max.id, surrealdb::RecordId::from(("order", i64::MAX)));
+    let negative: FlexibleOrder = serde_json::from_str(r#"{"id":"-7"}"#)?;
+    assert_eq!(negative.id, surrealdb::RecordId::from(("order", -7)));
+
+    // Digit strings that are not the canonical text of an integer are never turned into one.
+    for input in [
+        r#"{"id":"00123"}"#,
+        r#"{"id":"+5"}"#,
+        r#"{"id":"abc"}"#,
+        r#"{"id":"9223372036854775808"}"#,
+    ] {
+        let error = serde_json::from_str::<FlexibleOrder>(input)
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default();
+        assert!(error.contains("without leading zeros"), "{input}: {error}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_flexible_rejects_floats_and_overflow() {
+    for input in [
+        r#"{"id":1.5}"#,
+        r#"{"id":1e3}"#,
+        r#"{"id":true}"#,
+        r#"{"id":""}"#,
+    ] {
+        assert!(
+            serde_json::from_str::<FlexibleCustomer>(input).is_err(),
+            "{input}"
+        );
+        assert!(
+            serde_json::from_str::<FlexibleOrder>(input).is_err(),
+            "{input}"
+        );
+    }
+    let error = serde_json::from_str::<FlexibleCustomer>(r#"{"id":1.5}"#)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(error.contains("floating point"));
+
+    let too_big = format!(r#"{{"id":{}}}"#, u64::MAX);
+    assert!(serde_json::from_str::<FlexibleCustomer>(&too_big).is_err());
+    assert!(serde_json::from_str::<FlexibleOrder>(&too_big).is_err());
+}
+
+#[test]
+fn test_flexible_other_kinds() -> Result<(), Box<dyn std::error::Error>> {
+    let device: FlexibleDevice =
+        serde_json::from_str(r#"{"id":"u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'"}"#)?;
+    assert_eq!(
+        device.id,
+        surrealdb::RecordId::from_str("device:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'")?
+    );
+
+    let error = serde_json::from_str::<FlexibleDevice>(r#"{"id":42}"#)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(error.contains("expected a uuid record id key"), "{error}");
+    Ok(())
+}
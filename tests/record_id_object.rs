@@ -0,0 +1,102 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ContainerObject {
+    #[serde(with = "atopio_extra::record_id_object")]
+    id: surrealdb::RecordId,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ContainerObjectMany {
+    #[serde(default, with = "atopio_extra::record_id_object::option")]
+    parent: Option<surrealdb::RecordId>,
+    #[serde(with = "atopio_extra::record_id_object::vec")]
+    tags: Vec<surrealdb::RecordId>,
+}
+
+#[test]
+fn test_record_id_object_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let cases = [
+        ("user:abc123", "{\"table\":\"user\",\"key\":\"abc123\"}"),
+        ("order:42", "{\"table\":\"order\",\"key\":42}"),
+        ("order:⟨42⟩", "{\"table\":\"order\",\"key\":\"42\"}"),
+        (
+            "user:⟨has space⟩",
+            "{\"table\":\"user\",\"key\":\"has space\"}",
+        ),
+        (
+            "user:u'018f3c1e-0000-7000-8000-000000000000'",
+            "{\"table\":\"user\",\"key\":\"018f3c1e-0000-7000-8000-000000000000\"}",
+        ),
+        (
+            "reading:['sensor1', 1717000000]",
+            "{\"table\":\"reading\",\"key\":[\"sensor1\",1717000000]}",
+        ),
+        (
+            "event:{ city: 'london', year: 2024 }",
+            "{\"table\":\"event\",\"key\":{\"city\":\"london\",\"year\":2024}}",
+        ),
+    ];
+
+    for (raw, json) in cases {
+        let id = surrealdb::RecordId::from_str(raw)?;
+        let s = serde_json::to_string(&ContainerObject { id: id.clone() })?;
+        assert_eq!(s, format!("{{\"id\":{json}}}"));
+
+        let parsed: ContainerObject = serde_json::from_str(&s)?;
+        assert_eq!(parsed.id, id, "{raw}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_record_id_object_errors() {
+    for json in [
+        "{\"id\":{\"table\":\"user\"}}",
+        "{\"id\":{\"key\":\"abc\"}}",
+        "{\"id\":{\"table\":\"user\",\"key\":\"abc\",\"extra\":1}}",
+        "{\"id\":{\"table\":\"user\",\"key\":null}}",
+        "{\"id\":{\"table\":\"user\",\"key\":1.5}}",
+        "{\"id\":\"user:abc\"}",
+    ] {
+        assert!(
+            serde_json::from_str::<ContainerObject>(json).is_err(),
+            "{json}"
+        );
+    }
+
+    let err = serde_json::from_str::<ContainerObject>(
+        "{\"id\":{\"table\":\"user\",\"key\":\"abc\",\"extra\":1}}",
+    )
+    .err()
+    .map(|e| e.to_string())
+    .unwrap_or_default();
+    assert!(err.contains("unknown field `extra`"), "{err}");
+}
+
+#[test]
+fn test_record_id_object_option_and_vec() -> Result<(), Box<dyn std::error::Error>> {
+    let c = ContainerObjectMany {
+        parent: None,
+        tags: vec![surrealdb::RecordId::from_str("tag:1")?],
+    };
+
+    let s = serde_json::to_string(&c)?;
+    assert_eq!(
+        s,
+        "{\"parent\":null,\"tags\":[{\"table\":\"tag\",\"key\":1}]}"
+    );
+
+    let parsed: ContainerObjectMany =
+        serde_json::from_str("{\"tags\":[],\"parent\":{\"table\":\"user\",\"key\":\"a\"}}")?;
+    assert_eq!(
+        parsed.parent,
+        Some(surrealdb::RecordId::from_str("user:a")?)
+    );
+    assert!(parsed.tags.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AuditEvent {
+    #[serde(with = "atopio_extra::record_table")]
+    target: surrealdb::RecordId,
+    #[serde(with = "atopio_extra::record_table::option")]
+    actor: Option<surrealdb::RecordId>,
+}
+
+#[test]
+fn test_record_table_serialize() -> Result<(), Box<dyn std::error::Error>> {
+    let event = AuditEvent {
+        target: surrealdb::RecordId::from_str("user:⟨has space⟩")?,
+        actor: Some(surrealdb::RecordId::from(("admin", 42))),
+    };
+    assert_eq!(
+        serde_json::to_string(&event)?,
+        "{\"target\":\"user\",\"actor\":\"admin\"}"
+    );
+
+    let event = AuditEvent {
+        target: surrealdb::RecordId::from_str("reading:['sensor1', 1717000000]")?,
+        actor: None,
+    };
+    assert_eq!(
+        serde_json::to_string(&event)?,
+        "{\"target\":\"reading\",\"actor\":null}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_record_table_deserialize_errors() {
+    let err = serde_json::from_str::<AuditEvent>("{\"target\":\"user\",\"actor\":null}")
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("only serializes"), "{err}");
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "rkyv")]
+
+use atopio_extra::key_value::RecordIdParts;
+use atopio_extra::rkyv::{ArchivedRawKey, RawKey, RawRecordId};
+use atopio_extra::types::{FullId, Naked, OrdId};
+use atopio_extra::{Table, TypedId};
+use rkyv::rancor::Error;
+use std::str::FromStr;
+use surrealdb::RecordId;
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+struct Membership {
+    user: TypedId<User>,
+    group: FullId,
+    invited_by: Option<Naked<User>>,
+}
+
+#[test]
+fn test_rkyv_round_trips_every_key_kind() -> Result<(), Box<dyn std::error::Error>> {
+    for text in [
+        "user:abc",
+        "user:⟨42⟩",
+        "user:-7",
+        "user:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'",
+        "reading:['s1', 1717000000]",
+        "event:{ city: 'london', year: 2024 }",
+    ] {
+        let id = RecordId::from_str(text)?;
+
+        let bytes = rkyv::to_bytes::<Error>(&FullId(id.clone()))?;
+        assert_eq!(rkyv::from_bytes::<FullId, Error>(&bytes)?.0, id, "{text}");
+
+        let bytes = rkyv::to_bytes::<Error>(&OrdId(id.clone()))?;
+        assert_eq!(rkyv::from_bytes::<OrdId, Error>(&bytes)?.0, id, "{text}");
+
+        let bytes = rkyv::to_bytes::<Error>(&RecordIdParts::try_from(&id)?)?;
+        let parts = rkyv::from_bytes::<RecordIdParts, Error>(&bytes)?;
+        assert_eq!(RecordId::try_from(parts)?, id, "{text}");
+
+        let raw = RawRecordId::from(&id);
+        assert_eq!(RecordId::try_from(raw)?, id, "{text}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rkyv_archive_is_read_in_place() -> Result<(), Box<dyn std::error::Error>> {
+    let membership = Membership {
+        user: TypedId::new("abc"),
+        group: FullId(RecordId::from_str("group:⟨42⟩")?),
+        invited_by: Some(Naked::new(7)),
+    };
+    let bytes = rkyv::to_bytes::<Error>(&membership)?;
+
+    let archived = rkyv::access::<ArchivedMembership, Error>(&bytes)?;
+    assert_eq!(archived.user.table.as_str(), "user");
+    assert!(matches!(&archived.user.key, ArchivedRawKey::String(s) if s.as_str() == "abc"));
+    assert_eq!(archived.group.table.as_str(), "group");
+    assert!(matches!(&archived.group.key, ArchivedRawKey::String(s) if s.as_str() == "42"));
+    let invited_by = archived.invited_by.as_ref().ok_or("missing")?;
+    assert!(matches!(invited_by.key, ArchivedRawKey::Int(n) if n == 7));
+    assert_eq!(invited_by.to_record_id()?.to_string(), "user:7");
+
+    let back = rkyv::from_bytes::<Membership, Error>(&bytes)?;
+    assert_eq!(back.user, membership.user);
+    assert_eq!(back.group, membership.group);
+    assert_eq!(
+        back.invited_by.map(|id| id.id().clone()),
+        Some(RecordId::from_table_key("user", 7))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rkyv_uuid_and_compound_keys_are_not_strings() -> Result<(), Box<dyn std::error::Error>> {
+    let id = RecordId::from_str("user:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'")?;
+    let RawKey::Uuid(bytes) = RawRecordId::from(&id).key else {
+        return Err("not a uuid key".into());
+    };
+    assert_eq!(
+        surrealdb::Uuid::from_bytes(bytes).to_string(),
+        "0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10"
+    );
+
+    let id = RecordId::from_str("reading:['s1', 1]")?;
+    assert_eq!(
+        RawRecordId::from(&id).key,
+        RawKey::Other("['s1', 1]".to_owned())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rkyv_compound_keys_archive_as_text() -> Result<(), Box<dyn std::error::Error>> {
+    let id = RecordId::from_str(
+        "reading:[d'2024-06-01T10:00:00Z', 1.5f, { tags: ['a', 'b'], at: 3dec }]",
+    )?;
+    let bytes = rkyv::to_bytes::<Error>(&FullId(id.clone()))?;
+
+    // In place, the key is only its SurrealQL text; the values come back when it is parsed.
+    let archived = rkyv::access::<rkyv::Archived<FullId>, Error>(&bytes)?;
+    let ArchivedRawKey::Other(text) = &archived.key else {
+        return Err("not a text key".into());
+    };
+    assert_eq!(text.as_str(), id.key().to_string());
+    assert_eq!(archived.to_record_id()?, id);
+    assert_eq!(rkyv::from_bytes::<FullId, Error>(&bytes)?.0, id);
+    Ok(())
+}
+
+#[test]
+fn test_rkyv_typed_id_rejects_other_table() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = rkyv::to_bytes::<Error>(&FullId(RecordId::from_str("post:abc")?))?;
+    let err = rkyv::from_bytes::<TypedId<User>, Error>(&bytes)
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("post"), "{err}");
+    assert!(rkyv::from_bytes::<Naked<User>, Error>(&bytes).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_rkyv_rejects_invalid_other_key() {
+    let raw = RawRecordId {
+        table: "user".to_owned(),
+        key: RawKey::Other("['unclosed".to_owned()),
+    };
+    assert_eq!(
+        RecordId::try_from(raw)
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default(),
+        r#""['unclosed" is not a valid record id key"#
+    );
+}
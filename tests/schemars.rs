@@ -0,0 +1,96 @@
+#![cfg(feature = "schemars")]
+
+use schemars::{JsonSchema, schema_for};
+use serde::Serialize;
+use serde_json::json;
+
+use atopio_extra::types::{FullId, Naked};
+use atopio_extra::{Table, TypedId};
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[derive(Serialize, JsonSchema)]
+struct Post {
+    #[serde(with = "atopio_extra::record_id_full")]
+    #[schemars(schema_with = "atopio_extra::record_id_full::json_schema")]
+    id: surrealdb::RecordId,
+    #[serde(with = "atopio_extra::record_id_naked")]
+    #[schemars(schema_with = "atopio_extra::record_id_naked::json_schema")]
+    slug: surrealdb::RecordId,
+    #[serde(with = "atopio_extra::record_id_full::option")]
+    #[schemars(schema_with = "atopio_extra::record_id_full::option::json_schema")]
+    parent: Option<surrealdb::RecordId>,
+    author: TypedId<User>,
+    editor: Naked<User>,
+    origin: FullId,
+}
+
+#[test]
+fn test_schemars_post_schema() -> Result<(), Box<dyn std::error::Error>> {
+    let schema = serde_json::to_value(schema_for!(Post))?;
+
+    assert_eq!(
+        schema,
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Post",
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "pattern": "^[A-Za-z0-9_]+:.+$",
+                    "description": "A SurrealDB record id in table:key form, for example `user:abc123`."
+                },
+                "slug": {
+                    "type": "string",
+                    "minLength": 1,
+                    "description": "The key of a SurrealDB record id, without the table, for example `abc123`."
+                },
+                "parent": {
+                    "type": ["string", "null"],
+                    "pattern": "^[A-Za-z0-9_]+:.+$",
+                    "description": "A SurrealDB record id in table:key form, for example `user:abc123`."
+                },
+                "author": {
+                    "type": "string",
+                    "pattern": "^user:.+$",
+                    "description": "A SurrealDB record id in the `user` table, in table:key form, for example `user:abc123`."
+                },
+                "editor": {
+                    "type": "string",
+                    "minLength": 1,
+                    "description": "The key of a SurrealDB record id in the `user` table, without the table, for example `abc123`."
+                },
+                "origin": {
+                    "type": "string",
+                    "pattern": "^[A-Za-z0-9_]+:.+$",
+                    "description": "A SurrealDB record id in table:key form, for example `user:abc123`."
+                }
+            },
+            "required": ["id", "slug", "parent", "author", "editor", "origin"]
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_schemars_record_link_schema() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Author {
+        name: String,
+    }
+
+    let schema = serde_json::to_value(schema_for!(atopio_extra::link::RecordLink<Author>))?;
+    assert_eq!(schema["title"], "RecordLink_Author");
+    assert_eq!(schema["anyOf"][0]["pattern"], "^[A-Za-z0-9_]+:.+$");
+    assert_eq!(schema["anyOf"][1]["$ref"], "#/$defs/Author");
+    assert_eq!(schema["$defs"]["Author"]["required"], json!(["name"]));
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "serde_with")]
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use atopio_extra::Table;
+use atopio_extra::serde_as::{RecordIdFull, RecordIdNaked, RecordIdNakedFor};
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+struct ContainerAs {
+    #[serde_as(as = "RecordIdFull")]
+    id: surrealdb::RecordId,
+    #[serde_as(as = "Option<RecordIdFull>")]
+    parent: Option<surrealdb::RecordId>,
+    #[serde_as(as = "Vec<RecordIdFull>")]
+    tags: Vec<surrealdb::RecordId>,
+    #[serde_as(as = "HashMap<_, Vec<RecordIdFull>>")]
+    groups: HashMap<String, Vec<surrealdb::RecordId>>,
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+struct ContainerNakedAs {
+    #[serde_as(as = "RecordIdNaked")]
+    id: surrealdb::RecordId,
+    #[serde_as(as = "Vec<RecordIdNakedFor<User>>")]
+    friends: Vec<surrealdb::RecordId>,
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+struct ContainerNakedForAs {
+    #[serde_as(as = "Vec<RecordIdNakedFor<User>>")]
+    friends: Vec<surrealdb::RecordId>,
+}
+
+#[derive(Serialize)]
+struct ContainerModules {
+    #[serde(with = "atopio_extra::record_id_full")]
+    id: surrealdb::RecordId,
+    #[serde(with = "atopio_extra::record_id_full::option")]
+    parent: Option<surrealdb::RecordId>,
+    #[serde(with = "atopio_extra::record_id_full::vec")]
+    tags: Vec<surrealdb::RecordId>,
+}
+
+#[test]
+fn test_serde_as_full_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:abc")?;
+    let tag = surrealdb::RecordId::from_str("tag:rust")?;
+    let container = ContainerAs {
+        id: id.clone(),
+        parent: None,
+        tags: vec![tag.clone()],
+        groups: HashMap::from([("admins".to_string(), vec![id.clone()])]),
+    };
+
+    let s = serde_json::to_string(&container)?;
+    assert_eq!(
+        s,
+        "{\"id\":\"user:abc\",\"parent\":null,\"tags\":[\"tag:rust\"],\"groups\":{\"admins\":[\"user:abc\"]}}"
+    );
+
+    let parsed: ContainerAs = serde_json::from_str(&s)?;
+    assert_eq!(parsed.id, id);
+    assert_eq!(parsed.parent, None);
+    assert_eq!(parsed.tags, vec![tag]);
+    assert_eq!(parsed.groups["admins"], vec![id]);
+
+    Ok(())
+}
+
+#[test]
+fn test_serde_as_matches_modules() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:⟨has space⟩")?;
+    let parent = surrealdb::RecordId::from_str("user:42")?;
+
+    let modules = serde_json::to_string(&ContainerModules {
+        id: id.clone(),
+        parent: Some(parent.clone()),
+        tags: vec![id.clone()],
+    })?;
+    let adapters = serde_json::to_string(&ContainerAs {
+        id: id.clone(),
+        parent: Some(parent),
+        tags: vec![id],
+        groups: HashMap::new(),
+    })?;
+
+    assert_eq!(adapters, modules.replace('}', ",\"groups\":{}}"));
+
+    Ok(())
+}
+
+#[test]
+fn test_serde_as_naked() -> Result<(), Box<dyn std::error::Error>> {
+    let container = ContainerNakedAs {
+        id: surrealdb::RecordId::from_str("user:abc")?,
+        friends: vec![surrealdb::RecordId::from_str("user:def")?],
+    };
+
+    let s = serde_json::to_string(&container)?;
+    assert_eq!(s, "{\"id\":\"abc\",\"friends\":[\"def\"]}");
+
+    assert!(serde_json::from_str::<ContainerNakedAs>(&s).is_err());
+    let bytes = postcard::to_allocvec(&container)?;
+    assert_eq!(
+        postcard::from_bytes::<ContainerNakedAs>(&bytes)?.id,
+        container.id
+    );
+
+    let parsed: ContainerNakedForAs = serde_json::from_str("{\"friends\":[\"def\",7]}")?;
+    assert_eq!(
+        parsed.friends,
+        vec![
+            surrealdb::RecordId::from_str("user:def")?,
+            surrealdb::RecordId::from_str("user:7")?
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use atopio_extra::slug::{self, SlugError};
+use std::str::FromStr;
+use surrealdb::RecordId;
+
+fn round_trip(text: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let id = RecordId::from_str(text)?;
+    let encoded = slug::encode(&id);
+    assert!(
+        encoded
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"-._~".contains(&b)),
+        "{encoded} needs percent-encoding"
+    );
+    assert_eq!(slug::decode(&encoded)?, id, "{encoded} did not round-trip");
+    Ok(encoded)
+}
+
+#[test]
+fn test_slug_simple_keys_are_raw() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(round_trip("user:abc123")?, "user.abc123");
+    assert_eq!(round_trip("user:snake_case_key")?, "user.snake_case_key");
+    assert_eq!(round_trip("user:42")?, "user.42");
+    assert_eq!(round_trip("user:-7")?, "user.-7");
+    assert_eq!(round_trip("user_profile:abc")?, "user_profile.abc");
+    Ok(())
+}
+
+#[test]
+fn test_slug_encoded_string_keys() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(round_trip("user:⟨has spaces⟩")?, "user~aGFzIHNwYWNlcw");
+    assert_eq!(
+        round_trip("user:⟨héllo/world?⟩")?,
+        "user~aMOpbGxvL3dvcmxkPw"
+    );
+    assert_eq!(round_trip("user:⟨a-b⟩")?, "user~YS1i");
+
+    // A string key that looks like an integer stays distinct from the integer key.
+    assert_eq!(round_trip("user:⟨42⟩")?, "user~NDI");
+    assert_ne!(slug::decode("user~NDI")?, slug::decode("user.42")?);
+
+    let empty = RecordId::from_table_key("user", "");
+    assert_eq!(slug::encode(&empty), "user~");
+    assert_eq!(slug::decode("user~")?, empty);
+    Ok(())
+}
+
+#[test]
+fn test_slug_structured_keys() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(round_trip("reading:['s1', 1]")?, "reading-WydzMScsIDFd");
+    assert_eq!(
+        round_trip("event:{ city: 'london', year: 2024 }")?,
+        "event-eyBjaXR5OiAnbG9uZG9uJywgeWVhcjogMjAyNCB9"
+    );
+    assert_eq!(
+        round_trip("user:u'0189e3f0-0000-7000-8000-000000000000'")?,
+        "user-dScwMTg5ZTNmMC0wMDAwLTcwMDAtODAwMC0wMDAwMDAwMDAwMDAn"
+    );
+    round_trip("reading:[{ at: d'2024-06-01T00:00:00Z' }, ['nested', 1.5]]")?;
+    Ok(())
+}
+
+#[test]
+fn test_slug_escaped_table() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(round_trip("⟨my table⟩:abc")?, "~4p-obXkgdGFibGXin6k6YWJj");
+    round_trip("⟨my-table⟩:⟨has spaces⟩")?;
+    Ok(())
+}
+
+#[test]
+fn test_slug_decode_errors() {
+    let error = |slug: &str| slug::decode(slug).err();
+
+    assert_eq!(error("user"), Some(SlugError::Malformed("user".to_owned())));
+    assert!(matches!(error(".abc"), Some(SlugError::Malformed(_))));
+    assert!(matches!(
+        error("user.has space"),
+        Some(SlugError::Malformed(_))
+    ));
+    assert!(matches!(error("user."), Some(SlugError::Malformed(_))));
+    assert!(matches!(error("user.007"), Some(SlugError::Malformed(_))));
+    assert!(matches!(
+        error("user.99999999999999999999"),
+        Some(SlugError::Malformed(_))
+    ));
+    assert!(matches!(
+        error("user~!!"),
+        Some(SlugError::InvalidEncoding(_))
+    ));
+    assert!(matches!(
+        error("user~_w"),
+        Some(SlugError::InvalidEncoding(_))
+    ));
+
+    // Encoded parts must hold the kind of key their separator says.
+    assert!(matches!(error("user-NDI"), Some(SlugError::InvalidId(_))));
+    assert!(matches!(
+        error("user-WydzMSc"),
+        Some(SlugError::InvalidId(_))
+    ));
+    assert!(matches!(
+        error("~dXNlcjphYmM"),
+        Some(SlugError::InvalidId(text)) if text == "user:abc"
+    ));
+
+    let message = slug::decode("user")
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(message.contains("not a record id slug"));
+}
@@ -0,0 +1,145 @@
+use atopio_extra::surrealql::ParamNameError;
+use atopio_extra::{key_to_surrealql, to_surrealql, to_surrealql_param_name};
+use proptest::prelude::*;
+use std::str::FromStr;
+use surrealdb::RecordId;
+
+/// Renders `id` and checks that both the record id parser and the query value parser read it
+/// back as the same id.
+fn render(id: &RecordId) -> String {
+    let text = to_surrealql(id);
+    assert_eq!(
+        RecordId::from_str(&text).ok().as_ref(),
+        Some(id),
+        "{text} did not parse back"
+    );
+    match surrealdb::syn::value(&text) {
+        Ok(surrealdb::sql::Value::Thing(thing)) => {
+            assert_eq!(thing.tb, id.table(), "{text}");
+            assert_eq!(&thing.id, id.key().into_inner_ref(), "{text}");
+        }
+        other => panic!("{text} is not a record id value: {other:?}"),
+    }
+    text
+}
+
+#[test]
+fn test_to_surrealql_simple_ids_match_to_string() -> Result<(), Box<dyn std::error::Error>> {
+    for text in [
+        "user:abc123",
+        "user:_key",
+        "user:42",
+        "user:-9223372036854775808",
+        "user:⟨42⟩",
+        "user:⟨has space⟩",
+        "user:u'0189e3f0-6d2b-7c00-8a3f-3b2d4f6a7c10'",
+        "reading:['s1', 1717000000]",
+        "event:{ city: 'london', year: 2024 }",
+        "user_profile:abc",
+    ] {
+        let id = RecordId::from_str(text)?;
+        assert_eq!(render(&id), id.to_string());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_to_surrealql_escapes_tables() {
+    let cases = [
+        ("select", "`select`:abc"),
+        ("NONE", "`NONE`:abc"),
+        ("true", "`true`:abc"),
+        ("fn", "`fn`:abc"),
+        ("Ml", "`Ml`:abc"),
+        ("function", "`function`:abc"),
+        ("1abc", "`1abc`:abc"),
+        ("123", "`123`:abc"),
+        ("_", "`_`:abc"),
+        ("", "``:abc"),
+        ("has space", "`has space`:abc"),
+        ("a`b", r"`a\`b`:abc"),
+        (r"a\b", r"`a\\b`:abc"),
+        ("é", "`é`:abc"),
+    ];
+    for (table, expected) in cases {
+        assert_eq!(render(&RecordId::from_table_key(table, "abc")), expected);
+    }
+}
+
+#[test]
+fn test_to_surrealql_escapes_string_keys() {
+    let cases = [
+        ("NaN", "user:⟨NaN⟩"),
+        ("infinity", "user:⟨infinity⟩"),
+        ("1e10", "user:⟨1e10⟩"),
+        ("1d", "user:⟨1d⟩"),
+        ("select", "user:⟨select⟩"),
+        ("", "user:⟨⟩"),
+        ("a⟩b", r"user:⟨a\⟩b⟩"),
+        (r"a\b", r"user:⟨a\\b⟩"),
+        ("a`b", "user:⟨a`b⟩"),
+    ];
+    for (key, expected) in cases {
+        let id = RecordId::from_table_key("user", key);
+        assert_eq!(render(&id), expected);
+        assert_eq!(key_to_surrealql(id.key()), expected["user:".len()..]);
+    }
+}
+
+#[test]
+fn test_to_surrealql_param_name() {
+    assert_eq!(to_surrealql_param_name("owner").as_deref(), Ok("$owner"));
+    assert_eq!(
+        to_surrealql_param_name("_page_2").as_deref(),
+        Ok("$_page_2")
+    );
+    for name in ["", "1a", "a-b", "a b", "é", "$a"] {
+        assert_eq!(
+            to_surrealql_param_name(name),
+            Err(ParamNameError::Invalid(name.to_owned()))
+        );
+    }
+    assert_eq!(
+        to_surrealql_param_name("auth"),
+        Err(ParamNameError::Protected("auth".to_owned()))
+    );
+    assert_eq!(
+        to_surrealql_param_name("session")
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default(),
+        "$session is reserved for the session"
+    );
+}
+
+fn arb_name() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-zA-Z_][a-zA-Z0-9_]{0,8}",
+        "[0-9]{1,4}[a-z]{0,3}",
+        r"[a-z0-9 _`⟩⟨\\'.:-]{0,8}",
+        Just("select".to_owned()),
+        Just("NaN".to_owned()),
+        Just("Infinity".to_owned()),
+        Just("ml".to_owned()),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_to_surrealql_round_trips_string_keys(table in arb_name(), key in arb_name()) {
+        render(&RecordId::from_table_key(table, key));
+    }
+
+    #[test]
+    fn test_to_surrealql_round_trips_integer_keys(table in arb_name(), key in any::<i64>()) {
+        render(&RecordId::from_table_key(table, key));
+    }
+
+    #[test]
+    fn test_to_surrealql_param_name_parses(name in r"[a-zA-Z0-9_$ -]{0,8}") {
+        if let Ok(param) = to_surrealql_param_name(&name) {
+            let query = surrealdb::syn::parse(&format!("RETURN {param}"));
+            prop_assert_eq!(query.map(|q| q.to_string()).ok(), Some(format!("RETURN {param};")));
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use atopio_extra::Table;
+use atopio_extra::thing::{record_id_to_thing, thing_to_record_id};
+use surrealdb::sql::Thing;
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[test]
+fn test_thing_record_id_conversions() -> Result<(), Box<dyn std::error::Error>> {
+    for raw in [
+        "user:abc123",
+        "user:42",
+        "user:⟨has space⟩",
+        "user:u'018f3c1e-0000-7000-8000-000000000000'",
+        "reading:['sensor1', 1717000000, ['nested', 'a,b']]",
+        "event:{ city: 'london', at: d'2024-01-01T00:00:00Z', tags: ['x'] }",
+    ] {
+        let id = surrealdb::RecordId::from_str(raw)?;
+        let thing = record_id_to_thing(&id);
+        assert_eq!(thing.to_string(), id.to_string());
+        assert_eq!(thing, surrealdb::sql::thing(raw)?);
+        assert_eq!(thing_to_record_id(&thing)?, id);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_thing_to_record_id_errors() -> Result<(), Box<dyn std::error::Error>> {
+    for raw in ["user:rand()", "user:ulid()", "user:uuid()"] {
+        let thing = surrealdb::sql::thing(raw)?;
+        let err = thing_to_record_id(&thing)
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default();
+        assert!(err.contains("does not identify a single record"), "{err}");
+    }
+
+    let surrealdb::sql::Value::Thing(range) = surrealdb::sql::value("user:1..10")? else {
+        return Err("expected a thing".into());
+    };
+    assert!(thing_to_record_id(&range).is_err());
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ContainerThing {
+    #[serde(with = "atopio_extra::thing_full")]
+    id: Thing,
+    #[serde(default, with = "atopio_extra::thing_full::option")]
+    parent: Option<Thing>,
+    #[serde(
+        serialize_with = "atopio_extra::thing_naked::serialize",
+        deserialize_with = "atopio_extra::thing_naked::deserialize_for::<User, _>"
+    )]
+    owner: Thing,
+}
+
+#[test]
+fn test_thing_serde_modules() -> Result<(), Box<dyn std::error::Error>> {
+    let c = ContainerThing {
+        id: surrealdb::sql::thing("reading:['sensor1', 1717000000]")?,
+        parent: None,
+        owner: surrealdb::sql::thing("user:⟨has space⟩")?,
+    };
+
+    let s = serde_json::to_string(&c)?;
+    assert_eq!(
+        s,
+        "{\"id\":\"reading:['sensor1', 1717000000]\",\"parent\":null,\"owner\":\"has space\"}"
+    );
+
+    let parsed: ContainerThing = serde_json::from_str(&s)?;
+    assert_eq!(parsed.id, c.id);
+    assert_eq!(parsed.parent, None);
+    assert_eq!(parsed.owner, c.owner);
+
+    let generated = ContainerThing {
+        owner: surrealdb::sql::thing("user:rand()")?,
+        ..c
+    };
+    assert!(serde_json::to_string(&generated).is_err());
+
+    Ok(())
+}
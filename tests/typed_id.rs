@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use atopio_extra::typed_id::TableMismatch;
+use atopio_extra::{Table, TypedId};
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerTyped {
+    id: TypedId<User>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContainerTypedNaked {
+    #[serde(with = "atopio_extra::typed_id::naked")]
+    id: TypedId<User>,
+}
+
+#[test]
+fn test_typed_id_try_from() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:abc")?;
+    let typed = TypedId::<User>::try_from(id.clone())?;
+    assert_eq!(typed, TypedId::new("abc"));
+    assert_eq!(surrealdb::RecordId::from(typed), id);
+
+    let err = TypedId::<User>::try_from(surrealdb::RecordId::from_str("post:abc")?)
+        .err()
+        .ok_or("expected a table mismatch")?;
+    assert_eq!(
+        err,
+        TableMismatch {
+            expected: "user",
+            found: "post".into()
+        }
+    );
+    assert_eq!(err.to_string(), "expected table \"user\", got \"post\"");
+
+    Ok(())
+}
+
+#[test]
+fn test_typed_id_full_serde() -> Result<(), Box<dyn std::error::Error>> {
+    let c = ContainerTyped {
+        id: TypedId::new(42),
+    };
+
+    let s = serde_json::to_string(&c)?;
+    assert_eq!(s, "{\"id\":\"user:42\"}");
+
+    let parsed: ContainerTyped = serde_json::from_str(&s)?;
+    assert_eq!(parsed.id, c.id);
+
+    let err = serde_json::from_str::<ContainerTyped>("{\"id\":\"post:42\"}")
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(
+        err.contains("expected table \"user\", got \"post\""),
+        "{err}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_typed_id_naked_serde() -> Result<(), Box<dyn std::error::Error>> {
+    let c = ContainerTypedNaked {
+        id: TypedId::new("abc"),
+    };
+
+    let s = serde_json::to_string(&c)?;
+    assert_eq!(s, "{\"id\":\"abc\"}");
+
+    let parsed: ContainerTypedNaked = serde_json::from_str(&s)?;
+    assert_eq!(parsed.id, c.id);
+
+    Ok(())
+}
+
+#[test]
+fn test_typed_id_hash() {
+    let set: HashSet<TypedId<User>> = [TypedId::new("a"), TypedId::new("a"), TypedId::new("b")]
+        .into_iter()
+        .collect();
+    assert_eq!(set.len(), 2);
+}
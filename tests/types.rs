@@ -0,0 +1,132 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use atopio_extra::types::{FullId, IdsParam, OrdId};
+use surrealdb::RecordId;
+use surrealdb::Surreal;
+use surrealdb::engine::local::Mem;
+
+#[derive(Serialize, Deserialize)]
+struct ContainerSet {
+    #[serde(with = "atopio_extra::record_id_full::set")]
+    members: HashSet<FullId>,
+}
+
+#[test]
+fn test_full_id_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let id = surrealdb::RecordId::from_str("user:abc123")?;
+    let full = FullId::from(id.clone());
+
+    let s = serde_json::to_string(&full)?;
+    assert_eq!(s, "\"user:abc123\"");
+
+    let parsed: FullId = serde_json::from_str(&s)?;
+    assert_eq!(parsed, full);
+    assert_eq!(surrealdb::RecordId::from(parsed), id);
+
+    Ok(())
+}
+
+#[test]
+fn test_full_id_set_sorted_and_deduplicated() -> Result<(), Box<dyn std::error::Error>> {
+    let parsed: ContainerSet =
+        serde_json::from_str("{\"members\":[\"user:c\",\"user:a\",\"user:b\",\"user:a\"]}")?;
+    assert_eq!(parsed.members.len(), 3);
+    assert!(
+        parsed
+            .members
+            .contains(&FullId(surrealdb::RecordId::from_str("user:b")?))
+    );
+
+    let s = serde_json::to_string(&parsed)?;
+    assert_eq!(s, "{\"members\":[\"user:a\",\"user:b\",\"user:c\"]}");
+
+    Ok(())
+}
+
+#[test]
+fn test_ord_id_numeric_keys_sort_numerically() -> Result<(), Box<dyn std::error::Error>> {
+    let two = OrdId(surrealdb::RecordId::from_str("user:2")?);
+    let ten = OrdId(surrealdb::RecordId::from_str("user:10")?);
+    assert!(two < ten);
+
+    let post = OrdId(surrealdb::RecordId::from_str("post:99")?);
+    assert!(post < two);
+
+    Ok(())
+}
+
+#[test]
+fn test_ord_id_btree_set_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let mut set = BTreeSet::new();
+    for raw in ["user:10", "user:2", "post:a", "user:b"] {
+        set.insert(OrdId(surrealdb::RecordId::from_str(raw)?));
+    }
+
+    let s = serde_json::to_string(&set)?;
+    assert_eq!(s, "[\"post:a\",\"user:2\",\"user:10\",\"user:b\"]");
+
+    let parsed: BTreeSet<OrdId> = serde_json::from_str(&s)?;
+    assert_eq!(parsed, set);
+
+    Ok(())
+}
+
+#[test]
+fn test_ord_id_btree_map_keys() -> Result<(), Box<dyn std::error::Error>> {
+    let map = BTreeMap::from([
+        (OrdId(surrealdb::RecordId::from_str("user:10")?), 1),
+        (OrdId(surrealdb::RecordId::from_str("user:2")?), 2),
+    ]);
+
+    let s = serde_json::to_string(&map)?;
+    assert_eq!(s, "{\"user:2\":2,\"user:10\":1}");
+
+    let parsed: BTreeMap<OrdId, i32> = serde_json::from_str(&s)?;
+    assert_eq!(parsed, map);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ids_param_in_query() -> Result<(), Box<dyn std::error::Error>> {
+    let db = Surreal::new::<Mem>(()).await?;
+    db.use_ns("test").use_db("test").await?;
+    db.query("CREATE user:abc; CREATE user:42; CREATE user:⟨a b⟩; CREATE post:abc")
+        .await?
+        .check()?;
+
+    let ids: IdsParam = ["user:abc", "user:⟨a b⟩", "post:abc", "user:missing"]
+        .into_iter()
+        .map(RecordId::from_str)
+        .collect::<Result<_, _>>()?;
+    let mut response = db
+        .query("SELECT VALUE id FROM user WHERE id IN $ids ORDER BY id")
+        .bind(("ids", ids.clone()))
+        .await?;
+    let found: Vec<RecordId> = response.take(0)?;
+    assert_eq!(
+        found,
+        vec![
+            RecordId::from_str("user:⟨a b⟩")?,
+            RecordId::from_str("user:abc")?
+        ]
+    );
+
+    // The same ids bound as text match nothing.
+    let text: Vec<String> = ids.0.iter().map(|id| id.to_string()).collect();
+    let mut response = db
+        .query("SELECT VALUE id FROM user WHERE id IN $ids")
+        .bind(("ids", text))
+        .await?;
+    let found: Vec<RecordId> = response.take(0)?;
+    assert!(found.is_empty(), "{found:?}");
+
+    assert_eq!(
+        serde_json::to_string(&ids)?,
+        r#"["user:abc","user:⟨a b⟩","post:abc","user:missing"]"#
+    );
+    Ok(())
+}
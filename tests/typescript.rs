@@ -0,0 +1,64 @@
+#![cfg(any(feature = "ts-rs", feature = "specta"))]
+
+use atopio_extra::Table;
+use atopio_extra::link::RecordLink;
+use atopio_extra::types::{FullId, Naked};
+use atopio_extra::{RecordIdRange, TypedId};
+
+// specta's derive asks every generic argument of a field type for its own type, so the table
+// marker needs an implementation too, even though it never appears in the output.
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[allow(dead_code)]
+struct Author {
+    name: String,
+}
+
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[allow(dead_code)]
+struct Post {
+    author: TypedId<User>,
+    editor: Naked<User>,
+    origin: FullId,
+    reviewer: Option<TypedId<User>>,
+    range: RecordIdRange,
+    link: RecordLink<Author>,
+}
+
+#[cfg(feature = "ts-rs")]
+#[test]
+fn test_typescript_ts_rs_export() {
+    use ts_rs::{Config, TS};
+
+    let cfg = Config::default();
+    assert_eq!(
+        Post::decl(&cfg),
+        "type Post = { author: string, editor: string, origin: string, reviewer: string | null, \
+         range: string, link: string | Author, };"
+    );
+    assert_eq!(<TypedId<User>>::inline(&cfg), "string");
+}
+
+#[cfg(feature = "specta")]
+#[test]
+fn test_typescript_specta_export() -> Result<(), Box<dyn std::error::Error>> {
+    use specta::ts::{ExportConfiguration, export, inline};
+
+    let conf = ExportConfiguration::default();
+    assert_eq!(
+        export::<Post>(&conf)?,
+        "export type Post = { author: string; editor: string; origin: string; reviewer: string | null; \
+         range: string; link: string | Author }"
+    );
+    assert_eq!(inline::<TypedId<User>>(&conf)?, "string");
+
+    Ok(())
+}
@@ -0,0 +1,11 @@
+use atopio_extra::surreal_model;
+use surrealdb::RecordId;
+
+#[surreal_model(id = "naked")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Team {
+    name: String,
+    members: Vec<RecordId>,
+}
+
+fn main() {}
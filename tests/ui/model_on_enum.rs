@@ -0,0 +1,8 @@
+use atopio_extra::surreal_model;
+
+#[surreal_model(id = "full")]
+enum Id {
+    User(surrealdb::RecordId),
+}
+
+fn main() {}
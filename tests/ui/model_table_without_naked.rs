@@ -0,0 +1,10 @@
+use atopio_extra::surreal_model;
+
+struct User;
+
+#[surreal_model(id = "full", table = User)]
+struct Post {
+    id: surrealdb::RecordId,
+}
+
+fn main() {}
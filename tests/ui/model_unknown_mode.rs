@@ -0,0 +1,8 @@
+use atopio_extra::surreal_model;
+
+#[surreal_model(id = "short")]
+struct User {
+    id: surrealdb::RecordId,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use atopio_extra::surreal_model;
+use std::collections::HashMap;
+use surrealdb::RecordId;
+
+#[surreal_model(id = "full")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Scores {
+    scores: HashMap<RecordId, u32>,
+    parents: Option<Vec<RecordId>>,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use atopio_extra::SurrealTable;
+
+#[derive(SurrealTable)]
+#[surreal(name = "user")]
+struct User;
+
+fn main() {}
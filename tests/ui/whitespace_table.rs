@@ -0,0 +1,11 @@
+use atopio_extra::SurrealTable;
+
+#[derive(SurrealTable)]
+#[surreal(table = "   ")]
+struct User;
+
+#[derive(SurrealTable)]
+#[surreal(table = "user profile")]
+struct UserProfile;
+
+fn main() {}
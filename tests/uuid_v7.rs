@@ -0,0 +1,78 @@
+#![cfg(feature = "uuid")]
+
+use atopio_extra::{KeyKind, RecordIdExt, Table, TypedId, key_uuid_timestamp, new_uuid_v7_id};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+struct User;
+
+impl Table for User {
+    const NAME: &'static str = "user";
+}
+
+#[test]
+fn test_new_uuid_v7_id_has_uuid_key() -> Result<(), Box<dyn std::error::Error>> {
+    let id = new_uuid_v7_id("event");
+    assert_eq!(id.table(), "event");
+    assert_eq!(KeyKind::of(id.key()), KeyKind::Uuid);
+    assert_eq!(id.key_as_uuid()?.get_version_num(), 7);
+    assert!(id.to_string().starts_with("event:u'"));
+
+    let typed = TypedId::<User>::new_uuid_v7();
+    assert_eq!(typed.id().table(), "user");
+    assert_eq!(typed.id().key_as_uuid()?.get_version_num(), 7);
+    Ok(())
+}
+
+#[test]
+fn test_new_uuid_v7_id_is_monotonic() -> Result<(), Box<dyn std::error::Error>> {
+    let keys = (0..10_000)
+        .map(|_| new_uuid_v7_id("event").key_as_uuid())
+        .collect::<Result<Vec<_>, _>>()?;
+    assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let typed = (0..1_000)
+        .map(|_| TypedId::<User>::new_uuid_v7().id().key_as_uuid())
+        .collect::<Result<Vec<_>, _>>()?;
+    assert!(typed.windows(2).all(|pair| pair[0] < pair[1]));
+    Ok(())
+}
+
+#[test]
+fn test_new_uuid_v7_id_round_trips() -> Result<(), Box<dyn std::error::Error>> {
+    let id = new_uuid_v7_id("event");
+    assert_eq!(surrealdb::RecordId::from_str(&id.to_string())?, id);
+    Ok(())
+}
+
+#[test]
+fn test_key_uuid_timestamp() -> Result<(), Box<dyn std::error::Error>> {
+    let before = SystemTime::now() - Duration::from_millis(1);
+    let id = new_uuid_v7_id("event");
+    let after = SystemTime::now() + Duration::from_millis(1);
+
+    let timestamp = key_uuid_timestamp(&id).ok_or("missing timestamp")?;
+    assert!(before <= timestamp && timestamp <= after);
+
+    let fixed = surrealdb::RecordId::from_str("event:u'01900000-0000-7000-8000-000000000000'")?;
+    assert_eq!(
+        key_uuid_timestamp(&fixed),
+        Some(SystemTime::UNIX_EPOCH + Duration::from_millis(0x0190_0000_0000))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_key_uuid_timestamp_without_timestamp() -> Result<(), Box<dyn std::error::Error>> {
+    let v4 = surrealdb::RecordId::from_str("user:u'0e4b5a4e-6f7c-4d7a-9c1b-2f3a4b5c6d7e'")?;
+    assert_eq!(key_uuid_timestamp(&v4), None);
+
+    // A string key holding v7 text is not a uuid key.
+    let text = surrealdb::RecordId::from_str("user:⟨01900000-0000-7000-8000-000000000000⟩")?;
+    assert_eq!(key_uuid_timestamp(&text), None);
+    assert_eq!(
+        key_uuid_timestamp(&surrealdb::RecordId::from(("user", 42))),
+        None
+    );
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use atopio_extra::types::SurrealJWTClaimsPartial;
+use atopio_extra::{Expectations, Validation, ValidationFailure};
+use common::{at, claims};
+use std::time::Duration;
+
+mod common;
+
+#[test]
+fn test_validation_leeway_is_symmetric() {
+    let claims = claims(1_000, 2_000);
+    let strict = Validation::new();
+    assert!(strict.validate(&claims, at(1_000)).is_ok());
+    assert!(strict.validate(&claims, at(1_999)).is_ok());
+    assert!(strict.validate(&claims, at(2_000)).is_err());
+    assert!(strict.validate(&claims, at(999)).is_err());
+
+    let lenient = Validation::new().leeway(Duration::from_secs(30));
+    assert!(lenient.validate(&claims, at(2_029)).is_ok());
+    assert_eq!(
+        lenient
+            .validate(&claims, at(2_030))
+            .map_err(|e| e.failures().to_vec()),
+        Err(vec![ValidationFailure::Expired { exp: 2_000 }])
+    );
+    assert!(lenient.validate(&claims, at(970)).is_ok());
+    assert_eq!(
+        lenient
+            .validate(&claims, at(969))
+            .map_err(|e| e.failures().to_vec()),
+        Err(vec![ValidationFailure::NotYetValid { nbf: 1_000 }])
+    );
+
+    let huge = Validation::new().leeway(Duration::MAX);
+    assert!(huge.validate(&claims, at(u64::MAX / 2)).is_ok());
+    assert!(strict.validate(&self::claims(0, u64::MAX), at(1)).is_ok());
+}
+
+#[test]
+fn test_validation_reports_every_failure() {
+    let validation = Validation::new()
+        .leeway(Duration::from_secs(5))
+        .require_exp()
+        .expected_iss("surrealdb");
+
+    let mut claims = claims(3_000, 2_000);
+    let err = validation.validate(&claims, at(2_500)).unwrap_err();
+    assert_eq!(
+        err.failures(),
+        [
+            ValidationFailure::Expired { exp: 2_000 },
+            ValidationFailure::NotYetValid { nbf: 3_000 },
+            ValidationFailure::InvalidIssuer(Some("SurrealDB".into())),
+        ]
+    );
+    assert_eq!(
+        err.to_string(),
+        "the token expired at 2000; the token is not valid before 3000; the token was issued by \"SurrealDB\""
+    );
+
+    claims.iss = "surrealdb".into();
+    claims.nbf = 0;
+    claims.exp = 4_000;
+    assert!(validation.validate(&claims, at(2_500)).is_ok());
+}
+
+#[test]
+fn test_validation_partial_claims() {
+    let partial = SurrealJWTClaimsPartial::<String> {
+        nbf: Some(1_000),
+        ..Default::default()
+    };
+    assert!(
+        Validation::new()
+            .validate_partial(&partial, at(1_000))
+            .is_ok()
+    );
+
+    let err = Validation::new()
+        .require_exp()
+        .expected_iss("SurrealDB")
+        .validate_partial(&partial, at(500))
+        .unwrap_err();
+    assert_eq!(
+        err.failures(),
+        [
+            ValidationFailure::MissingExp,
+            ValidationFailure::NotYetValid { nbf: 1_000 },
+            ValidationFailure::InvalidIssuer(None),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_expectations() {
+    let claims = claims(1_000, 2_000);
+    assert!(
+        claims
+            .validate_expectations(&Expectations::default())
+            .is_ok()
+    );
+    let tenant = Expectations {
+        iss: Some("SurrealDB".into()),
+        ns: Some("app".into()),
+        db: Some("main".into()),
+        id_table: Some("user".into()),
+    };
+    assert!(claims.validate_expectations(&tenant).is_ok());
+
+    let err = claims
+        .validate_expectations(&Expectations {
+            db: Some("staging".into()),
+            iss: Some("gateway".into()),
+            ..tenant.clone()
+        })
+        .unwrap_err();
+    assert_eq!(
+        (err.claim(), err.expected(), err.actual()),
+        ("iss", "gateway", "SurrealDB")
+    );
+    assert_eq!(
+        err.to_string(),
+        r#"the `iss` claim is "SurrealDB", expected "gateway""#
+    );
+
+    let err = claims
+        .validate_expectations(&Expectations {
+            db: Some("staging".into()),
+            ..tenant.clone()
+        })
+        .unwrap_err();
+    assert_eq!(err.claim(), "DB");
+    assert_eq!(
+        err.redacted().to_string(),
+        r#"the `DB` claim is <redacted>, expected "staging""#
+    );
+}
+
+#[test]
+fn test_validate_expectations_id_table() {
+    let expectations = Expectations {
+        id_table: Some("user".into()),
+        ..Default::default()
+    };
+    let mut claims = claims(1_000, 2_000);
+    claims.id = "user:⟨ada@example.com⟩".into();
+    assert!(claims.validate_expectations(&expectations).is_ok());
+
+    claims.id = "admin:⟨ada@example.com⟩".into();
+    let err = claims.validate_expectations(&expectations).unwrap_err();
+    assert_eq!((err.claim(), err.expected()), ("ID", "user"));
+    assert_eq!(
+        err.to_string(),
+        r#"the `ID` claim is "admin:⟨ada@example.com⟩", expected a record id in table "user""#
+    );
+    let redacted = err.redacted().to_string();
+    assert!(
+        redacted.starts_with("the `ID` claim is admin:<redacted:"),
+        "{redacted}"
+    );
+    assert!(!redacted.contains("ada"));
+
+    claims.id = "ada@example.com".into();
+    let err = claims.validate_expectations(&expectations).unwrap_err();
+    assert_eq!(err.actual(), "ada@example.com");
+    assert_eq!(
+        err.redacted().to_string(),
+        r#"the `ID` claim is <redacted>, expected a record id in table "user""#
+    );
+
+    // Read as `subject_record_id` reads it, which rejects a key that generates a new record.
+    claims.id = "user:rand()".into();
+    assert!(claims.subject_record_id().is_err());
+    let err = claims.validate_expectations(&expectations).unwrap_err();
+    assert_eq!(err.actual(), "user:rand()");
+}
@@ -0,0 +1,193 @@
+#![cfg(feature = "verify")]
+
+use atopio_extra::types::SurrealJWTClaims;
+use atopio_extra::verify::Algorithm;
+use atopio_extra::{
+    Jwks, JwtError, Validation, VerifyingKey, encode_payload_insecurely, verify_with_jwks,
+    verify_with_jwks_at, verify_with_key, verify_with_key_at,
+};
+use jsonwebtoken::{EncodingKey, Header};
+use std::time::{Duration, UNIX_EPOCH};
+
+const RSA_PRIVATE: &[u8] = include_bytes!("keys/rsa_private.pem");
+const RSA_PUBLIC: &[u8] = include_bytes!("keys/rsa_public.pem");
+const RSA_PUBLIC_DER: &[u8] = include_bytes!("keys/rsa_public.der");
+const EC_PRIVATE: &[u8] = include_bytes!("keys/ec_private.pem");
+const EC_PUBLIC: &[u8] = include_bytes!("keys/ec_public.pem");
+const EC_PUBLIC_DER: &[u8] = include_bytes!("keys/ec_public.der");
+const JWKS: &[u8] = include_bytes!("keys/jwks.json");
+
+mod common;
+
+fn claims(exp: u64) -> SurrealJWTClaims<String> {
+    SurrealJWTClaims {
+        iss: "https://idp.example.com".into(),
+        ac: "idp".into(),
+        id: "user:ada".into(),
+        ..common::claims(1_000, exp)
+    }
+}
+
+fn sign(
+    claims: &SurrealJWTClaims<String>,
+    algorithm: Algorithm,
+) -> Result<String, Box<dyn std::error::Error>> {
+    sign_with_kid(claims, algorithm, None)
+}
+
+fn sign_with_kid(
+    claims: &SurrealJWTClaims<String>,
+    algorithm: Algorithm,
+    kid: Option<&str>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let key = match algorithm {
+        Algorithm::RS256 => EncodingKey::from_rsa_pem(RSA_PRIVATE)?,
+        _ => EncodingKey::from_ec_pem(EC_PRIVATE)?,
+    };
+    let mut header = Header::new(algorithm);
+    header.kid = kid.map(str::to_owned);
+    Ok(jsonwebtoken::encode(&header, claims, &key)?)
+}
+
+#[test]
+fn test_verify_with_key_rs256_and_es256() -> Result<(), Box<dyn std::error::Error>> {
+    let keys = [
+        (Algorithm::RS256, VerifyingKey::rsa_from_pem(RSA_PUBLIC)?),
+        (Algorithm::RS256, VerifyingKey::rsa_from_der(RSA_PUBLIC_DER)),
+        (Algorithm::ES256, VerifyingKey::ec_from_pem(EC_PUBLIC)?),
+        (Algorithm::ES256, VerifyingKey::ec_from_der(EC_PUBLIC_DER)),
+    ];
+    for (algorithm, key) in &keys {
+        let token = sign(&claims(u64::MAX), *algorithm)?;
+        let verified = verify_with_key::<String>(&token, key, *algorithm)?;
+        assert_eq!(verified.id, "user:ada");
+        assert_eq!(verified.iss, "https://idp.example.com");
+
+        let mut tampered = token.clone();
+        tampered.insert_str(token.find('.').ok_or("no payload")? + 1, "eyJ");
+        assert!(verify_with_key::<String>(&tampered, key, *algorithm).is_err());
+    }
+
+    let rs256 = sign(&claims(u64::MAX), Algorithm::RS256)?;
+    let ec_key = VerifyingKey::ec_from_pem(EC_PUBLIC)?;
+    assert!(matches!(
+        verify_with_key::<String>(&rs256, &ec_key, Algorithm::ES256),
+        Err(JwtError::UnsupportedAlgorithm(alg)) if alg == "RS256"
+    ));
+    assert!(matches!(
+        verify_with_key::<String>(&rs256, &ec_key, Algorithm::RS256),
+        Err(JwtError::InvalidSignature | JwtError::InvalidKey(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_verify_with_key_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let key = VerifyingKey::rsa_from_pem(RSA_PUBLIC)?;
+    let token = sign(&claims(2_000), Algorithm::RS256)?;
+    let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+
+    let validation = Validation::new().expected_iss("https://idp.example.com");
+    assert!(
+        verify_with_key_at::<String>(&token, &key, Algorithm::RS256, &validation, at(1_500))
+            .is_ok()
+    );
+    assert!(matches!(
+        verify_with_key_at::<String>(&token, &key, Algorithm::RS256, &validation, at(2_000)),
+        Err(JwtError::Validation(_))
+    ));
+    assert!(matches!(
+        verify_with_key::<String>(&token, &key, Algorithm::RS256),
+        Err(JwtError::Validation(_))
+    ));
+
+    let unsigned = encode_payload_insecurely(&claims(u64::MAX))?;
+    assert!(matches!(
+        verify_with_key::<String>(&unsigned, &key, Algorithm::RS256),
+        Err(JwtError::UnsupportedAlgorithm(alg)) if alg == "none"
+    ));
+
+    assert!(matches!(
+        VerifyingKey::rsa_from_pem(EC_PUBLIC),
+        Err(JwtError::InvalidKey(_))
+    ));
+    assert!(matches!(
+        VerifyingKey::ec_from_pem(b"not a key"),
+        Err(JwtError::InvalidKey(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_verify_with_jwks() -> Result<(), Box<dyn std::error::Error>> {
+    let jwks = Jwks::from_slice(JWKS)?;
+    assert_eq!(jwks.keys.len(), 4);
+    assert_eq!(
+        jwks.find("ec-2026").and_then(|key| key.crv.as_deref()),
+        Some("P-256")
+    );
+    assert!(jwks.find("rsa-enc").is_none());
+
+    for (algorithm, kid) in [
+        (Algorithm::RS256, "rsa-2026"),
+        (Algorithm::ES256, "ec-2026"),
+    ] {
+        let token = sign_with_kid(&claims(u64::MAX), algorithm, Some(kid))?;
+        let verified = verify_with_jwks::<String>(&token, &jwks)?;
+        assert_eq!(verified.id, "user:ada");
+    }
+
+    let token = sign_with_kid(&claims(2_000), Algorithm::RS256, Some("rsa-2026"))?;
+    let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+    assert!(verify_with_jwks_at::<String>(&token, &jwks, &Validation::new(), at(1_500)).is_ok());
+    assert!(matches!(
+        verify_with_jwks_at::<String>(&token, &jwks, &Validation::new(), at(2_000)),
+        Err(JwtError::Validation(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_verify_with_jwks_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let jwks = Jwks::from_slice(JWKS)?;
+    let claims = claims(u64::MAX);
+
+    let unknown = sign_with_kid(&claims, Algorithm::RS256, Some("rsa-2025"))?;
+    assert!(matches!(
+        verify_with_jwks::<String>(&unknown, &jwks),
+        Err(JwtError::UnknownKeyId(Some(kid))) if kid == "rsa-2025"
+    ));
+    let no_kid = sign(&claims, Algorithm::RS256)?;
+    assert!(matches!(
+        verify_with_jwks::<String>(&no_kid, &jwks),
+        Err(JwtError::UnknownKeyId(None))
+    ));
+    let encryption_key = sign_with_kid(&claims, Algorithm::RS256, Some("rsa-enc"))?;
+    assert!(matches!(
+        verify_with_jwks::<String>(&encryption_key, &jwks),
+        Err(JwtError::UnknownKeyId(Some(_)))
+    ));
+    let okp = sign_with_kid(&claims, Algorithm::ES256, Some("ed-2026"))?;
+    assert!(matches!(
+        verify_with_jwks::<String>(&okp, &jwks),
+        Err(JwtError::UnsupportedKeyType(kty)) if kty == "OKP"
+    ));
+
+    // An RS256 token naming the EC key, and an ES256 token naming the RSA key.
+    let wrong_key = sign_with_kid(&claims, Algorithm::RS256, Some("ec-2026"))?;
+    assert!(matches!(
+        verify_with_jwks::<String>(&wrong_key, &jwks),
+        Err(JwtError::UnsupportedAlgorithm(alg)) if alg == "RS256"
+    ));
+    let wrong_key = sign_with_kid(&claims, Algorithm::ES256, Some("rsa-2026"))?;
+    assert!(matches!(
+        verify_with_jwks::<String>(&wrong_key, &jwks),
+        Err(JwtError::UnsupportedAlgorithm(alg)) if alg == "ES256"
+    ));
+
+    assert!(matches!(
+        Jwks::from_slice(br#"{"keys":[{"kid":"k"}]}"#),
+        Err(JwtError::InvalidJson(_))
+    ));
+    Ok(())
+}